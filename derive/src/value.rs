@@ -3,13 +3,19 @@ use crate::{
     bound,
 };
 use proc_macro::TokenStream;
-use proc_macro2::Span;
+use proc_macro2::{
+    Span,
+    TokenStream as TokenStream2,
+};
 use syn::{
     Data,
+    DataEnum,
     DataStruct,
     DeriveInput,
     Fields,
     Ident,
+    Index,
+    Variant,
 };
 
 pub(crate) fn derive(input: DeriveInput) -> TokenStream {
@@ -57,24 +63,18 @@ pub(crate) fn derive_from_serde(input: DeriveInput) -> TokenStream {
 Construct an implementation of `sval::value::Value` based on the structure of the input.
 */
 pub(crate) fn derive_from_sval(input: DeriveInput) -> TokenStream {
-    let fields = match input.data {
-        Data::Struct(DataStruct {
-            fields: Fields::Named(fields),
-            ..
-        }) => fields,
-        _ => panic!("currently only structs with named fields are supported"),
-    };
-
-    let ident = input.ident;
+    let ident = &input.ident;
     let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
     let dummy = Ident::new(
         &format!("_IMPL_SVAL_VALUE_FOR_{}", ident),
         Span::call_site(),
     );
 
-    let fieldname = &fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
-    let fieldstr = fields.named.iter().map(attr::name_of_field);
-    let num_fields = fieldname.len();
+    let body = match input.data {
+        Data::Struct(DataStruct { ref fields, .. }) => stream_struct(fields),
+        Data::Enum(DataEnum { ref variants, .. }) => stream_enum(variants.iter()),
+        Data::Union(_) => panic!("unions are not supported"),
+    };
 
     let bound = parse_quote!(sval::value::Value);
     let bounded_where_clause = bound::where_clause_with_bound(&input.generics, bound);
@@ -86,16 +86,115 @@ pub(crate) fn derive_from_sval(input: DeriveInput) -> TokenStream {
 
             impl #impl_generics sval::value::Value for #ident #ty_generics #bounded_where_clause {
                 fn stream<'s, 'v>(&'v self, mut stream: sval::value::Stream<'s, 'v>) -> sval::value::Result {
-                    stream.map_begin(Some(#num_fields))?;
-
-                    #(
-                        stream.map_key(&#fieldstr)?;
-                        stream.map_value(&self.#fieldname)?;
-                    )*
-
-                    stream.map_end()
+                    #body
                 }
             }
         };
     })
 }
+
+/**
+Stream a struct.
+
+Structs with named fields are streamed as maps, tuple structs are
+streamed as sequences, and unit structs are streamed as empty values.
+*/
+fn stream_struct(fields: &Fields) -> TokenStream2 {
+    match fields {
+        Fields::Named(fields) => {
+            let fieldname = fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+            let fieldvalue = fieldname.iter().map(|f| quote!(&self.#f));
+
+            stream_map(fields.named.iter().map(attr::name_of_field), fieldvalue)
+        }
+        Fields::Unnamed(fields) => {
+            let fieldvalue = (0..fields.unnamed.len()).map(|i| {
+                let i = Index::from(i);
+                quote!(&self.#i)
+            });
+
+            stream_seq(fieldvalue)
+        }
+        Fields::Unit => quote!(stream.none()),
+    }
+}
+
+/**
+Stream an enum.
+
+Unit variants are streamed as their name, newtype variants are streamed
+as their inner value, and struct and tuple variants are streamed like
+structs with the same shape.
+*/
+fn stream_enum<'a>(variants: impl Iterator<Item = &'a Variant>) -> TokenStream2 {
+    let arms = variants.map(|variant| {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+
+        match variant.fields {
+            Fields::Named(ref fields) => {
+                let fieldname = fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+                let fieldbinding = (0..fields.named.len())
+                    .map(|i| Ident::new(&format!("__sval_field_{}", i), Span::call_site()))
+                    .collect::<Vec<_>>();
+                let body = stream_map(
+                    fields.named.iter().map(attr::name_of_field),
+                    fieldbinding.iter().map(|f| quote!(#f)),
+                );
+
+                quote!(Self::#variant_ident { #(#fieldname: ref #fieldbinding),* } => { #body })
+            }
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                quote!(Self::#variant_ident(ref inner) => stream.any(inner))
+            }
+            Fields::Unnamed(ref fields) => {
+                let fieldname = (0..fields.unnamed.len())
+                    .map(|i| Ident::new(&format!("__sval_field_{}", i), Span::call_site()))
+                    .collect::<Vec<_>>();
+                let body = stream_seq(fieldname.iter().map(|f| quote!(#f)));
+
+                quote!(Self::#variant_ident(#(ref #fieldname),*) => { #body })
+            }
+            Fields::Unit => quote!(Self::#variant_ident => stream.str(#variant_name)),
+        }
+    });
+
+    quote! {
+        match *self {
+            #(#arms,)*
+        }
+    }
+}
+
+fn stream_map(
+    key: impl Iterator<Item = String>,
+    value: impl Iterator<Item = TokenStream2>,
+) -> TokenStream2 {
+    let key = key.collect::<Vec<_>>();
+    let num_entries = key.len();
+
+    quote! {
+        stream.map_begin(Some(#num_entries))?;
+
+        #(
+            stream.map_key(&#key)?;
+            stream.map_value(#value)?;
+        )*
+
+        stream.map_end()
+    }
+}
+
+fn stream_seq(elem: impl ExactSizeIterator<Item = TokenStream2>) -> TokenStream2 {
+    let num_elems = elem.len();
+
+    quote! {
+        stream.seq_begin(Some(#num_elems))?;
+
+        #(
+            stream.seq_elem(#elem)?;
+        )*
+
+        stream.seq_end()
+    }
+}
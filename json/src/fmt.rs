@@ -148,6 +148,18 @@ where
         Ok(())
     }
 
+    fn f32(&mut self, v: f32) -> stream::Result {
+        if self.is_key {
+            return Err(sval::Error::unsupported(
+                "only strings are supported as json keys",
+            ));
+        }
+
+        self.out.write_str(ryu::Buffer::new().format(v))?;
+
+        Ok(())
+    }
+
     fn bool(&mut self, v: bool) -> stream::Result {
         if self.is_key {
             return Err(sval::Error::unsupported(
@@ -287,6 +299,17 @@ where
     }
 }
 
+impl<W> stream::Whitespace for Formatter<W>
+where
+    W: Write,
+{
+    fn whitespace(&mut self, ws: &str) -> stream::Result {
+        self.out.write_str(ws)?;
+
+        Ok(())
+    }
+}
+
 /*
 This `escape_str` implementation has been shamelessly lifted from dtolnay's `miniserde`:
 https://github.com/dtolnay/miniserde
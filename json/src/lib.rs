@@ -77,6 +77,19 @@ let json = sval_json::to_writer(MyWrite, &42)?;
 # Ok(())
 # }
 ```
+
+# Parsing JSON into a `Stream`
+
+```no_run
+# #[cfg(not(feature = "std"))]
+# fn main() {}
+# #[cfg(feature = "std")]
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+# let mut stream = sval_json::Formatter::new(String::new());
+sval_json::from_str("[1, 2, 3]", &mut stream)?;
+# Ok(())
+# }
+```
 */
 
 #![doc(html_root_url = "https://docs.rs/sval_json/1.0.0-alpha.5")]
@@ -94,6 +107,9 @@ pub use self::fmt::{
     Formatter,
 };
 
+mod parse;
+pub use self::parse::from_str;
+
 #[cfg(feature = "std")]
 mod std_support;
 
@@ -101,5 +117,6 @@ mod std_support;
 pub use self::std_support::{
     to_string,
     to_writer,
+    JsonFormat,
     Writer,
 };
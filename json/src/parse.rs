@@ -0,0 +1,441 @@
+use sval::stream::Stream;
+
+use crate::std::fmt::{
+    self,
+    Write,
+};
+
+#[cfg(feature = "std")]
+use crate::std::string::ToString;
+
+/**
+The maximum depth of maps and sequences that can be parsed.
+
+The parser is recursive, so this prevents deeply nested input
+from overflowing the call stack.
+*/
+const MAX_DEPTH: usize = 128;
+
+/**
+Parse json text into a [`Stream`].
+
+Strings that don't contain any escapes are streamed as borrowed
+slices of the input. Strings that do contain escapes are unescaped
+into a `String` when the `std` feature is enabled, and are streamed
+as formattable arguments otherwise, so parsing doesn't need to allocate.
+
+Numbers without a fraction or exponent are streamed as integers
+when they fit, and as floating points otherwise.
+Maps and sequences are streamed without a length hint.
+
+# Examples
+
+```
+# #[cfg(not(feature = "std"))]
+# fn main() {}
+# #[cfg(feature = "std")]
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+let mut stream = sval_json::Formatter::new(String::new());
+sval_json::from_str(r#"{ "id": 1, "tags": ["a", "b"] }"#, &mut stream)?;
+
+assert_eq!(r#"{"id":1,"tags":["a","b"]}"#, stream.into_inner());
+# Ok(())
+# }
+```
+*/
+pub fn from_str<'a>(input: &'a str, stream: &mut impl Stream<'a>) -> Result<(), sval::Error> {
+    let mut parser = Parser {
+        input,
+        pos: 0,
+        depth: 0,
+    };
+
+    parser.value(stream)?;
+
+    parser.skip_whitespace();
+    if parser.pos != input.len() {
+        return Err(sval::Error::msg("unexpected trailing characters"));
+    }
+
+    Ok(())
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), sval::Error> {
+        self.skip_whitespace();
+
+        if self.peek() != Some(expected) {
+            return Err(sval::Error::msg("unexpected character"));
+        }
+
+        self.pos += 1;
+
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: &str) -> Result<(), sval::Error> {
+        if !self.input[self.pos..].starts_with(literal) {
+            return Err(sval::Error::msg("invalid literal"));
+        }
+
+        self.pos += literal.len();
+
+        Ok(())
+    }
+
+    fn value(&mut self, stream: &mut impl Stream<'a>) -> Result<(), sval::Error> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.map(stream),
+            Some(b'[') => self.seq(stream),
+            Some(b'"') => self.str(stream),
+            Some(b't') => {
+                self.literal("true")?;
+                stream.bool(true)
+            }
+            Some(b'f') => {
+                self.literal("false")?;
+                stream.bool(false)
+            }
+            Some(b'n') => {
+                self.literal("null")?;
+                stream.none()
+            }
+            Some(b'-') | Some(b'0'..=b'9') => self.number(stream),
+            Some(_) => Err(sval::Error::msg("unexpected character")),
+            None => Err(sval::Error::msg("unexpected end of input")),
+        }
+    }
+
+    fn begin(&mut self) -> Result<(), sval::Error> {
+        self.depth += 1;
+
+        if self.depth > MAX_DEPTH {
+            return Err(sval::Error::msg("json is nested too deeply"));
+        }
+
+        // Skip the opening brace or bracket
+        self.pos += 1;
+
+        Ok(())
+    }
+
+    fn map(&mut self, stream: &mut impl Stream<'a>) -> Result<(), sval::Error> {
+        self.begin()?;
+        stream.map_begin(None)?;
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+        } else {
+            loop {
+                self.skip_whitespace();
+                if self.peek() != Some(b'"') {
+                    return Err(sval::Error::msg("expected a string map key"));
+                }
+
+                stream.map_key()?;
+                self.str(stream)?;
+
+                self.expect(b':')?;
+
+                stream.map_value()?;
+                self.value(stream)?;
+
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b'}') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(sval::Error::msg("expected `,` or `}`")),
+                }
+            }
+        }
+
+        self.depth -= 1;
+        stream.map_end()
+    }
+
+    fn seq(&mut self, stream: &mut impl Stream<'a>) -> Result<(), sval::Error> {
+        self.begin()?;
+        stream.seq_begin(None)?;
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+        } else {
+            loop {
+                stream.seq_elem()?;
+                self.value(stream)?;
+
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b']') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(sval::Error::msg("expected `,` or `]`")),
+                }
+            }
+        }
+
+        self.depth -= 1;
+        stream.seq_end()
+    }
+
+    fn str(&mut self, stream: &mut impl Stream<'a>) -> Result<(), sval::Error> {
+        // Skip the opening quote
+        self.pos += 1;
+
+        let start = self.pos;
+        let mut is_escaped = false;
+
+        loop {
+            match self.peek() {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    is_escaped = true;
+
+                    // Skip the escaped character so an escaped quote
+                    // isn't treated as the end of the string
+                    self.pos += 2;
+                }
+                Some(0x00..=0x1f) => {
+                    return Err(sval::Error::msg("unescaped control character in string"))
+                }
+                Some(_) => self.pos += 1,
+                None => return Err(sval::Error::msg("unterminated string")),
+            }
+        }
+
+        let raw = self
+            .input
+            .get(start..self.pos)
+            .ok_or_else(|| sval::Error::msg("unterminated string"))?;
+
+        // Skip the closing quote
+        self.pos += 1;
+
+        if !is_escaped {
+            return stream.str_borrowed(raw);
+        }
+
+        // Check the escapes up-front so formatting can't fail
+        unescape(raw, Ignore)?;
+
+        #[cfg(feature = "std")]
+        {
+            stream.str_owned(Unescape(raw).to_string())
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            stream.fmt(sval::stream::Arguments::display(&Unescape(raw)))
+        }
+    }
+
+    fn number(&mut self, stream: &mut impl Stream<'a>) -> Result<(), sval::Error> {
+        let start = self.pos;
+        let mut is_float = false;
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(sval::Error::msg("invalid number")),
+        }
+
+        if self.peek() == Some(b'.') {
+            is_float = true;
+            self.pos += 1;
+
+            if !self.at_digit() {
+                return Err(sval::Error::msg("invalid number"));
+            }
+            self.digits();
+        }
+
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            is_float = true;
+            self.pos += 1;
+
+            if let Some(b'+') | Some(b'-') = self.peek() {
+                self.pos += 1;
+            }
+
+            if !self.at_digit() {
+                return Err(sval::Error::msg("invalid number"));
+            }
+            self.digits();
+        }
+
+        let raw = &self.input[start..self.pos];
+
+        if !is_float {
+            if raw.starts_with('-') {
+                if let Ok(v) = raw.parse::<i64>() {
+                    return stream.i64(v);
+                }
+
+                if let Ok(v) = raw.parse::<i128>() {
+                    return stream.i128(v);
+                }
+            } else {
+                if let Ok(v) = raw.parse::<u64>() {
+                    return stream.u64(v);
+                }
+
+                if let Ok(v) = raw.parse::<u128>() {
+                    return stream.u128(v);
+                }
+            }
+        }
+
+        let v = raw
+            .parse::<f64>()
+            .map_err(|_| sval::Error::msg("invalid number"))?;
+
+        // Numbers too large for a `f64` parse as infinity, which isn't valid JSON
+        if !v.is_finite() {
+            return Err(sval::Error::msg("number out of range"));
+        }
+
+        stream.f64(v)
+    }
+
+    fn at_digit(&self) -> bool {
+        matches!(self.peek(), Some(b'0'..=b'9'))
+    }
+
+    fn digits(&mut self) {
+        while self.at_digit() {
+            self.pos += 1;
+        }
+    }
+}
+
+/**
+Write the unescaped contents of a json string.
+*/
+fn unescape(raw: &str, mut out: impl Write) -> Result<(), sval::Error> {
+    let mut rest = raw;
+
+    while let Some(i) = rest.find('\\') {
+        out.write_str(&rest[..i])?;
+
+        let escape = rest.as_bytes().get(i + 1).cloned();
+
+        // Valid escapes are always ascii, so this can't split a character
+        if let Some(b'"') | Some(b'\\') | Some(b'/') | Some(b'b') | Some(b'f') | Some(b'n')
+        | Some(b'r') | Some(b't') | Some(b'u') = escape
+        {
+            rest = &rest[i + 2..];
+        } else {
+            return Err(sval::Error::msg("invalid escape in string"));
+        }
+
+        let c = match escape {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\x08',
+            Some(b'f') => '\x0c',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                let hi = hex(&mut rest)?;
+
+                match hi {
+                    // A high surrogate must be followed by a low one
+                    0xd800..=0xdbff => {
+                        if !rest.starts_with("\\u") {
+                            return Err(sval::Error::msg("unpaired surrogate in string"));
+                        }
+                        rest = &rest[2..];
+
+                        let lo = hex(&mut rest)?;
+                        if !(0xdc00..=0xdfff).contains(&lo) {
+                            return Err(sval::Error::msg("unpaired surrogate in string"));
+                        }
+
+                        let c = 0x10000 + ((hi - 0xd800) << 10) + (lo - 0xdc00);
+                        crate::std::char::from_u32(c)
+                            .ok_or_else(|| sval::Error::msg("invalid unicode escape"))?
+                    }
+                    0xdc00..=0xdfff => {
+                        return Err(sval::Error::msg("unpaired surrogate in string"))
+                    }
+                    c => crate::std::char::from_u32(c)
+                        .ok_or_else(|| sval::Error::msg("invalid unicode escape"))?,
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        out.write_char(c)?;
+    }
+
+    out.write_str(rest)?;
+
+    Ok(())
+}
+
+/**
+Read the 4 hex digits of a unicode escape.
+*/
+fn hex(rest: &mut &str) -> Result<u32, sval::Error> {
+    let digits = rest
+        .get(..4)
+        .ok_or_else(|| sval::Error::msg("invalid unicode escape"))?;
+
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(sval::Error::msg("invalid unicode escape"));
+    }
+
+    let v =
+        u32::from_str_radix(digits, 16).map_err(|_| sval::Error::msg("invalid unicode escape"))?;
+
+    *rest = &rest[4..];
+
+    Ok(v)
+}
+
+struct Unescape<'a>(&'a str);
+
+impl<'a> fmt::Display for Unescape<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        unescape(self.0, f).map_err(sval::Error::into_fmt_error)
+    }
+}
+
+struct Ignore;
+
+impl Write for Ignore {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use sval::{
+    format::Format,
     stream::{
         self,
         Stream,
@@ -32,6 +33,37 @@ pub fn to_writer(writer: impl Write, v: impl Value) -> Result<(), sval::Error> {
     crate::to_fmt(FmtToIo(writer), v)
 }
 
+/**
+The JSON format.
+
+Values are written to a string using [`to_string`].
+
+# Examples
+
+```
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+use sval_json::JsonFormat;
+
+let json = sval::format::format_into::<JsonFormat>(&42)?;
+
+assert_eq!("42", json);
+# Ok(())
+# }
+```
+
+[`to_string`]: fn.to_string.html
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct JsonFormat;
+
+impl Format for JsonFormat {
+    type Output = String;
+
+    fn format(v: &(impl Value + ?Sized)) -> Result<Self::Output, sval::Error> {
+        to_string(v)
+    }
+}
+
 struct FmtToIo<W>(W);
 
 impl<W> fmt::Write for FmtToIo<W>
@@ -89,6 +121,15 @@ where
     }
 }
 
+impl<W> stream::Whitespace for Writer<W>
+where
+    W: Write,
+{
+    fn whitespace(&mut self, ws: &str) -> stream::Result {
+        self.0.whitespace(ws)
+    }
+}
+
 impl<'v, W> Stream<'v> for Writer<W>
 where
     W: Write,
@@ -121,6 +162,10 @@ where
         self.0.f64(v)
     }
 
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.0.f32(v)
+    }
+
     fn bool(&mut self, v: bool) -> stream::Result {
         self.0.bool(v)
     }
@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use sval::stream::IndentingStream;
+
+fn to_pretty_string(v: impl sval::value::Value, width: usize) -> String {
+    let mut stream = IndentingStream::new(sval_json::Formatter::new(String::new()), width);
+    sval::stream_owned(&mut stream, v).unwrap();
+
+    stream.into_inner().into_inner()
+}
+
+#[test]
+fn indent_primitive() {
+    assert_eq!("42", to_pretty_string(42, 2));
+}
+
+#[test]
+fn indent_nested() {
+    let mut map = BTreeMap::new();
+    map.insert("a", vec![1, 2]);
+    map.insert("b", vec![]);
+
+    assert_eq!(
+        "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": []\n}",
+        to_pretty_string(&map, 2)
+    );
+}
+
+#[test]
+fn indent_width() {
+    assert_eq!("[\n1,\n2\n]", to_pretty_string(vec![1, 2], 0));
+    assert_eq!(
+        "[\n                    [\n                                        1\n                    ]\n]",
+        to_pretty_string(vec![vec![1]], 20)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn indent_writer() {
+    let mut stream = IndentingStream::new(sval_json::Writer::new(Vec::new()), 4);
+    sval::stream(&mut stream, &vec![1]).unwrap();
+
+    assert_eq!(
+        "[\n    1\n]",
+        String::from_utf8(stream.into_inner().into_inner()).unwrap()
+    );
+}
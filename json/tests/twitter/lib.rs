@@ -36,6 +36,131 @@ fn sval_json_is_valid() {
     serde_json::from_str::<Twitter>(&json).unwrap();
 }
 
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn sval_json_nested() {
+    use std::collections::BTreeMap;
+
+    let mut inner = BTreeMap::new();
+    inner.insert("a", vec![Some(1), None]);
+    inner.insert("b \"quoted\"", vec![]);
+
+    let mut outer = BTreeMap::new();
+    outer.insert("inner", inner);
+
+    let json = sval_json::to_string(&outer).unwrap();
+
+    assert_eq!(r#"{"inner":{"a":[1,null],"b \"quoted\"":[]}}"#, json);
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn sval_json_non_string_key_is_unsupported() {
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert(1, true);
+
+    let err = sval_json::to_string(&map).unwrap_err();
+
+    assert!(err.is_unsupported());
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn sval_json_from_str_is_valid() {
+    fn check(input: &str) {
+        let mut stream = sval_json::Formatter::new(String::new());
+        sval_json::from_str(input, &mut stream).unwrap();
+        let json = stream.into_inner();
+
+        let expected: serde_json::Value = serde_json::from_str(input).unwrap();
+        let actual: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(expected, actual, "{}", input);
+    }
+
+    check("42");
+    check("-42");
+    check("0");
+    check("1.5e3");
+    check("-0.25");
+    check("18446744073709551615");
+    check("true");
+    check("null");
+    check(r#""a string""#);
+    check(r#""escaped \"quotes\" \\ \/ \b\f\n\r\t""#);
+    check(r#""\u00e9 \ud83d\ude00""#);
+    check("[]");
+    check("{}");
+    check(r#" { "a" : [ 1 , null , { "b" : [] } ] , "c" : false } "#);
+    check(include_str!("./twitter.json"));
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn sval_json_from_str_borrows_unescaped_strings() {
+    struct Borrowed<'a>(Option<&'a str>);
+
+    impl<'a> sval::stream::Stream<'a> for Borrowed<'a> {
+        fn str_borrowed(&mut self, v: &'a str) -> sval::stream::Result {
+            self.0 = Some(v);
+
+            Ok(())
+        }
+    }
+
+    let input = r#""a string""#;
+
+    let mut stream = Borrowed(None);
+    sval_json::from_str(input, &mut stream).unwrap();
+
+    assert_eq!(Some(&input[1..9]), stream.0);
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn sval_json_from_str_invalid() {
+    fn check(input: &str) {
+        let mut stream = sval_json::Formatter::new(String::new());
+
+        assert!(
+            sval_json::from_str(input, &mut stream).is_err(),
+            "{}",
+            input
+        );
+    }
+
+    check("");
+    check("[1, 2");
+    check("[1, 2,]");
+    check(r#"{"a" 1}"#);
+    check(r#"{1: 1}"#);
+    check(r#"{"a": 1,}"#);
+    check(r#""unterminated"#);
+    check(r#""\x""#);
+    check(r#""\ud83d""#);
+    check(r#""\u+123""#);
+    check("01");
+    check("1.");
+    check("-");
+    check("tru");
+    check("42 43");
+    check(&"[".repeat(1000));
+    check("1e400");
+    check("-1e400");
+    check(&"9".repeat(400));
+}
+
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn sval_json_from_str_number_out_of_range() {
+    let mut stream = sval_json::Formatter::new(String::new());
+    let err = sval_json::from_str("1e400", &mut stream).unwrap_err();
+
+    assert_eq!("number out of range", err.to_string());
+}
+
 #[derive(Serialize, Deserialize, MiniSerialize, Value)]
 pub struct Twitter {
     statuses: Vec<Status>,
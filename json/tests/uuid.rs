@@ -0,0 +1,66 @@
+use sval::{
+    buffer::Buffer,
+    test::{
+        self,
+        Token,
+    },
+    value::{
+        self,
+        UuidFormat,
+        Value,
+    },
+};
+
+use uuid::Uuid;
+
+const UUID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+struct AsBytes(Uuid);
+
+impl Value for AsBytes {
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        UuidFormat::Bytes.stream(&self.0, stream)
+    }
+}
+
+fn round_trip(v: impl Value) -> (String, Vec<Token>) {
+    let mut json = String::new();
+    sval_json::to_fmt(&mut json, v).unwrap();
+
+    let mut buffer = Buffer::new();
+    sval_json::from_str(&json, &mut buffer).unwrap();
+
+    (json, test::tokens(&buffer))
+}
+
+#[test]
+fn uuid_round_trip() {
+    let uuid = Uuid::parse_str(UUID).unwrap();
+
+    let (json, tokens) = round_trip(uuid);
+    assert_eq!(format!("\"{}\"", UUID), json);
+
+    match &*tokens {
+        [Token::Str(parsed)] => assert_eq!(uuid, Uuid::parse_str(parsed).unwrap()),
+        tokens => panic!("unexpected tokens {:?}", tokens),
+    }
+}
+
+#[test]
+fn uuid_bytes_round_trip() {
+    let uuid = Uuid::parse_str(UUID).unwrap();
+
+    let (_, tokens) = round_trip(AsBytes(uuid));
+
+    let bytes: Vec<u8> = tokens
+        .iter()
+        .filter_map(|token| match *token {
+            Token::Signed(b) => Some(b as u8),
+            Token::Unsigned(b) => Some(b as u8),
+            Token::SeqBegin(_) | Token::SeqEnd => None,
+            ref token => panic!("unexpected token {:?}", token),
+        })
+        .collect();
+
+    assert_eq!(uuid, Uuid::from_slice(&bytes).unwrap());
+}
@@ -0,0 +1,459 @@
+/*!
+A writable buffer of tokens.
+
+Add the `alloc` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["alloc"]
+```
+
+A [`Buffer`] is a [`Stream`] that records the tokens it's given,
+and a [`Value`] that replays those tokens when it's streamed.
+Unlike an [`OwnedValue`], a buffer can be written to directly:
+
+```
+# fn main() -> Result<(), sval::Error> {
+use sval::buffer::{Buffer, Token};
+
+let mut buffer = Buffer::new();
+
+buffer.push_token(Token::SeqBegin(Some(2)));
+buffer.push_token(Token::SeqElem);
+buffer.push_token(Token::Bool(true));
+buffer.push_token(Token::SeqElem);
+buffer.push_token(Token::Str("a string".into()));
+buffer.push_token(Token::SeqEnd);
+
+// `buffer` can now be streamed like any other value
+# let _ = buffer;
+# Ok(())
+# }
+```
+
+[`Stream`]: ../stream/trait.Stream.html
+[`Value`]: ../value/trait.Value.html
+[`OwnedValue`]: ../value/struct.OwnedValue.html
+*/
+
+use crate::{
+    std::{
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    stream::{
+        self,
+        Stream,
+    },
+    value::{
+        self,
+        owned::{
+            OwnedSource,
+            TokenBuf,
+            TokenKind,
+        },
+        Value,
+    },
+};
+
+/**
+A token that can be pushed into a [`Buffer`].
+
+All data in a token is owned.
+*/
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    MapBegin(Option<usize>),
+    MapKey,
+    MapValue,
+    MapEnd,
+    SeqBegin(Option<usize>),
+    SeqElem,
+    SeqEnd,
+    Signed(i64),
+    Unsigned(u64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    Float(f64),
+    Float32(f32),
+    BigSigned(i128),
+    BigUnsigned(u128),
+    Bool(bool),
+    Str(String),
+    Char(char),
+    Bytes(Vec<u8>),
+    /**
+    An error, captured as its message.
+    */
+    Error(String),
+    None,
+}
+
+impl Token {
+    pub(crate) fn from_kind(kind: &TokenKind) -> Self {
+        match *kind {
+            TokenKind::MapBegin(len) => Token::MapBegin(len),
+            TokenKind::MapKey => Token::MapKey,
+            TokenKind::MapValue => Token::MapValue,
+            TokenKind::MapEnd => Token::MapEnd,
+            TokenKind::SeqBegin(len) => Token::SeqBegin(len),
+            TokenKind::SeqElem => Token::SeqElem,
+            TokenKind::SeqEnd => Token::SeqEnd,
+            TokenKind::Signed(v) => Token::Signed(v),
+            TokenKind::Unsigned(v) => Token::Unsigned(v),
+            TokenKind::U8(v) => Token::U8(v),
+            TokenKind::U16(v) => Token::U16(v),
+            TokenKind::U32(v) => Token::U32(v),
+            TokenKind::I8(v) => Token::I8(v),
+            TokenKind::I16(v) => Token::I16(v),
+            TokenKind::I32(v) => Token::I32(v),
+            TokenKind::Float(v) => Token::Float(v),
+            TokenKind::Float32(v) => Token::Float32(v),
+            TokenKind::BigSigned(v) => Token::BigSigned(v),
+            TokenKind::BigUnsigned(v) => Token::BigUnsigned(v),
+            TokenKind::Bool(v) => Token::Bool(v),
+            TokenKind::Str(ref v) => Token::Str((**v).into()),
+            TokenKind::Char(v) => Token::Char(v),
+            TokenKind::Bytes(ref v) => Token::Bytes((**v).into()),
+            TokenKind::Error(ref err) => Token::Error(err.to_string()),
+            TokenKind::None => Token::None,
+        }
+    }
+}
+
+/**
+A buffer of tokens that implements both [`Stream`] and [`Value`].
+
+[`Stream`]: ../stream/trait.Stream.html
+[`Value`]: ../value/trait.Value.html
+*/
+pub struct Buffer(TokenBuf);
+
+impl Default for Buffer {
+    fn default() -> Self {
+        Buffer::new()
+    }
+}
+
+impl Buffer {
+    /**
+    Create a new, empty buffer.
+    */
+    pub fn new() -> Self {
+        Buffer(TokenBuf::new())
+    }
+
+    /**
+    Push a token into the buffer.
+    */
+    pub fn push_token(&mut self, token: Token) {
+        // Buffering tokens never fails
+        let _ = match token {
+            Token::MapBegin(len) => self.0.map_begin(len),
+            Token::MapKey => self.0.map_key(),
+            Token::MapValue => self.0.map_value(),
+            Token::MapEnd => self.0.map_end(),
+            Token::SeqBegin(len) => self.0.seq_begin(len),
+            Token::SeqElem => self.0.seq_elem(),
+            Token::SeqEnd => self.0.seq_end(),
+            Token::Signed(v) => self.0.i64(v),
+            Token::Unsigned(v) => self.0.u64(v),
+            Token::U8(v) => self.0.u8(v),
+            Token::U16(v) => self.0.u16(v),
+            Token::U32(v) => self.0.u32(v),
+            Token::I8(v) => self.0.i8(v),
+            Token::I16(v) => self.0.i16(v),
+            Token::I32(v) => self.0.i32(v),
+            Token::Float(v) => self.0.f64(v),
+            Token::Float32(v) => self.0.f32(v),
+            Token::BigSigned(v) => self.0.i128(v),
+            Token::BigUnsigned(v) => self.0.u128(v),
+            Token::Bool(v) => self.0.bool(v),
+            Token::Str(ref v) => self.0.str(v),
+            Token::Char(v) => self.0.char(v),
+            Token::Bytes(ref v) => self.0.bytes(v),
+            Token::Error(ref v) => self.0.error_owned(OwnedSource::msg(v.clone())),
+            Token::None => self.0.none(),
+        };
+    }
+
+    /**
+    Clear the buffer so that it can be re-used.
+    */
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl Value for Buffer {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        for token in self.0.tokens() {
+            token.stream_owned(stream.borrowed())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'v> Stream<'v> for Buffer {
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
+        self.0.fmt(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: stream::Arguments<'v>) -> stream::Result {
+        self.0.fmt_borrowed(v)
+    }
+
+    fn error(&mut self, v: stream::Source) -> stream::Result {
+        self.0.error(v)
+    }
+
+    fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
+        self.0.error_borrowed(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.0.error_str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> stream::Result {
+        self.0.i64(v)
+    }
+
+    fn u64(&mut self, v: u64) -> stream::Result {
+        self.0.u64(v)
+    }
+
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.0.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.0.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.0.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.0.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.0.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.0.i32(v)
+    }
+
+    fn i128(&mut self, v: i128) -> stream::Result {
+        self.0.i128(v)
+    }
+
+    fn u128(&mut self, v: u128) -> stream::Result {
+        self.0.u128(v)
+    }
+
+    fn f64(&mut self, v: f64) -> stream::Result {
+        self.0.f64(v)
+    }
+
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.0.f32(v)
+    }
+
+    fn bool(&mut self, v: bool) -> stream::Result {
+        self.0.bool(v)
+    }
+
+    fn char(&mut self, v: char) -> stream::Result {
+        self.0.char(v)
+    }
+
+    fn str(&mut self, v: &str) -> stream::Result {
+        self.0.str(v)
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> stream::Result {
+        self.0.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> stream::Result {
+        self.0.typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        self.0.typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.0.tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> stream::Result {
+        self.0.tagged_end(tag)
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.0.str_borrowed(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.0.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.0.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.0.bytes_borrowed(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        self.0.none()
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> stream::Result {
+        self.0.map_begin(len)
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.0.map_begin_unknown()
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        self.0.map_key()
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.0.map_key_collect(k)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.0.map_key_collect_borrowed(k)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        self.0.map_value()
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.0.map_value_collect(v)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.0.map_value_collect_borrowed(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.0.map_flat_entry_collect(k, v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.0.map_flat_entry_collect_borrowed(k, v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        self.0.map_end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> stream::Result {
+        self.0.seq_begin(len)
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.0.seq_begin_unknown()
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        self.0.seq_elem()
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        self.0.seq_elem_end()
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.0.seq_elem_collect(v)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.0.seq_elem_collect_borrowed(v)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        self.0.seq_end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::vec,
+        test::{
+            self,
+            Token as TestToken,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn buffer_push_token() {
+        let mut buffer = Buffer::new();
+
+        buffer.push_token(Token::MapBegin(Some(1)));
+        buffer.push_token(Token::MapKey);
+        buffer.push_token(Token::Str("a".into()));
+        buffer.push_token(Token::MapValue);
+        buffer.push_token(Token::Bytes(vec![1, 2]));
+        buffer.push_token(Token::MapEnd);
+
+        assert_eq!(
+            vec![
+                TestToken::MapBegin(Some(1)),
+                TestToken::Str("a".into()),
+                TestToken::Bytes(vec![1, 2]),
+                TestToken::MapEnd,
+            ],
+            test::tokens(&buffer)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn buffer_stream() {
+        let mut buffer = Buffer::new();
+
+        crate::stream(&mut buffer, &vec![1, 2]).unwrap();
+
+        assert_eq!(test::tokens(&vec![1, 2]), test::tokens(&buffer));
+
+        buffer.clear();
+
+        assert_eq!(Vec::<TestToken>::new(), test::tokens(&buffer));
+    }
+}
@@ -0,0 +1,608 @@
+/*!
+CBOR support for `sval`.
+
+Add the `cbor` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["cbor"]
+```
+
+# Writing CBOR to `io::Write`
+
+```
+# fn main() -> Result<(), sval::Error> {
+let mut encoder = sval::cbor::Encoder::new(Vec::new());
+sval::stream(&mut encoder, &vec![1, 2, 3])?;
+
+assert_eq!(vec![0x83, 0x01, 0x02, 0x03], encoder.into_inner());
+# Ok(())
+# }
+```
+
+Maps and sequences with a known length are encoded with a definite
+length. Maps and sequences without one are encoded with an indefinite
+length and terminated with a break.
+
+Values tagged with `"datetime"` using [`Stream::tagged_begin`] are
+encoded with the standard date/time string tag. Other tags are ignored.
+
+[`Stream::tagged_begin`]: ../stream/trait.Stream.html#method.tagged_begin
+*/
+
+use crate::{
+    std::{
+        io::Write,
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    stream::{
+        self,
+        Stream,
+    },
+};
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+
+const TAG_DATETIME: u64 = 0;
+const TAG_POSITIVE_BIGNUM: u64 = 2;
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+
+const INDEFINITE_ARRAY: u8 = 0x9f;
+const INDEFINITE_MAP: u8 = 0xbf;
+const BREAK: u8 = 0xff;
+
+const FALSE: u8 = 0xf4;
+const TRUE: u8 = 0xf5;
+const NULL: u8 = 0xf6;
+const FLOAT32: u8 = 0xfa;
+const FLOAT64: u8 = 0xfb;
+
+/**
+A stream for writing structured data as CBOR.
+
+The stream internally wraps a [`std::io::Write`].
+*/
+pub struct Encoder<W> {
+    // The number of items remaining in each map or sequence,
+    // or `None` if it has an indefinite length
+    remaining: Vec<Option<usize>>,
+    out: W,
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    /**
+    Create a new CBOR stream.
+    */
+    pub fn new(out: W) -> Self {
+        Encoder {
+            remaining: Vec::new(),
+            out,
+        }
+    }
+
+    /**
+    Get the inner writer back out of the stream without ensuring it's valid.
+    */
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn header(&mut self, major: u8, v: u64) -> stream::Result {
+        let major = major << 5;
+
+        if v < 24 {
+            self.out.write_all(&[major | v as u8])?;
+        } else if v <= u8::MAX as u64 {
+            self.out.write_all(&[major | 24, v as u8])?;
+        } else if v <= u16::MAX as u64 {
+            self.out.write_all(&[major | 25])?;
+            self.out.write_all(&(v as u16).to_be_bytes())?;
+        } else if v <= u32::MAX as u64 {
+            self.out.write_all(&[major | 26])?;
+            self.out.write_all(&(v as u32).to_be_bytes())?;
+        } else {
+            self.out.write_all(&[major | 27])?;
+            self.out.write_all(&v.to_be_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn bignum(&mut self, tag: u64, v: u128) -> stream::Result {
+        let bytes = v.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+
+        self.header(MAJOR_TAG, tag)?;
+        self.header(MAJOR_BYTES, (bytes.len() - start) as u64)?;
+        self.out.write_all(&bytes[start..])?;
+
+        Ok(())
+    }
+
+    fn begin(&mut self, major: u8, indefinite: u8, len: Option<usize>) -> stream::Result {
+        match len {
+            Some(len) => {
+                // Maps need both a key and a value for each entry
+                let items = if major == MAJOR_MAP { len * 2 } else { len };
+
+                self.remaining.push(Some(items));
+                self.header(major, len as u64)
+            }
+            None => {
+                self.remaining.push(None);
+                self.out.write_all(&[indefinite])?;
+
+                Ok(())
+            }
+        }
+    }
+
+    fn item(&mut self) -> stream::Result {
+        if let Some(Some(remaining)) = self.remaining.last_mut() {
+            if *remaining == 0 {
+                return Err(crate::Error::msg(
+                    "the number of items is greater than the length hint",
+                ));
+            }
+
+            *remaining -= 1;
+        }
+
+        Ok(())
+    }
+
+    fn end(&mut self) -> stream::Result {
+        match self.remaining.pop() {
+            Some(Some(0)) => Ok(()),
+            Some(Some(_)) => Err(crate::Error::msg(
+                "the number of items is less than the length hint",
+            )),
+            Some(None) => {
+                self.out.write_all(&[BREAK])?;
+
+                Ok(())
+            }
+            None => Err(crate::Error::msg("unexpected end of a map or sequence")),
+        }
+    }
+}
+
+impl<'v, W> Stream<'v> for Encoder<W>
+where
+    W: Write,
+{
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
+        self.str(&v.to_string())
+    }
+
+    fn fmt_borrowed(&mut self, v: stream::Arguments<'v>) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, v: stream::Source) -> stream::Result {
+        self.fmt(stream::Arguments::display(&v))
+    }
+
+    fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> stream::Result {
+        if v < 0 {
+            self.header(MAJOR_NEGATIVE, !v as u64)
+        } else {
+            self.header(MAJOR_UNSIGNED, v as u64)
+        }
+    }
+
+    fn u64(&mut self, v: u64) -> stream::Result {
+        self.header(MAJOR_UNSIGNED, v)
+    }
+
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.u64(v as u64)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.u64(v as u64)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.u64(v as u64)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn i128(&mut self, v: i128) -> stream::Result {
+        if v < 0 {
+            // Negative integers are encoded as `-1 - n`
+            let n = !v as u128;
+
+            if n <= u64::MAX as u128 {
+                self.header(MAJOR_NEGATIVE, n as u64)
+            } else {
+                self.bignum(TAG_NEGATIVE_BIGNUM, n)
+            }
+        } else {
+            self.u128(v as u128)
+        }
+    }
+
+    fn u128(&mut self, v: u128) -> stream::Result {
+        if v <= u64::MAX as u128 {
+            self.header(MAJOR_UNSIGNED, v as u64)
+        } else {
+            self.bignum(TAG_POSITIVE_BIGNUM, v)
+        }
+    }
+
+    fn f64(&mut self, v: f64) -> stream::Result {
+        self.out.write_all(&[FLOAT64])?;
+        self.out.write_all(&v.to_bits().to_be_bytes())?;
+
+        Ok(())
+    }
+
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.out.write_all(&[FLOAT32])?;
+        self.out.write_all(&v.to_bits().to_be_bytes())?;
+
+        Ok(())
+    }
+
+    fn bool(&mut self, v: bool) -> stream::Result {
+        self.out.write_all(&[if v { TRUE } else { FALSE }])?;
+
+        Ok(())
+    }
+
+    fn char(&mut self, v: char) -> stream::Result {
+        self.str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn str(&mut self, v: &str) -> stream::Result {
+        self.header(MAJOR_TEXT, v.len() as u64)?;
+        self.out.write_all(v.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        match tag {
+            "datetime" => self.header(MAJOR_TAG, TAG_DATETIME),
+            _ => Ok(()),
+        }
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.header(MAJOR_BYTES, v.len() as u64)?;
+        self.out.write_all(v)?;
+
+        Ok(())
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        self.out.write_all(&[NULL])?;
+
+        Ok(())
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> stream::Result {
+        self.begin(MAJOR_MAP, INDEFINITE_MAP, len)
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        self.end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> stream::Result {
+        self.begin(MAJOR_ARRAY, INDEFINITE_ARRAY, len)
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        self.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+        value::{
+            self,
+            Value,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn encode(v: impl Value) -> Vec<u8> {
+        let mut encoder = Encoder::new(Vec::new());
+        crate::stream(&mut encoder, &v).unwrap();
+
+        encoder.into_inner()
+    }
+
+    // Test vectors are from RFC 7049 Appendix A
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_unsigned() {
+        assert_eq!(vec![0x00], encode(0u64));
+        assert_eq!(vec![0x17], encode(23u8));
+        assert_eq!(vec![0x18, 0x18], encode(24u16));
+        assert_eq!(vec![0x18, 0x64], encode(100u32));
+        assert_eq!(vec![0x19, 0x03, 0xe8], encode(1000u64));
+        assert_eq!(vec![0x1a, 0x00, 0x0f, 0x42, 0x40], encode(1000000u64));
+        assert_eq!(
+            vec![0x1b, 0x00, 0x00, 0x00, 0xe8, 0xd4, 0xa5, 0x10, 0x00],
+            encode(1000000000000u64)
+        );
+        assert_eq!(
+            vec![0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            encode(u64::MAX)
+        );
+        assert_eq!(
+            vec![0xc2, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            encode(u64::MAX as u128 + 1)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_negative() {
+        assert_eq!(vec![0x20], encode(-1i8));
+        assert_eq!(vec![0x29], encode(-10i16));
+        assert_eq!(vec![0x38, 0x63], encode(-100i32));
+        assert_eq!(vec![0x39, 0x03, 0xe7], encode(-1000i64));
+        assert_eq!(
+            vec![0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            encode(-(u64::MAX as i128) - 1)
+        );
+        assert_eq!(
+            vec![0xc3, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            encode(-(u64::MAX as i128) - 2)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_primitives() {
+        assert_eq!(
+            vec![0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a],
+            encode(1.1f64)
+        );
+        assert_eq!(vec![0xfa, 0x47, 0xc3, 0x50, 0x00], encode(100000.0f32));
+        assert_eq!(vec![0xf4], encode(false));
+        assert_eq!(vec![0xf5], encode(true));
+        assert_eq!(vec![0xf6], encode(Option::None::<i32>));
+        assert_eq!(vec![0x60], encode(""));
+        assert_eq!(vec![0x64, 0x49, 0x45, 0x54, 0x46], encode("IETF"));
+        assert_eq!(vec![0x62, 0xc3, 0xbc], encode('\u{00fc}'));
+        assert_eq!(
+            vec![0x44, 0x01, 0x02, 0x03, 0x04],
+            encode(value::Bytes::new(&[1, 2, 3, 4]))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_tagged() {
+        struct Tagged(&'static str, &'static str);
+
+        impl Value for Tagged {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.tagged_begin(self.0)?;
+                stream.str(self.1)?;
+                stream.tagged_end(self.0)
+            }
+        }
+
+        let mut expected = vec![0xc0, 0x74];
+        expected.extend_from_slice(b"2013-03-21T20:04:00Z");
+        assert_eq!(expected, encode(Tagged("datetime", "2013-03-21T20:04:00Z")));
+
+        assert_eq!(vec![0x61, 0x61], encode(Tagged("unknown", "a")));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_definite() {
+        assert_eq!(vec![0x80], encode(&[] as &[i32]));
+        assert_eq!(vec![0x83, 0x01, 0x02, 0x03], encode(&[1, 2, 3][..]));
+        assert_eq!(vec![0xa0], encode(BTreeMap::<&str, i32>::new()));
+
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![]);
+        map.insert("b", vec![2, 3]);
+
+        assert_eq!(
+            vec![0xa2, 0x61, 0x61, 0x80, 0x61, 0x62, 0x82, 0x02, 0x03],
+            encode(map)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_indefinite() {
+        struct Indefinite;
+
+        impl Value for Indefinite {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin(None)?;
+                stream.map_key(&"a")?;
+                stream.map_value(&1)?;
+                stream.map_key(&"b")?;
+
+                stream.map_value_begin()?.seq_begin(None)?;
+                stream.seq_elem(&2)?;
+                stream.seq_elem(&3)?;
+                stream.seq_end()?;
+
+                stream.map_end()
+            }
+        }
+
+        assert_eq!(
+            vec![0xbf, 0x61, 0x61, 0x01, 0x61, 0x62, 0x9f, 0x02, 0x03, 0xff, 0xff],
+            encode(Indefinite)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_length_mismatch() {
+        struct TooShort;
+
+        impl Value for TooShort {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(Some(2))?;
+                stream.seq_elem(&1)?;
+                stream.seq_end()
+            }
+        }
+
+        struct TooLong;
+
+        impl Value for TooLong {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin(Some(0))?;
+                stream.map_key(&1)?;
+                stream.map_value(&1)?;
+                stream.map_end()
+            }
+        }
+
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &TooShort).is_err());
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &TooLong).is_err());
+    }
+}
@@ -0,0 +1,536 @@
+/*!
+Reconstruct values from their streamed structure.
+
+Add the `alloc` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["alloc"]
+```
+
+Just like [`Value`] is a trait for types that can be streamed,
+[`Deserialize`] is a trait for types that can be rebuilt from a stream.
+A [`Deserializer`] collects the tokens of a value so that a `Deserialize`
+implementation can read them back in order:
+
+```
+# fn main() -> Result<(), sval::Error> {
+use std::collections::BTreeMap;
+
+let mut map = BTreeMap::new();
+map.insert("a", vec![1, 2]);
+map.insert("b", vec![3]);
+
+let de: BTreeMap<String, Vec<u8>> = sval::de::from_value(&map)?;
+
+assert_eq!(vec![1, 2], de["a"]);
+# Ok(())
+# }
+```
+
+Primitives, `Option`, `Vec`, `BTreeMap`, and `HashMap` implement `Deserialize`.
+Integers can be deserialized from any integer token that's in range for them.
+Floating point numbers can be deserialized from any integer or floating point token.
+
+[`Value`]: ../value/trait.Value.html
+[`Deserialize`]: trait.Deserialize.html
+[`Deserializer`]: struct.Deserializer.html
+*/
+
+use crate::{
+    buffer::Token,
+    std::{
+        collections::BTreeMap,
+        convert::TryFrom,
+        string::String,
+        vec::{
+            self,
+            Vec,
+        },
+    },
+    value::{
+        owned::TokenBuf,
+        Value,
+    },
+    Error,
+};
+
+/**
+A type that can be reconstructed from the structure of a value.
+*/
+pub trait Deserialize: Sized {
+    /**
+    Read a value from the deserializer.
+    */
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error>;
+}
+
+/**
+Deserialize a type from the structure of a value.
+
+The value must be entirely consumed by the type.
+*/
+pub fn from_value<T>(v: &(impl Value + ?Sized)) -> Result<T, Error>
+where
+    T: Deserialize,
+{
+    let mut de = Deserializer::new(v)?;
+    let value = de.deserialize()?;
+    de.end()?;
+
+    Ok(value)
+}
+
+/**
+A sequence of tokens to deserialize types from.
+*/
+pub struct Deserializer {
+    tokens: vec::IntoIter<Token>,
+    peeked: Option<Token>,
+}
+
+impl Deserializer {
+    /**
+    Collect the tokens of a value to deserialize.
+    */
+    pub fn new(v: &(impl Value + ?Sized)) -> Result<Self, Error> {
+        let tokens = TokenBuf::collect(v)?
+            .iter()
+            .map(|token| Token::from_kind(&token.kind))
+            .collect::<Vec<_>>();
+
+        Ok(Deserializer {
+            tokens: tokens.into_iter(),
+            peeked: None,
+        })
+    }
+
+    /**
+    Deserialize a type from the next tokens.
+    */
+    pub fn deserialize<T>(&mut self) -> Result<T, Error>
+    where
+        T: Deserialize,
+    {
+        T::deserialize(self)
+    }
+
+    /**
+    Take the next token.
+    */
+    pub fn next_token(&mut self) -> Result<Token, Error> {
+        self.peeked
+            .take()
+            .or_else(|| self.tokens.next())
+            .ok_or_else(|| Error::msg("unexpected end of input"))
+    }
+
+    /**
+    Look at the next token without taking it.
+    */
+    pub fn peek_token(&mut self) -> Option<&Token> {
+        if self.peeked.is_none() {
+            self.peeked = self.tokens.next();
+        }
+
+        self.peeked.as_ref()
+    }
+
+    /**
+    Begin a sequence, returning its length if it's known.
+    */
+    pub fn seq_begin(&mut self) -> Result<Option<usize>, Error> {
+        match self.next_token()? {
+            Token::SeqBegin(len) => Ok(len),
+            _ => Err(Error::msg("expected a sequence")),
+        }
+    }
+
+    /**
+    Begin the next sequence element.
+
+    Returns `false` and ends the sequence if there are no more elements.
+    */
+    pub fn seq_elem(&mut self) -> Result<bool, Error> {
+        match self.next_token()? {
+            Token::SeqElem => Ok(true),
+            Token::SeqEnd => Ok(false),
+            _ => Err(Error::msg(
+                "expected a sequence element or the end of a sequence",
+            )),
+        }
+    }
+
+    /**
+    Begin a map, returning its length if it's known.
+    */
+    pub fn map_begin(&mut self) -> Result<Option<usize>, Error> {
+        match self.next_token()? {
+            Token::MapBegin(len) => Ok(len),
+            _ => Err(Error::msg("expected a map")),
+        }
+    }
+
+    /**
+    Begin the next map key.
+
+    Returns `false` and ends the map if there are no more entries.
+    */
+    pub fn map_key(&mut self) -> Result<bool, Error> {
+        match self.next_token()? {
+            Token::MapKey => Ok(true),
+            Token::MapEnd => Ok(false),
+            _ => Err(Error::msg("expected a map key or the end of a map")),
+        }
+    }
+
+    /**
+    Begin the value for the last map key.
+    */
+    pub fn map_value(&mut self) -> Result<(), Error> {
+        match self.next_token()? {
+            Token::MapValue => Ok(()),
+            _ => Err(Error::msg("expected a map value")),
+        }
+    }
+
+    /**
+    Check that all tokens have been deserialized.
+    */
+    pub fn end(mut self) -> Result<(), Error> {
+        match self.peek_token() {
+            None => Ok(()),
+            Some(_) => Err(Error::msg("unexpected trailing input")),
+        }
+    }
+
+    fn integer<T>(&mut self) -> Result<T, Error>
+    where
+        T: TryFrom<i128> + TryFrom<u128>,
+    {
+        let v = match self.next_token()? {
+            Token::Signed(v) => T::try_from(v as i128).ok(),
+            Token::Unsigned(v) => T::try_from(v as u128).ok(),
+            Token::U8(v) => T::try_from(v as u128).ok(),
+            Token::U16(v) => T::try_from(v as u128).ok(),
+            Token::U32(v) => T::try_from(v as u128).ok(),
+            Token::I8(v) => T::try_from(v as i128).ok(),
+            Token::I16(v) => T::try_from(v as i128).ok(),
+            Token::I32(v) => T::try_from(v as i128).ok(),
+            Token::BigSigned(v) => T::try_from(v).ok(),
+            Token::BigUnsigned(v) => T::try_from(v).ok(),
+            _ => return Err(Error::msg("expected an integer")),
+        };
+
+        v.ok_or_else(|| Error::msg("integer is out of range"))
+    }
+}
+
+/**
+Get a capacity to preallocate from a length hint.
+
+The hint comes from the value being deserialized so it can't be trusted.
+It's capped so a bogus length can't trigger a huge allocation.
+*/
+fn size_hint(len: Option<usize>) -> usize {
+    len.unwrap_or(0).min(4096)
+}
+
+macro_rules! impl_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Deserialize for $ty {
+                fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+                    de.integer()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Deserialize for f64 {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        match de.next_token()? {
+            Token::Float(v) => Ok(v),
+            Token::Float32(v) => Ok(v as f64),
+            Token::Signed(v) => Ok(v as f64),
+            Token::Unsigned(v) => Ok(v as f64),
+            Token::U8(v) => Ok(v as f64),
+            Token::U16(v) => Ok(v as f64),
+            Token::U32(v) => Ok(v as f64),
+            Token::I8(v) => Ok(v as f64),
+            Token::I16(v) => Ok(v as f64),
+            Token::I32(v) => Ok(v as f64),
+            Token::BigSigned(v) => Ok(v as f64),
+            Token::BigUnsigned(v) => Ok(v as f64),
+            _ => Err(Error::msg("expected a floating point number")),
+        }
+    }
+}
+
+impl Deserialize for f32 {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        match de.next_token()? {
+            Token::Float32(v) => Ok(v),
+            Token::Float(v) => Ok(v as f32),
+            Token::Signed(v) => Ok(v as f32),
+            Token::Unsigned(v) => Ok(v as f32),
+            Token::U8(v) => Ok(v as f32),
+            Token::U16(v) => Ok(v as f32),
+            Token::U32(v) => Ok(v as f32),
+            Token::I8(v) => Ok(v as f32),
+            Token::I16(v) => Ok(v as f32),
+            Token::I32(v) => Ok(v as f32),
+            Token::BigSigned(v) => Ok(v as f32),
+            Token::BigUnsigned(v) => Ok(v as f32),
+            _ => Err(Error::msg("expected a floating point number")),
+        }
+    }
+}
+
+impl Deserialize for bool {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        match de.next_token()? {
+            Token::Bool(v) => Ok(v),
+            _ => Err(Error::msg("expected a boolean")),
+        }
+    }
+}
+
+impl Deserialize for char {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        match de.next_token()? {
+            Token::Char(v) => Ok(v),
+            Token::Str(v) => {
+                let mut chars = v.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(v), None) => Ok(v),
+                    _ => Err(Error::msg("expected a single character")),
+                }
+            }
+            _ => Err(Error::msg("expected a character")),
+        }
+    }
+}
+
+impl Deserialize for String {
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        match de.next_token()? {
+            Token::Str(v) => Ok(v),
+            Token::Char(v) => Ok(v.into()),
+            _ => Err(Error::msg("expected a string")),
+        }
+    }
+}
+
+impl<T> Deserialize for Option<T>
+where
+    T: Deserialize,
+{
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        if let Some(Token::None) = de.peek_token() {
+            de.next_token()?;
+
+            Ok(None)
+        } else {
+            de.deserialize().map(Some)
+        }
+    }
+}
+
+impl<T> Deserialize for Vec<T>
+where
+    T: Deserialize,
+{
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        let mut seq = Vec::with_capacity(size_hint(de.seq_begin()?));
+
+        while de.seq_elem()? {
+            seq.push(de.deserialize()?);
+        }
+
+        Ok(seq)
+    }
+}
+
+impl<K, V> Deserialize for BTreeMap<K, V>
+where
+    K: Ord + Deserialize,
+    V: Deserialize,
+{
+    fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+        de.map_begin()?;
+
+        let mut map = BTreeMap::new();
+        while de.map_key()? {
+            let k = de.deserialize()?;
+            de.map_value()?;
+            let v = de.deserialize()?;
+
+            map.insert(k, v);
+        }
+
+        Ok(map)
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_support {
+    use super::*;
+
+    use crate::std::{
+        collections::HashMap,
+        hash::{
+            BuildHasher,
+            Hash,
+        },
+    };
+
+    impl<K, V, H> Deserialize for HashMap<K, V, H>
+    where
+        K: Hash + Eq + Deserialize,
+        V: Deserialize,
+        H: BuildHasher + Default,
+    {
+        fn deserialize(de: &mut Deserializer) -> Result<Self, Error> {
+            let len = size_hint(de.map_begin()?);
+
+            let mut map = HashMap::with_capacity_and_hasher(len, H::default());
+            while de.map_key()? {
+                let k = de.deserialize()?;
+                de.map_value()?;
+                let v = de.deserialize()?;
+
+                map.insert(k, v);
+            }
+
+            Ok(map)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::std::vec;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_primitive() {
+        assert_eq!(1u8, from_value::<u8>(&1i64).unwrap());
+        assert_eq!(-1i128, from_value::<i128>(&-1i8).unwrap());
+        assert_eq!(u128::MAX, from_value::<u128>(&u128::MAX).unwrap());
+        assert_eq!(1.5f64, from_value::<f64>(&1.5f32).unwrap());
+        assert!(from_value::<bool>(&true).unwrap());
+        assert_eq!('a', from_value::<char>("a").unwrap());
+        assert_eq!("a string", from_value::<String>("a string").unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_float_from_integer() {
+        assert_eq!(1f64, from_value::<f64>(&1).unwrap());
+        assert_eq!(1f64, from_value::<f64>(&1u8).unwrap());
+        assert_eq!(-1f64, from_value::<f64>(&-1i16).unwrap());
+        assert_eq!(1f32, from_value::<f32>(&1u32).unwrap());
+        assert_eq!(-1f32, from_value::<f32>(&-1i128).unwrap());
+        assert_eq!(1f64, from_value::<f64>(&1u128).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_option() {
+        assert_eq!(Some(1), from_value::<Option<i32>>(&Some(1)).unwrap());
+        assert_eq!(None, from_value::<Option<i32>>(&None::<i32>).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_seq() {
+        let v = vec![vec![1, 2], vec![], vec![3]];
+
+        assert_eq!(v, from_value::<Vec<Vec<u64>>>(&v).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_seq_huge_len() {
+        struct HugeLen;
+
+        impl Value for HugeLen {
+            fn stream<'s, 'v>(
+                &'v self,
+                mut stream: crate::value::Stream<'s, 'v>,
+            ) -> crate::value::Result {
+                stream.seq_begin(Some(usize::MAX / 2))?;
+                stream.seq_end()
+            }
+        }
+
+        assert_eq!(Vec::<u64>::new(), from_value::<Vec<u64>>(&HugeLen).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_map_huge_len() {
+        use crate::std::collections::HashMap;
+
+        struct HugeLen;
+
+        impl Value for HugeLen {
+            fn stream<'s, 'v>(
+                &'v self,
+                mut stream: crate::value::Stream<'s, 'v>,
+            ) -> crate::value::Result {
+                stream.map_begin(Some(usize::MAX / 2))?;
+                stream.map_end()
+            }
+        }
+
+        assert!(from_value::<HashMap<u8, bool>>(&HugeLen)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_map() {
+        let mut map = BTreeMap::new();
+        map.insert(String::from("a"), vec![Some(1), None]);
+        map.insert(String::from("b"), vec![]);
+
+        assert_eq!(map, from_value(&map).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_hash_map() {
+        use crate::std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        map.insert(1, true);
+        map.insert(2, false);
+
+        assert_eq!(map, from_value::<HashMap<u8, bool>>(&map).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn de_invalid() {
+        assert!(from_value::<u8>(&256).is_err());
+        assert!(from_value::<u8>(&-1).is_err());
+        assert!(from_value::<char>("ab").is_err());
+        assert!(from_value::<String>(&1).is_err());
+        assert!(from_value::<Vec<i32>>(&1).is_err());
+        assert!(from_value::<i32>(&vec![1]).is_err());
+    }
+}
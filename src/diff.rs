@@ -0,0 +1,172 @@
+use crate::{
+    buffer::Token,
+    value::{
+        owned::TokenBuf,
+        Value,
+    },
+    Error,
+};
+
+/**
+The first point where two values differ.
+
+See [`diff`](fn.diff.html) for more details.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diff {
+    /**
+    The index of the first token that differs.
+    */
+    pub position: usize,
+    /**
+    The token produced by the left value.
+
+    This will be `None` if the left value ended first.
+    */
+    pub left: Option<Token>,
+    /**
+    The token produced by the right value.
+
+    This will be `None` if the right value ended first.
+    */
+    pub right: Option<Token>,
+}
+
+/**
+Compare the structure of two values token-by-token.
+
+Add the `alloc` feature to your `Cargo.toml` to enable this function.
+
+Both values are buffered and then compared. If they produce the same
+tokens then `None` is returned. Otherwise the first token where they
+differ is returned. This is useful for checking that two different
+implementations of [`Value`] produce the same structure:
+
+```
+# fn main() -> Result<(), sval::Error> {
+use sval::buffer::Token;
+
+assert_eq!(None, sval::diff(&vec![1, 2], &(1, 2))?);
+
+let diff = sval::diff(&vec![1, 2], &vec![1, 3])?.expect("the values are different");
+
+assert_eq!(Some(Token::I32(2)), diff.left);
+assert_eq!(Some(Token::I32(3)), diff.right);
+# Ok(())
+# }
+```
+
+If either value fails to stream then the error is returned.
+
+[`Value`]: value/trait.Value.html
+*/
+pub fn diff(
+    left: &(impl Value + ?Sized),
+    right: &(impl Value + ?Sized),
+) -> Result<Option<Diff>, Error> {
+    let left = TokenBuf::collect(left)?;
+    let right = TokenBuf::collect(right)?;
+
+    let mut left = left.iter().map(|token| Token::from_kind(&token.kind));
+    let mut right = right.iter().map(|token| Token::from_kind(&token.kind));
+
+    let mut position = 0;
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ok(None),
+            (left, right) if left != right => {
+                return Ok(Some(Diff {
+                    position,
+                    left,
+                    right,
+                }))
+            }
+            _ => position += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn diff_same() {
+        assert_eq!(None, diff(&1, &1).unwrap());
+        assert_eq!(None, diff(&vec!["a", "b"], &("a", "b")).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn diff_primitive() {
+        assert_eq!(
+            Some(Diff {
+                position: 0,
+                left: Some(Token::I32(1)),
+                right: Some(Token::Signed(1)),
+            }),
+            diff(&1i32, &1i64).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn diff_map() {
+        let mut a = BTreeMap::new();
+        a.insert("a", 1);
+        a.insert("b", 2);
+
+        let mut b = a.clone();
+        b.insert("b", 3);
+
+        assert_eq!(
+            Some(Diff {
+                position: 8,
+                left: Some(Token::I32(2)),
+                right: Some(Token::I32(3)),
+            }),
+            diff(&a, &b).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn diff_different_lengths() {
+        assert_eq!(
+            Some(Diff {
+                position: 0,
+                left: Some(Token::SeqBegin(Some(1))),
+                right: Some(Token::SeqBegin(Some(2))),
+            }),
+            diff(&vec![1], &vec![1, 2]).unwrap()
+        );
+
+        let mut left = Buffer::new();
+        left.push_token(Token::Bool(true));
+
+        let mut right = Buffer::new();
+        right.push_token(Token::Bool(true));
+        right.push_token(Token::Bool(false));
+
+        assert_eq!(
+            Some(Diff {
+                position: 1,
+                left: None,
+                right: Some(Token::Bool(false)),
+            }),
+            diff(&left, &right).unwrap()
+        );
+    }
+}
@@ -0,0 +1,365 @@
+/*!
+A human-readable textual representation of values.
+
+The output looks similar to JSON, but isn't guaranteed to be valid JSON.
+Strings aren't quoted or escaped, and map keys can be any value.
+
+# Writing values to `fmt::Write`
+
+```
+# #[cfg(feature = "alloc")]
+# fn main() -> Result<(), sval::Error> {
+use std::collections::BTreeMap;
+
+let mut map = BTreeMap::new();
+map.insert("a", vec![Some(1), None]);
+
+let mut stream = sval::display::DisplayStream::new(String::new());
+sval::stream(&mut stream, &map)?;
+
+assert_eq!("{a: [1, null]}", stream.into_inner());
+# Ok(())
+# }
+# #[cfg(not(feature = "alloc"))]
+# fn main() {}
+```
+*/
+
+use crate::{
+    std::{
+        fmt::{
+            self,
+            Write,
+        },
+        string::String,
+    },
+    stream::{
+        self,
+        Arguments,
+        Source,
+        Stream,
+    },
+};
+
+/**
+A stream that writes a human-readable representation of values.
+
+Primitives are written using their `Display` implementations,
+except for bytes, which are written using `Debug`. Sequences are
+written as `[a, b]`, maps are written as `{k: v}`, and empty
+values are written as `null`.
+*/
+pub struct DisplayStream<W> {
+    out: W,
+    is_current_depth_empty: bool,
+}
+
+impl<W> DisplayStream<W>
+where
+    W: Write,
+{
+    /**
+    Create a new stream that writes to `out`.
+    */
+    pub fn new(out: W) -> Self {
+        DisplayStream {
+            out,
+            is_current_depth_empty: true,
+        }
+    }
+
+    /**
+    Get the inner writer.
+    */
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn display(&mut self, v: impl fmt::Display) -> stream::Result {
+        write!(self.out, "{}", v)?;
+
+        Ok(())
+    }
+
+    fn begin(&mut self, open: char) -> stream::Result {
+        self.out.write_char(open)?;
+        self.is_current_depth_empty = true;
+
+        Ok(())
+    }
+
+    fn item(&mut self) -> stream::Result {
+        if !self.is_current_depth_empty {
+            self.out.write_str(", ")?;
+        }
+
+        self.is_current_depth_empty = false;
+
+        Ok(())
+    }
+
+    fn end(&mut self, close: char) -> stream::Result {
+        self.out.write_char(close)?;
+
+        // The map or sequence that just ended is an item
+        // in its parent, so the parent isn't empty
+        self.is_current_depth_empty = false;
+
+        Ok(())
+    }
+}
+
+impl<'v, W> Stream<'v> for DisplayStream<W>
+where
+    W: Write,
+{
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, v: Arguments) -> stream::Result {
+        self.display(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, v: Source) -> stream::Result {
+        self.display(v)
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> stream::Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i64(&mut self, v: i64) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u64(&mut self, v: u64) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i128(&mut self, v: i128) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u128(&mut self, v: u128) -> stream::Result {
+        self.display(v)
+    }
+
+    fn f64(&mut self, v: f64) -> stream::Result {
+        self.display(v)
+    }
+
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.display(v)
+    }
+
+    fn bool(&mut self, v: bool) -> stream::Result {
+        self.display(v)
+    }
+
+    fn char(&mut self, v: char) -> stream::Result {
+        self.display(v)
+    }
+
+    fn str(&mut self, v: &str) -> stream::Result {
+        self.display(v)
+    }
+
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        write!(self.out, "{:?}", v)?;
+
+        Ok(())
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        self.out.write_str("null")?;
+
+        Ok(())
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> stream::Result {
+        self.begin('{')
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        self.out.write_str(": ")?;
+
+        Ok(())
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        self.end('}')
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> stream::Result {
+        self.begin('[')
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        self.end(']')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::std::{
+        collections::BTreeMap,
+        vec,
+        vec::Vec,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn display(v: &(impl crate::Value + ?Sized)) -> String {
+        let mut stream = DisplayStream::new(String::new());
+        crate::stream(&mut stream, v).unwrap();
+
+        stream.into_inner()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn display_primitive() {
+        assert_eq!("1", display(&1));
+        assert_eq!("a string", display("a string"));
+        assert_eq!("null", display(&Option::None::<i32>));
+        assert_eq!("[1, 2]", display(&crate::value::Bytes::new(&[1u8, 2][..])));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn display_nested() {
+        let mut map = BTreeMap::new();
+        map.insert(1, vec![vec![], vec!["a", "b"]]);
+        map.insert(2, Vec::new());
+
+        assert_eq!("{1: [[], [a, b]], 2: []}", display(&map));
+    }
+}
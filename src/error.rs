@@ -5,13 +5,14 @@ use crate::std::string::String;
 
 #[cfg(feature = "std")]
 use crate::std::{
-    boxed::Box,
     error,
+    sync::Arc,
 };
 
 /**
 An error encountered while visiting a value.
 */
+#[derive(Clone)]
 pub struct Error(ErrorInner);
 
 impl Error {
@@ -64,6 +65,7 @@ impl fmt::Display for Error {
     }
 }
 
+#[derive(Clone)]
 enum ErrorInner {
     Unsupported {
         msg: &'static str,
@@ -75,7 +77,7 @@ enum ErrorInner {
     #[cfg(feature = "std")]
     Source {
         msg: String,
-        source: Box<dyn error::Error + Send + Sync + 'static>,
+        source: Arc<dyn error::Error + Send + Sync + 'static>,
     },
 }
 
@@ -125,6 +127,12 @@ impl From<fmt::Error> for Error {
     }
 }
 
+impl From<Error> for fmt::Error {
+    fn from(err: Error) -> Self {
+        err.into_fmt_error()
+    }
+}
+
 #[cfg(feature = "alloc")]
 mod alloc_support {
     use super::*;
@@ -137,6 +145,33 @@ mod alloc_support {
             Error(ErrorInner::Owned(err.to_string()))
         }
     }
+
+    // Errors are compared by their message only.
+    // Any underlying source is only considered through its formatted value.
+    impl PartialEq for Error {
+        fn eq(&self, other: &Self) -> bool {
+            self.to_string() == other.to_string()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn error_eq() {
+            assert_eq!(Error::msg("a message"), Error::custom("a message"));
+            assert_ne!(Error::msg("a message"), Error::msg("another message"));
+        }
+
+        #[test]
+        fn error_clone() {
+            let err = Error::unsupported("an operation");
+
+            assert_eq!(err, err.clone());
+            assert!(err.clone().is_unsupported());
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -144,9 +179,9 @@ mod std_support {
     use super::*;
 
     use crate::std::{
-        boxed::Box,
         error,
         io,
+        sync::Arc,
     };
 
     impl Error {
@@ -171,7 +206,7 @@ mod std_support {
         fn from(err: io::Error) -> Self {
             Error(ErrorInner::Source {
                 msg: "failed during an IO operation".into(),
-                source: Box::new(err),
+                source: Arc::new(err),
             })
         }
     }
@@ -180,7 +215,11 @@ mod std_support {
     mod tests {
         use super::*;
 
-        use crate::std::error::Error as StdError;
+        use crate::std::{
+            boxed::Box,
+            error::Error as StdError,
+            string::ToString,
+        };
 
         #[test]
         fn io_error() {
@@ -188,6 +227,25 @@ mod std_support {
 
             assert!(err.source().is_some());
         }
+
+        #[test]
+        fn boxed_error() {
+            let err: Box<dyn StdError + Send + Sync> = Box::new(Error::msg("an error"));
+
+            assert_eq!("an error", err.to_string());
+            assert!(err.source().is_none());
+        }
+
+        #[test]
+        fn question_mark_boxed_error() {
+            fn fails() -> Result<(), Box<dyn StdError>> {
+                Err(Error::msg("an error"))?;
+
+                Ok(())
+            }
+
+            assert_eq!("an error", fails().unwrap_err().to_string());
+        }
     }
 }
 
@@ -200,5 +258,6 @@ mod tests {
     #[test]
     fn fmt_error() {
         let _ = Error::from(fmt::Error);
+        let _ = fmt::Error::from(Error::msg("an error"));
     }
 }
@@ -24,20 +24,39 @@ a type that implements [`std::fmt::Debug`]:
 # let my_value = MyValue;
 let my_debug = sval::fmt::to_debug(my_value);
 ```
+
+It can also be converted into a type that implements [`std::fmt::Display`]:
+
+```
+# use sval::value::{self, Value};
+# struct MyValue;
+# impl Value for MyValue {
+#     fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+#         unimplemented!()
+#     }
+# }
+# let my_value = MyValue;
+let my_display = sval::fmt::to_display(my_value);
+```
 */
 
 use crate::{
     std::fmt::{
         self,
         Debug,
+        Display,
         Formatter,
     },
     Value,
 };
 
 mod to_debug;
+mod to_display;
 
-pub use self::to_debug::ToDebug;
+pub use self::{
+    to_debug::ToDebug,
+    to_display::ToDisplay,
+};
 
 /**
 Convert a [`Value`] into a [`Debug`].
@@ -58,5 +77,27 @@ where
 Format a [`Value`] using the given [`Formatter`].
 */
 pub fn debug(f: &mut Formatter, value: impl Value) -> fmt::Result {
-    to_debug(value).fmt(f)
+    Debug::fmt(&to_debug(value), f)
+}
+
+/**
+Convert a [`Value`] into a [`Display`].
+
+The value is formatted the same way as [`to_debug`], so it's
+human-readable but not guaranteed to be valid in any particular format.
+
+This method doesn't need to allocate or perform any buffering.
+*/
+pub fn to_display<V>(value: V) -> ToDisplay<V>
+where
+    V: Value,
+{
+    ToDisplay(value)
+}
+
+/**
+Format a [`Value`] using the given [`Formatter`] as a [`Display`].
+*/
+pub fn display(f: &mut Formatter, value: impl Value) -> fmt::Result {
+    Display::fmt(&to_display(value), f)
 }
@@ -9,6 +9,9 @@ use crate::{
     value,
 };
 
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
 /**
 The result of calling [`sval::fmt::to_debug`](fn.to_debug.html).
 */
@@ -31,14 +34,14 @@ The format stream.
 This stream is an alternative implementation of `std::fmt::DebugMap` and `std::fmt::DebugList`.
 It should be kept up to date with changes made upstream.
 */
-struct Stream<'a, 'b: 'a> {
+pub(super) struct Stream<'a, 'b: 'a> {
     depth: usize,
     is_current_depth_empty: bool,
     fmt: &'a mut Formatter<'b>,
 }
 
 impl<'a, 'b: 'a> Stream<'a, 'b> {
-    fn new(fmt: &'a mut Formatter<'b>) -> Self {
+    pub(super) fn new(fmt: &'a mut Formatter<'b>) -> Self {
         Stream {
             depth: 0,
             is_current_depth_empty: false,
@@ -58,6 +61,10 @@ impl<'a, 'b: 'a> Stream<'a, 'b> {
 }
 
 impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
     fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
         self.fmt(v)
     }
@@ -74,6 +81,10 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         self.fmt(v)
     }
 
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.fmt(format_args!("{}", v))
+    }
+
     fn i64(&mut self, v: i64) -> stream::Result {
         self.fmt(v)
     }
@@ -82,6 +93,30 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         self.fmt(v)
     }
 
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.fmt(v)
+    }
+
     fn i128(&mut self, v: i128) -> stream::Result {
         self.fmt(v)
     }
@@ -94,6 +129,10 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         self.fmt(v)
     }
 
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.fmt(v)
+    }
+
     fn bool(&mut self, v: bool) -> stream::Result {
         self.fmt(v)
     }
@@ -106,10 +145,43 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         self.fmt(v)
     }
 
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
     fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
         self.fmt(v)
     }
 
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.fmt(v)
+    }
+
     fn none(&mut self) -> stream::Result {
         self.fmt(format_args!("None"))
     }
@@ -125,6 +197,10 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         Ok(())
     }
 
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
     fn map_key(&mut self) -> stream::Result {
         if self.is_pretty() {
             if !self.is_current_depth_empty {
@@ -166,6 +242,19 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         self.map_value_collect(v)
     }
 
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
     fn map_end(&mut self) -> stream::Result {
         if self.is_pretty() {
             self.depth -= 1;
@@ -195,6 +284,10 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         Ok(())
     }
 
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
     fn seq_elem(&mut self) -> stream::Result {
         if self.is_pretty() {
             if !self.is_current_depth_empty {
@@ -212,6 +305,10 @@ impl<'a, 'b: 'a, 'v> stream::Stream<'v> for Stream<'a, 'b> {
         Ok(())
     }
 
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
     fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
         self.seq_elem()?;
         v.stream(self)
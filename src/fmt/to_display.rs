@@ -0,0 +1,37 @@
+use crate::{
+    std::fmt::{
+        self,
+        Debug,
+        Display,
+        Formatter,
+    },
+    value,
+};
+
+use super::to_debug::Stream;
+
+/**
+The result of calling [`sval::fmt::to_display`](fn.to_display.html).
+*/
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ToDisplay<V>(pub(super) V);
+
+impl<V> Display for ToDisplay<V>
+where
+    V: value::Value,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        crate::stream(&mut Stream::new(f), &self.0).map_err(crate::Error::into_fmt_error)?;
+
+        Ok(())
+    }
+}
+
+impl<V> Debug for ToDisplay<V>
+where
+    V: value::Value,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,181 @@
+/*!
+Pluggable output formats.
+
+A [`Format`] turns any [`Value`] into some output, like a buffer of bytes.
+Code that produces output can be generic over its format:
+
+```
+# fn main() -> Result<(), sval::Error> {
+# #[cfg(feature = "cbor")]
+# {
+use sval::format::{self, CborFormat, Format};
+
+fn encode<F: Format>(v: &[i32]) -> Result<F::Output, sval::Error> {
+    format::format_into::<F>(v)
+}
+
+assert_eq!(vec![0x83, 0x01, 0x02, 0x03], encode::<CborFormat>(&[1, 2, 3])?);
+# }
+# Ok(())
+# }
+```
+
+Any stream that implements `Default` is also a format, where the output
+is the stream itself after the value has been streamed through it.
+
+JSON is supported by `sval_json::JsonFormat`.
+
+[`Format`]: trait.Format.html
+[`Value`]: ../value/trait.Value.html
+*/
+
+use crate::{
+    stream::Stream,
+    value::Value,
+    Error,
+};
+
+#[cfg(any(feature = "cbor", feature = "msgpack"))]
+use crate::std::vec::Vec;
+
+/**
+An output format for values.
+*/
+pub trait Format {
+    /**
+    The result of formatting a value.
+    */
+    type Output;
+
+    /**
+    Format a value.
+    */
+    fn format(v: &(impl Value + ?Sized)) -> Result<Self::Output, Error>;
+}
+
+impl<S> Format for S
+where
+    S: for<'v> Stream<'v> + Default,
+{
+    type Output = S;
+
+    fn format(v: &(impl Value + ?Sized)) -> Result<Self::Output, Error> {
+        let mut stream = S::default();
+        crate::stream(&mut stream, v)?;
+
+        Ok(stream)
+    }
+}
+
+/**
+Format a value using the format `F`.
+*/
+pub fn format_into<F>(v: &(impl Value + ?Sized)) -> Result<F::Output, Error>
+where
+    F: Format,
+{
+    F::format(v)
+}
+
+/**
+The CBOR format.
+
+Values are encoded into a buffer of bytes using a [`cbor::Encoder`].
+
+[`cbor::Encoder`]: ../cbor/struct.Encoder.html
+*/
+#[cfg(feature = "cbor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+#[derive(Debug, Clone, Copy)]
+pub struct CborFormat;
+
+#[cfg(feature = "cbor")]
+impl Format for CborFormat {
+    type Output = Vec<u8>;
+
+    fn format(v: &(impl Value + ?Sized)) -> Result<Self::Output, Error> {
+        let mut encoder = crate::cbor::Encoder::new(Vec::new());
+        crate::stream(&mut encoder, v)?;
+
+        Ok(encoder.into_inner())
+    }
+}
+
+/**
+The MessagePack format.
+
+Values are encoded into a buffer of bytes using a [`msgpack::Encoder`].
+
+[`msgpack::Encoder`]: ../msgpack/struct.Encoder.html
+*/
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+#[derive(Debug, Clone, Copy)]
+pub struct MsgPackFormat;
+
+#[cfg(feature = "msgpack")]
+impl Format for MsgPackFormat {
+    type Output = Vec<u8>;
+
+    fn format(v: &(impl Value + ?Sized)) -> Result<Self::Output, Error> {
+        let mut encoder = crate::msgpack::Encoder::new(Vec::new());
+        crate::stream(&mut encoder, v)?;
+
+        Ok(encoder.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    mod alloc_support {
+        use crate::{
+            format::{
+                self,
+                Format,
+            },
+            std::vec,
+            stream::{
+                CountingStream,
+                RecordedCall,
+                RecordingStream,
+            },
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn format_stream() {
+            let counts = format::format_into::<CountingStream>(&vec![1, 2]).unwrap();
+
+            assert_eq!(1, counts.seq_count());
+            assert_eq!(2, counts.primitive_count());
+
+            let recording = RecordingStream::format("a").unwrap();
+
+            assert_eq!(&[RecordedCall::Str("a".into())][..], recording.calls());
+        }
+
+        #[test]
+        #[cfg(feature = "cbor")]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn format_cbor() {
+            assert_eq!(
+                vec![0x82, 0x01, 0x02],
+                format::format_into::<format::CborFormat>(&[1, 2][..]).unwrap()
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "msgpack")]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn format_msgpack() {
+            assert_eq!(
+                vec![0x92, 0x01, 0x02],
+                format::format_into::<format::MsgPackFormat>(&[1, 2][..]).unwrap()
+            );
+        }
+    }
+}
@@ -18,11 +18,39 @@ version = "1.0.0-alpha.5"
 # Supported formats
 
 - [JSON](https://crates.io/crates/sval_json), the ubiquitous JavaScript Object Notation used by many HTTP APIs.
+- [CBOR](cbor/index.html), the Concise Binary Object Representation, using the `cbor` Cargo feature.
+- [MessagePack](msgpack/index.html), a compact binary format, using the `msgpack` Cargo feature.
+- [XML](xml/index.html), using the `xml` Cargo feature.
+- [TOML](toml/index.html), using the `toml` Cargo feature.
 
 # Streaming values
 
 The structure of a [`Value`] can be streamed to a [`Stream`].
 
+# Deriving `Value`
+
+Use the `derive` Cargo feature to derive the `Value` trait:
+
+```toml,ignore
+[dependencies.sval]
+features = ["derive"]
+```
+
+Structs with named fields are streamed as maps, tuple structs as sequences,
+and unit structs as empty values. Unit enum variants are streamed as their
+name, newtype variants as their inner value, and other variants like structs:
+
+```ignore
+#[macro_use]
+extern crate sval;
+
+#[derive(Value)]
+pub struct Data {
+    id: u32,
+    title: String,
+}
+```
+
 # `serde` integration
 
 Use the `serde` Cargo feature to enable integration with `serde`:
@@ -171,6 +199,7 @@ extern crate core as core_lib;
 #[cfg(all(feature = "alloc", not(feature = "std")))]
 mod std {
     pub use crate::alloc_lib::{
+        borrow,
         boxed,
         collections,
         rc,
@@ -197,16 +226,67 @@ pub mod test;
 #[cfg_attr(docsrs, doc(cfg(feature = "fmt")))]
 pub mod fmt;
 
+#[cfg(feature = "cbor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbor")))]
+pub mod cbor;
+
+#[cfg(feature = "msgpack")]
+#[cfg_attr(docsrs, doc(cfg(feature = "msgpack")))]
+pub mod msgpack;
+
+#[cfg(feature = "xml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+pub mod xml;
+
+#[cfg(feature = "toml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+pub mod toml;
+
 #[cfg(feature = "serde1_lib")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 pub mod serde;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod owned;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod buffer;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod schema;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod de;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod display;
+
+#[cfg(feature = "alloc")]
+mod diff;
+
+mod transcode;
+
+pub mod format;
 pub mod stream;
 pub mod value;
 
 #[doc(inline)]
 pub use self::error::Error;
 
+pub use self::transcode::transcode;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use self::diff::{
+    diff,
+    Diff,
+};
+
 use self::{
     stream::Stream,
     value::Value,
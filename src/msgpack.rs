@@ -0,0 +1,681 @@
+/*!
+MessagePack support for `sval`.
+
+Add the `msgpack` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["msgpack"]
+```
+
+# Writing MessagePack to `io::Write`
+
+```
+# fn main() -> Result<(), sval::Error> {
+let mut encoder = sval::msgpack::Encoder::new(Vec::new());
+sval::stream(&mut encoder, &vec![1, 2, 3])?;
+
+assert_eq!(vec![0x93, 0x01, 0x02, 0x03], encoder.into_inner());
+# Ok(())
+# }
+```
+
+MessagePack has no indefinite-length maps or sequences. Maps and sequences
+with a known length are written directly. Maps and sequences without one
+are buffered until they end so their length can be written up-front.
+*/
+
+use crate::{
+    std::{
+        io::Write,
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    stream::{
+        self,
+        Stream,
+    },
+};
+
+const NIL: u8 = 0xc0;
+const FALSE: u8 = 0xc2;
+const TRUE: u8 = 0xc3;
+
+const BIN8: u8 = 0xc4;
+const BIN16: u8 = 0xc5;
+const BIN32: u8 = 0xc6;
+
+const FLOAT32: u8 = 0xca;
+const FLOAT64: u8 = 0xcb;
+
+const UINT8: u8 = 0xcc;
+const UINT16: u8 = 0xcd;
+const UINT32: u8 = 0xce;
+const UINT64: u8 = 0xcf;
+
+const INT8: u8 = 0xd0;
+const INT16: u8 = 0xd1;
+const INT32: u8 = 0xd2;
+const INT64: u8 = 0xd3;
+
+const FIXSTR: u8 = 0xa0;
+const STR8: u8 = 0xd9;
+const STR16: u8 = 0xda;
+const STR32: u8 = 0xdb;
+
+const FIXARRAY: u8 = 0x90;
+const ARRAY16: u8 = 0xdc;
+const ARRAY32: u8 = 0xdd;
+
+const FIXMAP: u8 = 0x80;
+const MAP16: u8 = 0xde;
+const MAP32: u8 = 0xdf;
+
+/**
+A stream for writing structured data as MessagePack.
+
+The stream internally wraps a [`std::io::Write`].
+*/
+pub struct Encoder<W> {
+    // The state of each map or sequence being written
+    stack: Vec<Frame>,
+    // Buffers for maps and sequences without a length hint,
+    // one for each frame with an unknown length
+    buffers: Vec<Vec<u8>>,
+    out: W,
+}
+
+struct Frame {
+    map: bool,
+    len: Len,
+}
+
+enum Len {
+    // The number of items remaining in a map or sequence with a known length
+    Remaining(usize),
+    // The number of items seen so far in a buffered map or sequence
+    Counted(usize),
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    /**
+    Create a new MessagePack stream.
+    */
+    pub fn new(out: W) -> Self {
+        Encoder {
+            stack: Vec::new(),
+            buffers: Vec::new(),
+            out,
+        }
+    }
+
+    /**
+    Get the inner writer back out of the stream without ensuring it's valid.
+    */
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> stream::Result {
+        match self.buffers.last_mut() {
+            Some(buf) => buf.extend_from_slice(bytes),
+            None => self.out.write_all(bytes)?,
+        }
+
+        Ok(())
+    }
+
+    fn marker(&mut self, marker: u8, v: &[u8]) -> stream::Result {
+        self.write(&[marker])?;
+        self.write(v)
+    }
+
+    fn unsigned(&mut self, v: u64) -> stream::Result {
+        if v <= 0x7f {
+            self.write(&[v as u8])
+        } else if v <= u8::MAX as u64 {
+            self.marker(UINT8, &[v as u8])
+        } else if v <= u16::MAX as u64 {
+            self.marker(UINT16, &(v as u16).to_be_bytes())
+        } else if v <= u32::MAX as u64 {
+            self.marker(UINT32, &(v as u32).to_be_bytes())
+        } else {
+            self.marker(UINT64, &v.to_be_bytes())
+        }
+    }
+
+    fn signed(&mut self, v: i64) -> stream::Result {
+        if v >= 0 {
+            self.unsigned(v as u64)
+        } else if v >= -32 {
+            // Negative fixints are stored in the marker byte itself
+            self.write(&[v as u8])
+        } else if v >= i8::MIN as i64 {
+            self.marker(INT8, &(v as i8).to_be_bytes())
+        } else if v >= i16::MIN as i64 {
+            self.marker(INT16, &(v as i16).to_be_bytes())
+        } else if v >= i32::MIN as i64 {
+            self.marker(INT32, &(v as i32).to_be_bytes())
+        } else {
+            self.marker(INT64, &v.to_be_bytes())
+        }
+    }
+
+    fn collection_header(&mut self, map: bool, len: usize) -> stream::Result {
+        let (fix, m16, m32) = if map {
+            (FIXMAP, MAP16, MAP32)
+        } else {
+            (FIXARRAY, ARRAY16, ARRAY32)
+        };
+
+        if len <= 15 {
+            self.write(&[fix | len as u8])
+        } else if len <= u16::MAX as usize {
+            self.marker(m16, &(len as u16).to_be_bytes())
+        } else if len <= u32::MAX as usize {
+            self.marker(m32, &(len as u32).to_be_bytes())
+        } else {
+            Err(crate::Error::msg(
+                "the length is too large to encode as MessagePack",
+            ))
+        }
+    }
+
+    fn begin(&mut self, map: bool, len: Option<usize>) -> stream::Result {
+        match len {
+            Some(len) => {
+                // Maps need both a key and a value for each entry
+                let items = if map { len * 2 } else { len };
+
+                self.collection_header(map, len)?;
+                self.stack.push(Frame {
+                    map,
+                    len: Len::Remaining(items),
+                });
+            }
+            None => {
+                self.buffers.push(Vec::new());
+                self.stack.push(Frame {
+                    map,
+                    len: Len::Counted(0),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn item(&mut self) -> stream::Result {
+        match self.stack.last_mut() {
+            Some(Frame {
+                len: Len::Remaining(remaining),
+                ..
+            }) => {
+                if *remaining == 0 {
+                    return Err(crate::Error::msg(
+                        "the number of items is greater than the length hint",
+                    ));
+                }
+
+                *remaining -= 1;
+            }
+            Some(Frame {
+                len: Len::Counted(count),
+                ..
+            }) => *count += 1,
+            None => (),
+        }
+
+        Ok(())
+    }
+
+    fn end(&mut self) -> stream::Result {
+        match self.stack.pop() {
+            Some(Frame {
+                len: Len::Remaining(0),
+                ..
+            }) => Ok(()),
+            Some(Frame {
+                len: Len::Remaining(_),
+                ..
+            }) => Err(crate::Error::msg(
+                "the number of items is less than the length hint",
+            )),
+            Some(Frame {
+                map,
+                len: Len::Counted(count),
+            }) => {
+                let buf = self.buffers.pop().unwrap_or_default();
+                let len = if map { count / 2 } else { count };
+
+                self.collection_header(map, len)?;
+                self.write(&buf)
+            }
+            None => Err(crate::Error::msg("unexpected end of a map or sequence")),
+        }
+    }
+}
+
+impl<'v, W> Stream<'v> for Encoder<W>
+where
+    W: Write,
+{
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
+        self.str(&v.to_string())
+    }
+
+    fn fmt_borrowed(&mut self, v: stream::Arguments<'v>) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, v: stream::Source) -> stream::Result {
+        self.fmt(stream::Arguments::display(&v))
+    }
+
+    fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> stream::Result {
+        self.signed(v)
+    }
+
+    fn u64(&mut self, v: u64) -> stream::Result {
+        self.unsigned(v)
+    }
+
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.unsigned(v as u64)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.unsigned(v as u64)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.unsigned(v as u64)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.signed(v as i64)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.signed(v as i64)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.signed(v as i64)
+    }
+
+    fn i128(&mut self, v: i128) -> stream::Result {
+        if v >= i64::MIN as i128 && v <= i64::MAX as i128 {
+            self.signed(v as i64)
+        } else {
+            self.u128(v as u128)
+        }
+    }
+
+    fn u128(&mut self, v: u128) -> stream::Result {
+        if v <= u64::MAX as u128 {
+            self.unsigned(v as u64)
+        } else {
+            Err(crate::Error::msg(
+                "the integer is too large to encode as MessagePack",
+            ))
+        }
+    }
+
+    fn f64(&mut self, v: f64) -> stream::Result {
+        self.marker(FLOAT64, &v.to_bits().to_be_bytes())
+    }
+
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.marker(FLOAT32, &v.to_bits().to_be_bytes())
+    }
+
+    fn bool(&mut self, v: bool) -> stream::Result {
+        self.write(&[if v { TRUE } else { FALSE }])
+    }
+
+    fn char(&mut self, v: char) -> stream::Result {
+        self.str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn str(&mut self, v: &str) -> stream::Result {
+        if v.len() <= 31 {
+            self.write(&[FIXSTR | v.len() as u8])?;
+        } else if v.len() <= u8::MAX as usize {
+            self.marker(STR8, &[v.len() as u8])?;
+        } else if v.len() <= u16::MAX as usize {
+            self.marker(STR16, &(v.len() as u16).to_be_bytes())?;
+        } else if v.len() <= u32::MAX as usize {
+            self.marker(STR32, &(v.len() as u32).to_be_bytes())?;
+        } else {
+            return Err(crate::Error::msg(
+                "the length is too large to encode as MessagePack",
+            ));
+        }
+
+        self.write(v.as_bytes())
+    }
+
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        if v.len() <= u8::MAX as usize {
+            self.marker(BIN8, &[v.len() as u8])?;
+        } else if v.len() <= u16::MAX as usize {
+            self.marker(BIN16, &(v.len() as u16).to_be_bytes())?;
+        } else if v.len() <= u32::MAX as usize {
+            self.marker(BIN32, &(v.len() as u32).to_be_bytes())?;
+        } else {
+            return Err(crate::Error::msg(
+                "the length is too large to encode as MessagePack",
+            ));
+        }
+
+        self.write(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        self.write(&[NIL])
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> stream::Result {
+        self.begin(true, len)
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        self.end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> stream::Result {
+        self.begin(false, len)
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        self.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+        value::{
+            self,
+            Value,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn encode(v: impl Value) -> Vec<u8> {
+        let mut encoder = Encoder::new(Vec::new());
+        crate::stream(&mut encoder, &v).unwrap();
+
+        encoder.into_inner()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_unsigned() {
+        assert_eq!(vec![0x00], encode(0u64));
+        assert_eq!(vec![0x7f], encode(127u8));
+        assert_eq!(vec![0xcc, 0x80], encode(128u16));
+        assert_eq!(vec![0xcd, 0x01, 0x00], encode(256u32));
+        assert_eq!(vec![0xce, 0x00, 0x01, 0x00, 0x00], encode(65536u64));
+        assert_eq!(
+            vec![0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            encode(u64::MAX)
+        );
+        assert_eq!(vec![0x2a], encode(42i32));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_negative() {
+        assert_eq!(vec![0xff], encode(-1i8));
+        assert_eq!(vec![0xe0], encode(-32i16));
+        assert_eq!(vec![0xd0, 0xdf], encode(-33i32));
+        assert_eq!(vec![0xd1, 0xff, 0x7f], encode(-129i64));
+        assert_eq!(vec![0xd2, 0xff, 0xff, 0x7f, 0xff], encode(-32769i64));
+        assert_eq!(
+            vec![0xd3, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            encode(i64::MIN as i128)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_big_integer() {
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &(u64::MAX as u128 + 1)).is_err());
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &(i64::MIN as i128 - 1)).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_primitives() {
+        assert_eq!(
+            vec![0xcb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a],
+            encode(1.1f64)
+        );
+        assert_eq!(vec![0xca, 0x47, 0xc3, 0x50, 0x00], encode(100000.0f32));
+        assert_eq!(vec![0xc2], encode(false));
+        assert_eq!(vec![0xc3], encode(true));
+        assert_eq!(vec![0xc0], encode(Option::None::<i32>));
+        assert_eq!(vec![0xa0], encode(""));
+        assert_eq!(vec![0xa4, 0x49, 0x45, 0x54, 0x46], encode("IETF"));
+        assert_eq!(vec![0xa2, 0xc3, 0xbc], encode('\u{00fc}'));
+        assert_eq!(
+            vec![0xc4, 0x04, 0x01, 0x02, 0x03, 0x04],
+            encode(value::Bytes::new(&[1, 2, 3, 4]))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_long_str() {
+        let s: String = "a".repeat(32);
+        let mut expected = vec![0xd9, 0x20];
+        expected.extend_from_slice(s.as_bytes());
+        assert_eq!(expected, encode(&*s));
+
+        let s: String = "a".repeat(256);
+        let mut expected = vec![0xda, 0x01, 0x00];
+        expected.extend_from_slice(s.as_bytes());
+        assert_eq!(expected, encode(&*s));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_definite() {
+        assert_eq!(vec![0x90], encode(&[] as &[i32]));
+        assert_eq!(vec![0x93, 0x01, 0x02, 0x03], encode(&[1, 2, 3][..]));
+        assert_eq!(vec![0x80], encode(BTreeMap::<&str, i32>::new()));
+
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![]);
+        map.insert("b", vec![2, 3]);
+
+        assert_eq!(
+            vec![0x82, 0xa1, 0x61, 0x90, 0xa1, 0x62, 0x92, 0x02, 0x03],
+            encode(map)
+        );
+
+        let seq = vec![0u8; 16];
+        let mut expected = vec![0xdc, 0x00, 0x10];
+        expected.extend_from_slice(&seq);
+        assert_eq!(expected, encode(seq));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_unknown_length() {
+        struct Unknown;
+
+        impl Value for Unknown {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin(None)?;
+                stream.map_key(&"a")?;
+                stream.map_value(&1)?;
+                stream.map_key(&"b")?;
+
+                stream.map_value_begin()?.seq_begin(None)?;
+                stream.seq_elem(&2)?;
+                stream.seq_elem(&3)?;
+                stream.seq_end()?;
+
+                stream.map_end()
+            }
+        }
+
+        assert_eq!(
+            vec![0x82, 0xa1, 0x61, 0x01, 0xa1, 0x62, 0x92, 0x02, 0x03],
+            encode(Unknown)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_length_mismatch() {
+        struct TooShort;
+
+        impl Value for TooShort {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(Some(2))?;
+                stream.seq_elem(&1)?;
+                stream.seq_end()
+            }
+        }
+
+        struct TooLong;
+
+        impl Value for TooLong {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin(Some(0))?;
+                stream.map_key(&1)?;
+                stream.map_value(&1)?;
+                stream.map_end()
+            }
+        }
+
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &TooShort).is_err());
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &TooLong).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+/*!
+Owned values.
+
+Add the `alloc` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["alloc"]
+```
+
+An arbitrary [`Value`] can be collected into an [`OwnedValue`], which
+records the structure it streams so it can be replayed later:
+
+```
+# fn main() -> Result<(), sval::Error> {
+let owned = sval::owned::collect(&vec![1, 2, 3])?;
+
+// `owned` can be streamed like the original value
+# let _ = owned;
+# Ok(())
+# }
+```
+
+[`Value`]: ../value/trait.Value.html
+*/
+
+use crate::{
+    value::Value,
+    Error,
+};
+
+#[doc(inline)]
+pub use crate::value::OwnedValue;
+
+/**
+Collect a [`Value`] into an [`OwnedValue`].
+
+If the value fails to stream then the error is returned.
+
+[`Value`]: ../value/trait.Value.html
+*/
+pub fn collect(v: &(impl Value + ?Sized)) -> Result<OwnedValue, Error> {
+    OwnedValue::try_collect(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        test::{
+            self,
+            Token,
+        },
+        value,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn collect_replays_tokens() {
+        let owned = collect(&vec![1, 2]).unwrap();
+
+        assert_eq!(
+            vec![
+                Token::SeqBegin(Some(2)),
+                Token::I32(1),
+                Token::I32(2),
+                Token::SeqEnd,
+            ],
+            test::tokens(&owned)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn collect_err() {
+        struct Broken;
+
+        impl Value for Broken {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(None)?;
+
+                Err(Error::msg("broken"))
+            }
+        }
+
+        assert!(collect(&Broken).is_err());
+    }
+}
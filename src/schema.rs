@@ -0,0 +1,517 @@
+/*!
+Infer the shape of values.
+
+Add the `alloc` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["alloc"]
+```
+
+A [`Schema`] describes the structure a [`Value`] streams without
+retaining any of its data:
+
+```
+# fn main() -> Result<(), sval::Error> {
+use sval::schema::{self, PrimKind, Schema};
+
+let schema = schema::infer(&vec![Some(1), None])?;
+
+assert_eq!(
+    Schema::Seq(Box::new(Schema::Option(Box::new(Schema::Primitive(PrimKind::Signed))))),
+    schema,
+);
+# Ok(())
+# }
+```
+
+[`Value`]: ../value/trait.Value.html
+*/
+
+use crate::{
+    std::{
+        boxed::Box,
+        string::String,
+        vec::Vec,
+    },
+    stream::{
+        self,
+        Stream,
+    },
+    value::Value,
+    Error,
+};
+
+/**
+The kind of a primitive value.
+
+Integers are grouped by signedness rather than width.
+*/
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimKind {
+    Unsigned,
+    Signed,
+    BigUnsigned,
+    BigSigned,
+    Float,
+    Bool,
+    Char,
+    Str,
+    Bytes,
+    Error,
+}
+
+/**
+A description of the shape of a value.
+*/
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    /**
+    A primitive value.
+    */
+    Primitive(PrimKind),
+    /**
+    A sequence where each element has the given schema.
+    */
+    Seq(Box<Schema>),
+    /**
+    A map where each key and value has the given schema.
+    */
+    Map {
+        key: Box<Schema>,
+        value: Box<Schema>,
+    },
+    /**
+    A value that may be empty.
+    */
+    Option(Box<Schema>),
+    /**
+    A value with no single consistent shape.
+
+    Empty maps and sequences use this schema for their contents.
+    */
+    Any,
+}
+
+impl Schema {
+    /**
+    Combine two schemas into one that describes both.
+    */
+    fn merge(self, other: Schema) -> Schema {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Schema::Option(a), Schema::Option(b)) => Schema::Option(Box::new(a.merge_inner(*b))),
+            (Schema::Option(a), b) | (b, Schema::Option(a)) => {
+                Schema::Option(Box::new(a.merge_inner(b)))
+            }
+            (Schema::Seq(a), Schema::Seq(b)) => Schema::Seq(Box::new(a.merge_inner(*b))),
+            (Schema::Map { key: ak, value: av }, Schema::Map { key: bk, value: bv }) => {
+                Schema::Map {
+                    key: Box::new(ak.merge_inner(*bk)),
+                    value: Box::new(av.merge_inner(*bv)),
+                }
+            }
+            _ => Schema::Any,
+        }
+    }
+
+    /**
+    Combine two schemas where `Any` stands for a shape that hasn't been seen yet.
+    */
+    fn merge_inner(self, other: Schema) -> Schema {
+        match (self, other) {
+            (Schema::Any, other) | (other, Schema::Any) => other,
+            (a, b) => a.merge(b),
+        }
+    }
+}
+
+/**
+Infer the [`Schema`] of a [`Value`].
+
+If the value fails to stream then the error is returned.
+
+[`Value`]: ../value/trait.Value.html
+*/
+pub fn infer(v: &(impl Value + ?Sized)) -> Result<Schema, Error> {
+    let mut stream = SchemaStream {
+        root: None,
+        stack: Vec::new(),
+    };
+
+    crate::stream_owned(&mut stream, v)?;
+
+    stream
+        .root
+        .ok_or_else(|| Error::msg("the value didn't stream anything"))
+}
+
+enum Frame {
+    Seq {
+        elem: Option<Schema>,
+    },
+    Map {
+        key: Option<Schema>,
+        value: Option<Schema>,
+        is_value: bool,
+    },
+}
+
+struct SchemaStream {
+    root: Option<Schema>,
+    stack: Vec<Frame>,
+}
+
+fn merge_into(slot: &mut Option<Schema>, schema: Schema) {
+    *slot = Some(match slot.take() {
+        Some(current) => current.merge(schema),
+        None => schema,
+    });
+}
+
+fn finish(slot: Option<Schema>) -> Box<Schema> {
+    Box::new(slot.unwrap_or(Schema::Any))
+}
+
+impl SchemaStream {
+    fn push(&mut self, schema: Schema) -> stream::Result {
+        match self.stack.last_mut() {
+            None => merge_into(&mut self.root, schema),
+            Some(Frame::Seq { elem }) => merge_into(elem, schema),
+            Some(Frame::Map {
+                key,
+                value,
+                is_value,
+            }) => {
+                if *is_value {
+                    merge_into(value, schema)
+                } else {
+                    merge_into(key, schema)
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn primitive(&mut self, kind: PrimKind) -> stream::Result {
+        self.push(Schema::Primitive(kind))
+    }
+
+    fn end(&mut self) -> stream::Result {
+        let schema = match self.stack.pop() {
+            Some(Frame::Seq { elem }) => Schema::Seq(finish(elem)),
+            Some(Frame::Map { key, value, .. }) => Schema::Map {
+                key: finish(key),
+                value: finish(value),
+            },
+            None => return Err(Error::msg("unexpected end of a map or sequence")),
+        };
+
+        self.push(schema)
+    }
+
+    fn set_is_value(&mut self, v: bool) -> stream::Result {
+        match self.stack.last_mut() {
+            Some(Frame::Map { is_value, .. }) => {
+                *is_value = v;
+
+                Ok(())
+            }
+            _ => Err(Error::msg("expected a map")),
+        }
+    }
+}
+
+impl<'v> Stream<'v> for SchemaStream {
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, _: stream::Arguments) -> stream::Result {
+        self.primitive(PrimKind::Str)
+    }
+
+    fn fmt_borrowed(&mut self, v: stream::Arguments<'v>) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, _: stream::Source) -> stream::Result {
+        self.primitive(PrimKind::Error)
+    }
+
+    fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, _: &str) -> stream::Result {
+        self.primitive(PrimKind::Error)
+    }
+
+    fn i64(&mut self, _: i64) -> stream::Result {
+        self.primitive(PrimKind::Signed)
+    }
+
+    fn u64(&mut self, _: u64) -> stream::Result {
+        self.primitive(PrimKind::Unsigned)
+    }
+
+    fn u8(&mut self, _: u8) -> stream::Result {
+        self.primitive(PrimKind::Unsigned)
+    }
+
+    fn u16(&mut self, _: u16) -> stream::Result {
+        self.primitive(PrimKind::Unsigned)
+    }
+
+    fn u32(&mut self, _: u32) -> stream::Result {
+        self.primitive(PrimKind::Unsigned)
+    }
+
+    fn i8(&mut self, _: i8) -> stream::Result {
+        self.primitive(PrimKind::Signed)
+    }
+
+    fn i16(&mut self, _: i16) -> stream::Result {
+        self.primitive(PrimKind::Signed)
+    }
+
+    fn i32(&mut self, _: i32) -> stream::Result {
+        self.primitive(PrimKind::Signed)
+    }
+
+    fn i128(&mut self, _: i128) -> stream::Result {
+        self.primitive(PrimKind::BigSigned)
+    }
+
+    fn u128(&mut self, _: u128) -> stream::Result {
+        self.primitive(PrimKind::BigUnsigned)
+    }
+
+    fn f64(&mut self, _: f64) -> stream::Result {
+        self.primitive(PrimKind::Float)
+    }
+
+    fn f32(&mut self, _: f32) -> stream::Result {
+        self.primitive(PrimKind::Float)
+    }
+
+    fn bool(&mut self, _: bool) -> stream::Result {
+        self.primitive(PrimKind::Bool)
+    }
+
+    fn char(&mut self, _: char) -> stream::Result {
+        self.primitive(PrimKind::Char)
+    }
+
+    fn str(&mut self, _: &str) -> stream::Result {
+        self.primitive(PrimKind::Str)
+    }
+
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, _: &[u8]) -> stream::Result {
+        self.primitive(PrimKind::Bytes)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        self.push(Schema::Option(Box::new(Schema::Any)))
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> stream::Result {
+        self.stack.push(Frame::Map {
+            key: None,
+            value: None,
+            is_value: false,
+        });
+
+        Ok(())
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        self.set_is_value(false)
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        self.set_is_value(true)
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        self.end()
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> stream::Result {
+        self.stack.push(Frame::Seq { elem: None });
+
+        Ok(())
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        self.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::std::{
+        collections::BTreeMap,
+        vec,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn prim(kind: PrimKind) -> Schema {
+        Schema::Primitive(kind)
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn infer_primitive() {
+        assert_eq!(prim(PrimKind::Signed), infer(&1).unwrap());
+        assert_eq!(prim(PrimKind::Unsigned), infer(&1u8).unwrap());
+        assert_eq!(prim(PrimKind::Str), infer("a").unwrap());
+        assert_eq!(
+            Schema::Option(Box::new(Schema::Any)),
+            infer(&None::<i32>).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn infer_seq() {
+        assert_eq!(
+            Schema::Seq(Box::new(prim(PrimKind::Bool))),
+            infer(&vec![true, false]).unwrap()
+        );
+
+        assert_eq!(
+            Schema::Seq(Box::new(Schema::Any)),
+            infer(&Vec::<bool>::new()).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn infer_seq_option() {
+        assert_eq!(
+            Schema::Seq(Box::new(Schema::Option(Box::new(prim(PrimKind::Signed))))),
+            infer(&vec![None, Some(1), None]).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn infer_seq_mixed() {
+        assert_eq!(
+            Schema::Seq(Box::new(Schema::Any)),
+            infer(&(1, "a")).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn infer_map() {
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![1]);
+        map.insert("b", vec![]);
+
+        assert_eq!(
+            Schema::Map {
+                key: Box::new(prim(PrimKind::Str)),
+                value: Box::new(Schema::Seq(Box::new(prim(PrimKind::Signed)))),
+            },
+            infer(&map).unwrap()
+        );
+    }
+}
@@ -5,7 +5,13 @@ use serde1_lib::ser;
 /**
 An error encountered during serialization.
 */
-pub(super) struct Error(crate::Error);
+pub struct Error(crate::Error);
+
+impl From<Error> for crate::Error {
+    fn from(err: Error) -> Self {
+        err.0
+    }
+}
 
 impl From<fmt::Error> for Error {
     fn from(_: fmt::Error) -> Self {
@@ -48,6 +48,9 @@ a type that implements [`Value`](../value/trait.Value.html):
 let my_value = sval::serde::v1::to_value(my_serialize);
 ```
 
+A `serde::Serialize` can also be serialized directly into an existing
+[`Stream`](../stream/trait.Stream.html) using a [`StreamSerializer`](struct.StreamSerializer.html).
+
 [`value::Stream::map_key_begin`]: ../value/struct.Stream.html#method.map_key_begin
 [`value::Stream::map_value_begin`]: ../value/struct.Stream.html#method.map_value_begin
 [`value::Stream::seq_elem_begin`]: ../value/struct.Stream.html#method.seq_elem_begin
@@ -58,8 +61,10 @@ mod error;
 mod to_serialize;
 mod to_value;
 
+#[cfg(all(test, feature = "std"))]
+mod tests;
+
 use crate::{
-    Error,
     Stream,
     Value,
 };
@@ -70,8 +75,12 @@ use serde1_lib::ser::{
 };
 
 pub use self::{
+    error::Error,
     to_serialize::ToSerialize,
-    to_value::ToValue,
+    to_value::{
+        StreamSerializer,
+        ToValue,
+    },
 };
 
 /**
@@ -88,6 +97,20 @@ where
     ToSerialize(value)
 }
 
+/**
+Convert a reference to a [`Value`] into a [`Serialize`].
+
+Unlike [`to_serialize`], the value may be unsized, like a `str` or `[T]`.
+A `ToSerialize<V>` can also be coerced into a `ToSerialize` over an
+unsized value, like `ToSerialize<[T]>` or `ToSerialize<dyn Value>`.
+*/
+pub fn to_serialize_ref<V>(value: &V) -> &ToSerialize<V>
+where
+    V: Value + ?Sized,
+{
+    ToSerialize::from_ref(value)
+}
+
 /**
 Serialize a [`Value`] using the given [`Serializer`].
 */
@@ -111,7 +134,10 @@ where
 /**
 Stream a [`Serialize`] using the given [`Stream`].
 */
-pub fn stream_owned<'a, S>(stream: impl Stream<'a>, value: impl Serialize) -> Result<(), Error> {
+pub fn stream_owned<'a, S>(
+    stream: impl Stream<'a>,
+    value: impl Serialize,
+) -> Result<(), crate::Error> {
     crate::stream_owned(stream, to_value(value))
 }
 
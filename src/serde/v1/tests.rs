@@ -0,0 +1,514 @@
+/*
+Property tests for the bridge from `sval` to `serde`.
+
+Values that implement both `Value` and `Serialize` are serialized
+directly with `serde` and through `to_serialize`, and the tokens
+each one produces are compared.
+*/
+
+use super::*;
+
+use crate::std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    convert::TryFrom,
+    fmt,
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+
+use serde1_lib::ser::{
+    self,
+    SerializeMap,
+    SerializeSeq,
+    SerializeStruct,
+    SerializeStructVariant,
+    SerializeTuple,
+    SerializeTupleStruct,
+    SerializeTupleVariant,
+};
+
+use quickcheck::quickcheck;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    // Floats are compared by their bits so `NaN`s are equal
+    F32(u32),
+    F64(u64),
+    Char(char),
+    Str(String),
+    Bytes(Vec<u8>),
+    None,
+    Some,
+    Unit,
+    UnitStruct(&'static str),
+    UnitVariant(&'static str, &'static str),
+    NewtypeStruct(&'static str),
+    NewtypeVariant(&'static str, &'static str),
+    Seq(Option<usize>),
+    Tuple(usize),
+    TupleStruct(&'static str, usize),
+    TupleVariant(&'static str, &'static str, usize),
+    Map(Option<usize>),
+    Struct(&'static str, usize),
+    StructVariant(&'static str, &'static str, usize),
+    Field(&'static str),
+    Elem,
+    Key,
+    Value,
+    End,
+}
+
+#[derive(Debug)]
+struct TestError(String);
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl crate::std::error::Error for TestError {}
+
+impl ser::Error for TestError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        TestError(msg.to_string())
+    }
+}
+
+#[derive(Default)]
+struct Tokens {
+    tokens: Vec<Token>,
+    human_readable: bool,
+}
+
+impl Tokens {
+    fn push(&mut self, token: Token) -> Result<(), TestError> {
+        self.tokens.push(token);
+
+        Ok(())
+    }
+}
+
+fn tokens(v: impl Serialize) -> Vec<Token> {
+    let mut tokens = Tokens::default();
+    v.serialize(&mut tokens).expect("failed to serialize");
+
+    tokens.tokens
+}
+
+fn human_readable_tokens(v: impl Serialize) -> Vec<Token> {
+    let mut tokens = Tokens {
+        tokens: Vec::new(),
+        human_readable: true,
+    };
+    v.serialize(&mut tokens).expect("failed to serialize");
+
+    tokens.tokens
+}
+
+impl<'a> Serializer for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), TestError> {
+        self.push(Token::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), TestError> {
+        self.push(Token::I8(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), TestError> {
+        self.push(Token::I16(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), TestError> {
+        self.push(Token::I32(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), TestError> {
+        self.push(Token::I64(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), TestError> {
+        self.push(Token::I128(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), TestError> {
+        self.push(Token::U8(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), TestError> {
+        self.push(Token::U16(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), TestError> {
+        self.push(Token::U32(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), TestError> {
+        self.push(Token::U64(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), TestError> {
+        self.push(Token::U128(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), TestError> {
+        self.push(Token::F32(v.to_bits()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), TestError> {
+        self.push(Token::F64(v.to_bits()))
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), TestError> {
+        self.push(Token::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), TestError> {
+        self.push(Token::Str(v.into()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), TestError> {
+        self.push(Token::Bytes(v.into()))
+    }
+
+    fn serialize_none(self) -> Result<(), TestError> {
+        self.push(Token::None)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, v: &T) -> Result<(), TestError> {
+        self.push(Token::Some)?;
+        v.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), TestError> {
+        self.push(Token::Unit)
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), TestError> {
+        self.push(Token::UnitStruct(name))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), TestError> {
+        self.push(Token::UnitVariant(name, variant))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        v: &T,
+    ) -> Result<(), TestError> {
+        self.push(Token::NewtypeStruct(name))?;
+        v.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        _: u32,
+        variant: &'static str,
+        v: &T,
+    ) -> Result<(), TestError> {
+        self.push(Token::NewtypeVariant(name, variant))?;
+        v.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, TestError> {
+        self.push(Token::Seq(len))?;
+
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self, TestError> {
+        self.push(Token::Tuple(len))?;
+
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, len: usize) -> Result<Self, TestError> {
+        self.push(Token::TupleStruct(name, len))?;
+
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, TestError> {
+        self.push(Token::TupleVariant(name, variant, len))?;
+
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, TestError> {
+        self.push(Token::Map(len))?;
+
+        Ok(self)
+    }
+
+    fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self, TestError> {
+        self.push(Token::Struct(name, len))?;
+
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self, TestError> {
+        self.push(Token::StructVariant(name, variant, len))?;
+
+        Ok(self)
+    }
+}
+
+impl<'a> SerializeSeq for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, v: &T) -> Result<(), TestError> {
+        self.push(Token::Elem)?;
+        v.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), TestError> {
+        self.push(Token::End)
+    }
+}
+
+impl<'a> SerializeTuple for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, v: &T) -> Result<(), TestError> {
+        SerializeSeq::serialize_element(self, v)
+    }
+
+    fn end(self) -> Result<(), TestError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> SerializeTupleStruct for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, v: &T) -> Result<(), TestError> {
+        SerializeSeq::serialize_element(self, v)
+    }
+
+    fn end(self) -> Result<(), TestError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> SerializeTupleVariant for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, v: &T) -> Result<(), TestError> {
+        SerializeSeq::serialize_element(self, v)
+    }
+
+    fn end(self) -> Result<(), TestError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl<'a> SerializeMap for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, k: &T) -> Result<(), TestError> {
+        self.push(Token::Key)?;
+        k.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, v: &T) -> Result<(), TestError> {
+        self.push(Token::Value)?;
+        v.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), TestError> {
+        self.push(Token::End)
+    }
+}
+
+impl<'a> SerializeStruct for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        k: &'static str,
+        v: &T,
+    ) -> Result<(), TestError> {
+        self.push(Token::Field(k))?;
+        v.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), TestError> {
+        self.push(Token::End)
+    }
+}
+
+impl<'a> SerializeStructVariant for &'a mut Tokens {
+    type Ok = ();
+    type Error = TestError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        k: &'static str,
+        v: &T,
+    ) -> Result<(), TestError> {
+        SerializeStruct::serialize_field(self, k, v)
+    }
+
+    fn end(self) -> Result<(), TestError> {
+        SerializeStruct::end(self)
+    }
+}
+
+/**
+Check that a value produces the same tokens through the bridge as it does directly.
+*/
+fn same_tokens<V>(v: V) -> bool
+where
+    V: Value + Serialize,
+{
+    tokens(&v) == tokens(to_serialize(&v))
+}
+
+quickcheck! {
+    fn bridge_bool(v: bool) -> bool {
+        same_tokens(v)
+    }
+
+    fn bridge_signed(a: i8, b: i16, c: i32, d: i64, e: i128, f: isize) -> bool {
+        same_tokens(a)
+            && same_tokens(b)
+            && same_tokens(c)
+            && same_tokens(d)
+            && same_tokens(e)
+            && same_tokens(f)
+    }
+
+    fn bridge_unsigned(a: u8, b: u16, c: u32, d: u64, e: u128, f: usize) -> bool {
+        same_tokens(a)
+            && same_tokens(b)
+            && same_tokens(c)
+            && same_tokens(d)
+            && same_tokens(e)
+            && same_tokens(f)
+    }
+
+    fn bridge_float(a: f32, b: f64) -> bool {
+        same_tokens(a) && same_tokens(b)
+    }
+
+    fn bridge_char(v: char) -> bool {
+        same_tokens(v)
+    }
+
+    fn bridge_str(v: String) -> bool {
+        same_tokens(v)
+    }
+
+    fn bridge_seq(a: Vec<i32>, b: Vec<String>, c: Vec<Vec<u8>>) -> bool {
+        same_tokens(a) && same_tokens(b) && same_tokens(c)
+    }
+
+    fn bridge_map(a: HashMap<String, i64>, b: BTreeMap<u8, Vec<bool>>) -> bool {
+        same_tokens(a) && same_tokens(b)
+    }
+
+    fn bridge_big_integer(a: i128, b: u128) -> bool {
+        // Human-readable serializers get 128bit integers that fit in 64 bits as 64bit integers
+        let expected_a = match i64::try_from(a) {
+            Ok(a) => Token::I64(a),
+            Err(_) => Token::Str(a.to_string()),
+        };
+        let expected_b = match u64::try_from(b) {
+            Ok(b) => Token::U64(b),
+            Err(_) => Token::Str(b.to_string()),
+        };
+
+        human_readable_tokens(to_serialize(a)) == [expected_a]
+            && human_readable_tokens(to_serialize(b)) == [expected_b.clone()]
+            && human_readable_tokens(to_serialize(vec![b])) == [Token::Seq(Some(1)), Token::Elem, expected_b, Token::End]
+    }
+
+    fn bridge_option(v: Option<Vec<Option<i32>>>) -> bool {
+        // `sval` doesn't distinguish `Some(v)` from `v`
+        let expected = tokens(&v)
+            .into_iter()
+            .filter(|token| *token != Token::Some)
+            .collect::<Vec<_>>();
+
+        expected == tokens(to_serialize(&v))
+    }
+}
+
+#[test]
+fn bridge_big_integer_out_of_range() {
+    // Human-readable serializers get 128bit integers that don't fit in 64 bits as strings
+    assert_eq!(
+        vec![Token::Str(u128::MAX.to_string())],
+        human_readable_tokens(to_serialize(u128::MAX))
+    );
+    assert_eq!(
+        vec![Token::Str(i128::MIN.to_string())],
+        human_readable_tokens(to_serialize(i128::MIN))
+    );
+
+    assert_eq!(
+        vec![Token::U128(u128::MAX)],
+        tokens(to_serialize(u128::MAX))
+    );
+    assert_eq!(
+        vec![Token::I128(i128::MIN)],
+        tokens(to_serialize(i128::MIN))
+    );
+}
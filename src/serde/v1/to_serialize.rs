@@ -1,4 +1,5 @@
 use crate::{
+    std::convert::TryFrom,
     stream,
     value,
 };
@@ -17,11 +18,20 @@ use serde1_lib::ser::{
 The result of calling [`sval::serde::v1::to_serialize`](fn.to_serialize.html).
 */
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ToSerialize<V>(pub(super) V);
+#[repr(transparent)]
+pub struct ToSerialize<V: ?Sized>(pub(super) V);
+
+impl<V: ?Sized> ToSerialize<V> {
+    pub(super) fn from_ref(value: &V) -> &Self {
+        // SAFETY: `ToSerialize` is `repr(transparent)` over `V`
+        // so they have the same layout, including for unsized `V`
+        unsafe { &*(value as *const V as *const ToSerialize<V>) }
+    }
+}
 
 impl<V> Serialize for ToSerialize<V>
 where
-    V: value::Value,
+    V: value::Value + ?Sized,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -219,6 +229,75 @@ impl<'a> stream::Source<'a> {
     }
 }
 
+struct SerializeBytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for SerializeBytes<'a> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_bytes(self.0)
+    }
+}
+
+/**
+A 128bit unsigned integer.
+
+Some serializers don't support 128bit integers. Since a serializer
+is consumed when it fails, we can't retry a 128bit integer as something
+else. Instead, human-readable serializers are given 128bit integers that
+fit in 64 bits as 64bit integers, which look the same in their output.
+With the `alloc` feature, larger integers are given to human-readable
+serializers as strings. Other serializers always receive 128bit integers,
+so their encoding doesn't depend on the value.
+*/
+struct SerializeU128(u128);
+
+impl Serialize for SerializeU128 {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !s.is_human_readable() {
+            return s.serialize_u128(self.0);
+        }
+
+        match u64::try_from(self.0) {
+            Ok(v) => s.serialize_u64(v),
+            #[cfg(feature = "alloc")]
+            Err(_) => s.collect_str(&self.0),
+            #[cfg(not(feature = "alloc"))]
+            Err(_) => s.serialize_u128(self.0),
+        }
+    }
+}
+
+/**
+A 128bit signed integer.
+
+This follows the same rules as `SerializeU128`.
+*/
+struct SerializeI128(i128);
+
+impl Serialize for SerializeI128 {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !s.is_human_readable() {
+            return s.serialize_i128(self.0);
+        }
+
+        match i64::try_from(self.0) {
+            Ok(v) => s.serialize_i64(v),
+            #[cfg(feature = "alloc")]
+            Err(_) => s.collect_str(&self.0),
+            #[cfg(not(feature = "alloc"))]
+            Err(_) => s.serialize_i128(self.0),
+        }
+    }
+}
+
 impl<'a> stream::Value<'a> {
     fn into_serialize(self) -> impl Serialize + 'a {
         ToSerialize(self)
@@ -239,6 +318,10 @@ mod no_alloc_support {
     where
         S: Serializer,
     {
+        fn hint_skip(&self) -> bool {
+            false
+        }
+
         fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
             self.serialize_any(v.into_serialize())
         }
@@ -255,6 +338,10 @@ mod no_alloc_support {
             self.serialize_any(v.into_serialize())
         }
 
+        fn error_str(&mut self, v: &str) -> stream::Result {
+            self.serialize_any(v)
+        }
+
         fn i64(&mut self, v: i64) -> stream::Result {
             self.serialize_any(v)
         }
@@ -263,18 +350,46 @@ mod no_alloc_support {
             self.serialize_any(v)
         }
 
-        fn i128(&mut self, v: i128) -> stream::Result {
+        fn u8(&mut self, v: u8) -> stream::Result {
             self.serialize_any(v)
         }
 
-        fn u128(&mut self, v: u128) -> stream::Result {
+        fn u16(&mut self, v: u16) -> stream::Result {
+            self.serialize_any(v)
+        }
+
+        fn u32(&mut self, v: u32) -> stream::Result {
+            self.serialize_any(v)
+        }
+
+        fn i8(&mut self, v: i8) -> stream::Result {
+            self.serialize_any(v)
+        }
+
+        fn i16(&mut self, v: i16) -> stream::Result {
             self.serialize_any(v)
         }
 
+        fn i32(&mut self, v: i32) -> stream::Result {
+            self.serialize_any(v)
+        }
+
+        fn i128(&mut self, v: i128) -> stream::Result {
+            self.serialize_any(SerializeI128(v))
+        }
+
+        fn u128(&mut self, v: u128) -> stream::Result {
+            self.serialize_any(SerializeU128(v))
+        }
+
         fn f64(&mut self, v: f64) -> stream::Result {
             self.serialize_any(v)
         }
 
+        fn f32(&mut self, v: f32) -> stream::Result {
+            self.serialize_any(v)
+        }
+
         fn bool(&mut self, v: bool) -> stream::Result {
             self.serialize_any(v)
         }
@@ -287,10 +402,38 @@ mod no_alloc_support {
             self.serialize_any(v)
         }
 
+        fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+            self.str(v)
+        }
+
+        fn typed_begin(&mut self, _: &str) -> stream::Result {
+            Ok(())
+        }
+
+        fn typed_end(&mut self) -> stream::Result {
+            Ok(())
+        }
+
+        fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+            self.typed_begin(tag)
+        }
+
+        fn tagged_end(&mut self, _: &str) -> stream::Result {
+            self.typed_end()
+        }
+
         fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
             self.serialize_any(v)
         }
 
+        fn bytes(&mut self, v: &[u8]) -> stream::Result {
+            self.serialize_any(SerializeBytes(v))
+        }
+
+        fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+            self.serialize_any(SerializeBytes(v))
+        }
+
         fn none(&mut self) -> stream::Result {
             self.serialize_any(Option::None::<()>)
         }
@@ -312,6 +455,10 @@ mod no_alloc_support {
             }
         }
 
+        fn map_begin_unknown(&mut self) -> stream::Result {
+            self.map_begin(None)
+        }
+
         fn map_key(&mut self) -> stream::Result {
             self.pos = Some(Pos::Key);
 
@@ -340,6 +487,20 @@ mod no_alloc_support {
             self.serialize_value(v.into_serialize())
         }
 
+        fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+            self.map_key_collect(k)?;
+            self.map_value_collect(v)
+        }
+
+        fn map_flat_entry_collect_borrowed(
+            &mut self,
+            k: stream::Value<'v>,
+            v: stream::Value<'v>,
+        ) -> stream::Result {
+            self.map_key_collect_borrowed(k)?;
+            self.map_value_collect_borrowed(v)
+        }
+
         fn map_end(&mut self) -> stream::Result {
             let map = self.take_current().take_serialize_map();
             self.ok = Some(map.end().map_err(err("error completing map"))?);
@@ -364,12 +525,20 @@ mod no_alloc_support {
             }
         }
 
+        fn seq_begin_unknown(&mut self) -> stream::Result {
+            self.seq_begin(None)
+        }
+
         fn seq_elem(&mut self) -> stream::Result {
             self.pos = Some(Pos::Elem);
 
             Ok(())
         }
 
+        fn seq_elem_end(&mut self) -> stream::Result {
+            Ok(())
+        }
+
         fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
             self.serialize_elem(v.into_serialize())
         }
@@ -391,6 +560,8 @@ mod no_alloc_support {
 mod alloc_support {
     use super::*;
 
+    use crate::std::string::String;
+
     pub(super) use crate::value::owned::{
         Token,
         TokenBuf,
@@ -446,6 +617,10 @@ mod alloc_support {
     where
         S: Serializer,
     {
+        fn hint_skip(&self) -> bool {
+            false
+        }
+
         fn seq_begin(&mut self, len: Option<usize>) -> stream::Result {
             match self.buffer() {
                 None => {
@@ -470,6 +645,10 @@ mod alloc_support {
             }
         }
 
+        fn seq_begin_unknown(&mut self) -> stream::Result {
+            self.seq_begin(None)
+        }
+
         fn seq_elem(&mut self) -> stream::Result {
             match self.buffer() {
                 None => {
@@ -481,6 +660,10 @@ mod alloc_support {
             }
         }
 
+        fn seq_elem_end(&mut self) -> stream::Result {
+            Ok(())
+        }
+
         fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
             match self.buffer() {
                 None => self.serialize_elem(v.into_serialize()),
@@ -538,6 +721,10 @@ mod alloc_support {
             }
         }
 
+        fn map_begin_unknown(&mut self) -> stream::Result {
+            self.map_begin(None)
+        }
+
         fn map_key(&mut self) -> stream::Result {
             match self.buffer() {
                 None => {
@@ -588,6 +775,19 @@ mod alloc_support {
             self.map_value_collect(v)
         }
 
+        fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+            self.map_key_collect(k)?;
+            self.map_value_collect(v)
+        }
+
+        fn map_flat_entry_collect_borrowed(
+            &mut self,
+            k: stream::Value<'v>,
+            v: stream::Value<'v>,
+        ) -> stream::Result {
+            self.map_flat_entry_collect(k, v)
+        }
+
         fn map_end(&mut self) -> stream::Result {
             match self.buffer() {
                 None => {
@@ -622,16 +822,58 @@ mod alloc_support {
             }
         }
 
-        fn i128(&mut self, v: i128) -> stream::Result {
+        fn u8(&mut self, v: u8) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.u8(v),
+            }
+        }
+
+        fn u16(&mut self, v: u16) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.u16(v),
+            }
+        }
+
+        fn u32(&mut self, v: u32) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.u32(v),
+            }
+        }
+
+        fn i8(&mut self, v: i8) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.i8(v),
+            }
+        }
+
+        fn i16(&mut self, v: i16) -> stream::Result {
             match self.buffer() {
                 None => self.serialize_any(v),
+                Some(buffered) => buffered.i16(v),
+            }
+        }
+
+        fn i32(&mut self, v: i32) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.i32(v),
+            }
+        }
+
+        fn i128(&mut self, v: i128) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(SerializeI128(v)),
                 Some(buffered) => buffered.i128(v),
             }
         }
 
         fn u128(&mut self, v: u128) -> stream::Result {
             match self.buffer() {
-                None => self.serialize_any(v),
+                None => self.serialize_any(SerializeU128(v)),
                 Some(buffered) => buffered.u128(v),
             }
         }
@@ -643,6 +885,13 @@ mod alloc_support {
             }
         }
 
+        fn f32(&mut self, v: f32) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.f32(v),
+            }
+        }
+
         fn bool(&mut self, v: bool) -> stream::Result {
             match self.buffer() {
                 None => self.serialize_any(v),
@@ -664,10 +913,51 @@ mod alloc_support {
             }
         }
 
+        fn typed_str(&mut self, type_name: &str, v: &str) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.typed_str(type_name, v),
+            }
+        }
+
+        fn typed_begin(&mut self, _: &str) -> stream::Result {
+            Ok(())
+        }
+
+        fn typed_end(&mut self) -> stream::Result {
+            Ok(())
+        }
+
+        fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+            self.typed_begin(tag)
+        }
+
+        fn tagged_end(&mut self, _: &str) -> stream::Result {
+            self.typed_end()
+        }
+
         fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
             self.str(v)
         }
 
+        fn str_owned(&mut self, v: String) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(&*v),
+                Some(buffered) => buffered.str_owned(v),
+            }
+        }
+
+        fn bytes(&mut self, v: &[u8]) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(SerializeBytes(v)),
+                Some(buffered) => buffered.bytes(v),
+            }
+        }
+
+        fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+            self.bytes(v)
+        }
+
         fn none(&mut self) -> stream::Result {
             match self.buffer() {
                 None => self.serialize_any(Option::None::<()>),
@@ -686,6 +976,13 @@ mod alloc_support {
             self.error(v)
         }
 
+        fn error_str(&mut self, v: &str) -> stream::Result {
+            match self.buffer() {
+                None => self.serialize_any(v),
+                Some(buffered) => buffered.error_str(v),
+            }
+        }
+
         fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
             match self.buffer() {
                 None => self.serialize_any(v.into_serialize()),
@@ -774,6 +1071,36 @@ mod alloc_support {
 
                         v.serialize(serializer)
                     }
+                    TokenKind::U8(v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        v.serialize(serializer)
+                    }
+                    TokenKind::U16(v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        v.serialize(serializer)
+                    }
+                    TokenKind::U32(v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        v.serialize(serializer)
+                    }
+                    TokenKind::I8(v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        v.serialize(serializer)
+                    }
+                    TokenKind::I16(v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        v.serialize(serializer)
+                    }
+                    TokenKind::I32(v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        v.serialize(serializer)
+                    }
                     TokenKind::BigSigned(v) => {
                         reader.expect_empty().map_err(S::Error::custom)?;
 
@@ -789,6 +1116,11 @@ mod alloc_support {
 
                         v.serialize(serializer)
                     }
+                    TokenKind::Float32(v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        v.serialize(serializer)
+                    }
                     TokenKind::Bool(v) => {
                         reader.expect_empty().map_err(S::Error::custom)?;
 
@@ -804,6 +1136,11 @@ mod alloc_support {
 
                         v.serialize(serializer)
                     }
+                    TokenKind::Bytes(ref v) => {
+                        reader.expect_empty().map_err(S::Error::custom)?;
+
+                        serializer.serialize_bytes(v)
+                    }
                     TokenKind::Error(ref v) => {
                         reader.expect_empty().map_err(S::Error::custom)?;
 
@@ -33,44 +33,80 @@ where
 {
     fn stream<'s, 'v>(&'s self, stream: value::Stream<'s, 'v>) -> value::Result {
         self.0
-            .serialize(Serializer(stream))
+            .serialize(StreamSerializer(stream))
             .map_err(err("error streaming serde"))?;
 
         Ok(())
     }
 }
 
-struct Serializer<T>(T);
+/**
+A [`Serializer`] that streams into an [`sval::Stream`].
+
+This lets a `Serialize` be driven directly into an existing [`Stream`]
+without wrapping it in [`to_value`](fn.to_value.html) first:
+
+```
+# fn main() -> Result<(), sval::serde::v1::Error> {
+# #[cfg(feature = "alloc")]
+# {
+use sval::{buffer::Buffer, serde::v1::StreamSerializer};
+use serde1_lib::Serialize;
+
+let mut buffer = Buffer::new();
+
+vec![1, 2, 3].serialize(StreamSerializer::new(&mut buffer))?;
+# }
+# Ok(())
+# }
+```
 
-impl<'a, 'v> ser::Serializer for Serializer<value::Stream<'a, 'v>> {
+[`Serializer`]: https://docs.rs/serde/1/serde/ser/trait.Serializer.html
+[`sval::Stream`]: ../../stream/trait.Stream.html
+[`Stream`]: ../../stream/trait.Stream.html
+*/
+pub struct StreamSerializer<'s, 'v>(value::Stream<'s, 'v>);
+
+impl<'s, 'v> StreamSerializer<'s, 'v> {
+    /**
+    Wrap an implementation of [`Stream`].
+
+    [`Stream`]: ../../stream/trait.Stream.html
+    */
+    pub fn new(stream: &'s mut impl stream::Stream<'v>) -> Self {
+        StreamSerializer(value::Stream::new(stream))
+    }
+}
+
+impl<'a, 'v> ser::Serializer for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
-    type SerializeSeq = Serializer<value::Stream<'a, 'v>>;
-    type SerializeTuple = Serializer<value::Stream<'a, 'v>>;
-    type SerializeTupleStruct = Serializer<value::Stream<'a, 'v>>;
-    type SerializeTupleVariant = Serializer<value::Stream<'a, 'v>>;
-    type SerializeMap = Serializer<value::Stream<'a, 'v>>;
-    type SerializeStruct = Serializer<value::Stream<'a, 'v>>;
-    type SerializeStructVariant = Serializer<value::Stream<'a, 'v>>;
+    type SerializeSeq = StreamSerializer<'a, 'v>;
+    type SerializeTuple = StreamSerializer<'a, 'v>;
+    type SerializeTupleStruct = StreamSerializer<'a, 'v>;
+    type SerializeTupleVariant = StreamSerializer<'a, 'v>;
+    type SerializeMap = StreamSerializer<'a, 'v>;
+    type SerializeStruct = StreamSerializer<'a, 'v>;
+    type SerializeStructVariant = StreamSerializer<'a, 'v>;
 
     fn serialize_bool(mut self, v: bool) -> Result<Self::Ok, Self::Error> {
         self.0.bool(v)?;
         Ok(())
     }
 
-    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-        self.serialize_i64(i64::from(v))?;
+    fn serialize_i8(mut self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.0.i8(v)?;
         Ok(())
     }
 
-    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-        self.serialize_i64(i64::from(v))?;
+    fn serialize_i16(mut self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.0.i16(v)?;
         Ok(())
     }
 
-    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
-        self.serialize_i64(i64::from(v))?;
+    fn serialize_i32(mut self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.0.i32(v)?;
         Ok(())
     }
 
@@ -79,18 +115,18 @@ impl<'a, 'v> ser::Serializer for Serializer<value::Stream<'a, 'v>> {
         Ok(())
     }
 
-    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
-        self.serialize_u64(u64::from(v))?;
+    fn serialize_u8(mut self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.0.u8(v)?;
         Ok(())
     }
 
-    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-        self.serialize_u64(u64::from(v))?;
+    fn serialize_u16(mut self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.0.u16(v)?;
         Ok(())
     }
 
-    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
-        self.serialize_u64(u64::from(v))?;
+    fn serialize_u32(mut self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.0.u32(v)?;
         Ok(())
     }
 
@@ -109,8 +145,8 @@ impl<'a, 'v> ser::Serializer for Serializer<value::Stream<'a, 'v>> {
         Ok(())
     }
 
-    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-        self.serialize_f64(f64::from(v))?;
+    fn serialize_f32(mut self, v: f32) -> Result<Self::Ok, Self::Error> {
+        self.0.f32(v)?;
         Ok(())
     }
 
@@ -140,13 +176,7 @@ impl<'a, 'v> ser::Serializer for Serializer<value::Stream<'a, 'v>> {
     }
 
     fn serialize_bytes(mut self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        self.0.seq_begin(Some(v.len()))?;
-
-        for b in v {
-            self.0.owned().seq_elem(&b)?;
-        }
-
-        self.0.seq_end()?;
+        self.0.owned().bytes(v)?;
         Ok(())
     }
 
@@ -214,7 +244,10 @@ impl<'a, 'v> ser::Serializer for Serializer<value::Stream<'a, 'v>> {
     }
 
     fn serialize_seq(mut self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-        self.0.seq_begin(len)?;
+        match len {
+            Some(len) => self.0.seq_begin(Some(len))?,
+            None => self.0.seq_begin_unknown()?,
+        }
         Ok(self)
     }
 
@@ -248,7 +281,10 @@ impl<'a, 'v> ser::Serializer for Serializer<value::Stream<'a, 'v>> {
     }
 
     fn serialize_map(mut self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        self.0.map_begin(len)?;
+        match len {
+            Some(len) => self.0.map_begin(Some(len))?,
+            None => self.0.map_begin_unknown()?,
+        }
         Ok(self)
     }
 
@@ -277,7 +313,7 @@ impl<'a, 'v> ser::Serializer for Serializer<value::Stream<'a, 'v>> {
     }
 }
 
-impl<'a, 'v> SerializeSeq for Serializer<value::Stream<'a, 'v>> {
+impl<'a, 'v> SerializeSeq for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
@@ -295,7 +331,7 @@ impl<'a, 'v> SerializeSeq for Serializer<value::Stream<'a, 'v>> {
     }
 }
 
-impl<'a, 'v> SerializeTuple for Serializer<value::Stream<'a, 'v>> {
+impl<'a, 'v> SerializeTuple for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
@@ -313,7 +349,7 @@ impl<'a, 'v> SerializeTuple for Serializer<value::Stream<'a, 'v>> {
     }
 }
 
-impl<'a, 'v> SerializeTupleStruct for Serializer<value::Stream<'a, 'v>> {
+impl<'a, 'v> SerializeTupleStruct for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
@@ -331,7 +367,7 @@ impl<'a, 'v> SerializeTupleStruct for Serializer<value::Stream<'a, 'v>> {
     }
 }
 
-impl<'a, 'v> SerializeTupleVariant for Serializer<value::Stream<'a, 'v>> {
+impl<'a, 'v> SerializeTupleVariant for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
@@ -351,7 +387,7 @@ impl<'a, 'v> SerializeTupleVariant for Serializer<value::Stream<'a, 'v>> {
     }
 }
 
-impl<'a, 'v> SerializeMap for Serializer<value::Stream<'a, 'v>> {
+impl<'a, 'v> SerializeMap for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
@@ -377,7 +413,7 @@ impl<'a, 'v> SerializeMap for Serializer<value::Stream<'a, 'v>> {
     }
 }
 
-impl<'a, 'v> SerializeStruct for Serializer<value::Stream<'a, 'v>> {
+impl<'a, 'v> SerializeStruct for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
@@ -396,7 +432,7 @@ impl<'a, 'v> SerializeStruct for Serializer<value::Stream<'a, 'v>> {
     }
 }
 
-impl<'a, 'v> SerializeStructVariant for Serializer<value::Stream<'a, 'v>> {
+impl<'a, 'v> SerializeStructVariant for StreamSerializer<'a, 'v> {
     type Ok = ();
     type Error = Error;
 
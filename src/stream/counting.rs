@@ -0,0 +1,321 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that counts the structures it receives.
+
+The stream doesn't retain any data, only how many maps,
+sequences, and primitives were streamed. Nested structures
+are counted along with their parents.
+*/
+#[derive(Debug, Default, Clone)]
+pub struct CountingStream {
+    maps: usize,
+    seqs: usize,
+    primitives: usize,
+}
+
+impl CountingStream {
+    /**
+    Create a new stream with all counts at zero.
+    */
+    pub fn new() -> Self {
+        CountingStream::default()
+    }
+
+    /**
+    The number of maps that have begun.
+    */
+    pub fn map_count(&self) -> usize {
+        self.maps
+    }
+
+    /**
+    The number of sequences that have begun.
+    */
+    pub fn seq_count(&self) -> usize {
+        self.seqs
+    }
+
+    /**
+    The number of primitives, including empty values.
+
+    Map keys and values are counted as primitives when they are primitives.
+    */
+    pub fn primitive_count(&self) -> usize {
+        self.primitives
+    }
+
+    fn primitive(&mut self) -> Result {
+        self.primitives += 1;
+
+        Ok(())
+    }
+}
+
+impl<'v> Stream<'v> for CountingStream {
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, _: Arguments) -> Result {
+        self.primitive()
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, _: Source) -> Result {
+        self.primitive()
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, _: &str) -> Result {
+        self.primitive()
+    }
+
+    fn i64(&mut self, _: i64) -> Result {
+        self.primitive()
+    }
+
+    fn u64(&mut self, _: u64) -> Result {
+        self.primitive()
+    }
+
+    fn u8(&mut self, _: u8) -> Result {
+        self.primitive()
+    }
+
+    fn u16(&mut self, _: u16) -> Result {
+        self.primitive()
+    }
+
+    fn u32(&mut self, _: u32) -> Result {
+        self.primitive()
+    }
+
+    fn i8(&mut self, _: i8) -> Result {
+        self.primitive()
+    }
+
+    fn i16(&mut self, _: i16) -> Result {
+        self.primitive()
+    }
+
+    fn i32(&mut self, _: i32) -> Result {
+        self.primitive()
+    }
+
+    fn i128(&mut self, _: i128) -> Result {
+        self.primitive()
+    }
+
+    fn u128(&mut self, _: u128) -> Result {
+        self.primitive()
+    }
+
+    fn f64(&mut self, _: f64) -> Result {
+        self.primitive()
+    }
+
+    fn f32(&mut self, _: f32) -> Result {
+        self.primitive()
+    }
+
+    fn bool(&mut self, _: bool) -> Result {
+        self.primitive()
+    }
+
+    fn char(&mut self, _: char) -> Result {
+        self.primitive()
+    }
+
+    fn str(&mut self, _: &str) -> Result {
+        self.primitive()
+    }
+
+    fn typed_str(&mut self, _: &str, _: &str) -> Result {
+        self.primitive()
+    }
+
+    fn typed_begin(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> Result {
+        self.typed_end()
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.str(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, _: &[u8]) -> Result {
+        self.primitive()
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> Result {
+        self.primitive()
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        self.maps += 1;
+
+        Ok(())
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        self.map_begin(None)
+    }
+
+    fn map_key(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        self.seqs += 1;
+
+        Ok(())
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn count(v: &impl crate::value::Value) -> CountingStream {
+        let mut stream = CountingStream::new();
+        crate::stream(&mut stream, v).unwrap();
+
+        stream
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn count_primitive() {
+        let stream = count(&1);
+
+        assert_eq!(1, stream.primitive_count());
+        assert_eq!(0, stream.map_count());
+        assert_eq!(0, stream.seq_count());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn count_nested_seq() {
+        let stream = count(&(1, &[Some(2), None][..], "a"));
+
+        assert_eq!(4, stream.primitive_count());
+        assert_eq!(0, stream.map_count());
+        assert_eq!(2, stream.seq_count());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn count_map() {
+        use crate::std::{
+            collections::BTreeMap,
+            vec,
+        };
+
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![1, 2]);
+        map.insert("b", vec![]);
+
+        let stream = count(&map);
+
+        assert_eq!(4, stream.primitive_count());
+        assert_eq!(1, stream.map_count());
+        assert_eq!(2, stream.seq_count());
+    }
+}
@@ -0,0 +1,355 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that fails when maps and sequences are nested too deeply.
+
+Each map or sequence that begins inside another one increases the
+depth by one, and ending it decreases the depth again. A map or
+sequence that would exceed the maximum depth returns an error instead
+of being forwarded to the inner stream. This is useful for protecting
+recursive streams from untrusted data.
+*/
+pub struct DepthLimitedStream<S> {
+    inner: S,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<S> DepthLimitedStream<S> {
+    /**
+    Wrap a stream so it accepts at most `max_depth` levels of nesting.
+    */
+    pub fn new(inner: S, max_depth: usize) -> Self {
+        DepthLimitedStream {
+            inner,
+            depth: 0,
+            max_depth,
+        }
+    }
+
+    /**
+    Get the inner stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /**
+    Begin a map or sequence, failing if it's nested too deeply.
+    */
+    fn begin(&mut self) -> Result {
+        if self.depth == self.max_depth {
+            return Err(crate::Error::msg(
+                "the stream received values nested deeper than its limit",
+            ));
+        }
+
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    /**
+    End a map or sequence.
+    */
+    fn end(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+impl<'v, S> Stream<'v> for DepthLimitedStream<S>
+where
+    S: Stream<'v>,
+{
+    fn hint_skip(&self) -> bool {
+        self.inner.hint_skip()
+    }
+
+    fn fmt(&mut self, v: Arguments) -> Result {
+        self.inner.fmt(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.inner.fmt_borrowed(v)
+    }
+
+    fn error(&mut self, v: Source) -> Result {
+        self.inner.error(v)
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.inner.error_borrowed(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> Result {
+        self.inner.error_str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        self.inner.i64(v)
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        self.inner.u64(v)
+    }
+
+    fn u8(&mut self, v: u8) -> Result {
+        self.inner.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        self.inner.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        self.inner.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        self.inner.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        self.inner.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        self.inner.i32(v)
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        self.inner.i128(v)
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        self.inner.u128(v)
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        self.inner.f64(v)
+    }
+
+    fn f32(&mut self, v: f32) -> Result {
+        self.inner.f32(v)
+    }
+
+    fn bool(&mut self, v: bool) -> Result {
+        self.inner.bool(v)
+    }
+
+    fn char(&mut self, v: char) -> Result {
+        self.inner.char(v)
+    }
+
+    fn str(&mut self, v: &str) -> Result {
+        self.inner.str(v)
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        self.inner.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        self.inner.typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> Result {
+        self.inner.typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.inner.tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        self.inner.tagged_end(tag)
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.inner.str_borrowed(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.inner.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.inner.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.inner.bytes_borrowed(v)
+    }
+
+    fn none(&mut self) -> Result {
+        self.inner.none()
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> Result {
+        self.begin()?;
+        self.inner.map_begin(len)
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        self.begin()?;
+        self.inner.map_begin_unknown()
+    }
+
+    fn map_key(&mut self) -> Result {
+        self.inner.map_key()
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.map_key()?;
+        k.stream_owned(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_value(&mut self) -> Result {
+        self.inner.map_value()
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.map_value()?;
+        v.stream_owned(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.end();
+        self.inner.map_end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        self.begin()?;
+        self.inner.seq_begin(len)
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.begin()?;
+        self.inner.seq_begin_unknown()
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        self.inner.seq_elem()
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        self.inner.seq_elem_end()
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.seq_elem()?;
+        v.stream_owned(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.end();
+        self.inner.seq_end()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            vec,
+            vec::Vec,
+        },
+        stream::CountingStream,
+        test,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn depth_limited_at_limit() {
+        let v = vec![vec![vec![1]]];
+
+        let mut limited = DepthLimitedStream::new(Buffer::new(), 3);
+        crate::stream(&mut limited, &v).unwrap();
+
+        assert_eq!(test::tokens(&v), test::tokens(&limited.into_inner()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn depth_limited_over_limit() {
+        let v = vec![vec![vec![1]]];
+
+        let mut limited = DepthLimitedStream::new(CountingStream::new(), 2);
+        assert!(crate::stream(&mut limited, &v).is_err());
+
+        // The innermost sequence and its contents were never forwarded
+        let counts = limited.into_inner();
+        assert_eq!(2, counts.seq_count());
+        assert_eq!(0, counts.primitive_count());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn depth_limited_end_decrements() {
+        let mut limited = DepthLimitedStream::new(CountingStream::new(), 1);
+
+        limited.seq_begin(None).unwrap();
+        assert!(limited.map_begin(None).is_err());
+        limited.seq_end().unwrap();
+
+        // Siblings at the same depth are accepted once the previous one ends
+        limited.map_begin(None).unwrap();
+        limited.map_end().unwrap();
+        limited.seq_begin(None).unwrap();
+        limited.seq_end().unwrap();
+
+        let counts = limited.into_inner();
+        assert_eq!(1, counts.map_count());
+        assert_eq!(2, counts.seq_count());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn depth_limited_zero() {
+        let mut limited = DepthLimitedStream::new(Buffer::new(), 0);
+
+        crate::stream(&mut limited, &1).unwrap();
+        assert!(crate::stream(&mut limited, &Vec::<i32>::new()).is_err());
+    }
+}
@@ -12,6 +12,7 @@ error types, that may have backtraces and other metadata.
 but streams can still work with them by formatting them or passing
 them along even in no-std environments where the `Error` trait isn't available.
 */
+#[derive(Clone, Copy)]
 pub struct Source<'v> {
     #[cfg(feature = "std")]
     inner: self::std_support::SourceError<'v>,
@@ -65,6 +66,7 @@ mod std_support {
 
     use super::Source;
 
+    #[derive(Clone, Copy)]
     pub(super) struct SourceError<'v>(&'v (dyn Error + 'static));
 
     impl<'v> fmt::Debug for SourceError<'v> {
@@ -0,0 +1,618 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that drops map entries based on their keys.
+
+Each map key that's a string is passed to a predicate. If the
+predicate returns `false` then the key and its value are
+dropped instead of being forwarded to the inner stream.
+Keys that aren't strings are always forwarded.
+
+The filter applies to maps at any depth. Since entries may be
+dropped, maps are always forwarded without a length hint.
+*/
+pub struct KeyFilter<S, F> {
+    inner: S,
+    predicate: F,
+    state: State,
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    // Tokens are forwarded to the inner stream
+    Forward,
+    // A map key has begun but its value hasn't been seen yet
+    Key,
+    // A map key was rejected and the map value is expected next
+    SkipKey,
+    // A map value is being skipped at the given depth
+    SkipValue(usize),
+}
+
+enum Event<'a> {
+    Primitive(Option<&'a str>),
+    Begin,
+    End,
+    MapKey,
+    MapValue,
+    SeqElem,
+}
+
+impl<S, F> KeyFilter<S, F> {
+    /**
+    Wrap a stream in a key filter.
+    */
+    pub fn new(inner: S, predicate: F) -> Self {
+        KeyFilter {
+            inner,
+            predicate,
+            state: State::Forward,
+        }
+    }
+
+    /**
+    Get the inner stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<'v, S, F> KeyFilter<S, F>
+where
+    S: Stream<'v>,
+    F: FnMut(&str) -> bool,
+{
+    /**
+    Process the next event, returning whether or not it should be forwarded.
+    */
+    fn next(&mut self, event: Event) -> Result<bool> {
+        match (self.state, event) {
+            (State::Forward, Event::MapKey) => {
+                // Hold on to the key until we know whether it's accepted
+                self.state = State::Key;
+
+                Ok(false)
+            }
+            (State::Forward, _) => Ok(true),
+            (State::Key, Event::Primitive(Some(key))) if !(self.predicate)(key) => {
+                self.state = State::SkipKey;
+
+                Ok(false)
+            }
+            (State::Key, event) => {
+                self.state = State::Forward;
+                self.inner.map_key()?;
+
+                self.next(event)
+            }
+            (State::SkipKey, Event::MapValue) => {
+                self.state = State::SkipValue(0);
+
+                Ok(false)
+            }
+            (State::SkipKey, event) => {
+                self.state = State::Forward;
+
+                self.next(event)
+            }
+            (State::SkipValue(depth), Event::Primitive(_)) => {
+                if depth == 0 {
+                    self.state = State::Forward;
+                }
+
+                Ok(false)
+            }
+            (State::SkipValue(depth), Event::Begin) => {
+                self.state = State::SkipValue(depth + 1);
+
+                Ok(false)
+            }
+            (State::SkipValue(depth), Event::End) => {
+                self.state = if depth <= 1 {
+                    State::Forward
+                } else {
+                    State::SkipValue(depth - 1)
+                };
+
+                Ok(false)
+            }
+            (State::SkipValue(_), _) => Ok(false),
+        }
+    }
+}
+
+impl<'v, S, F> Stream<'v> for KeyFilter<S, F>
+where
+    S: Stream<'v>,
+    F: FnMut(&str) -> bool,
+{
+    fn hint_skip(&self) -> bool {
+        // Values are always needed to track the state of the filter
+        false
+    }
+
+    fn fmt(&mut self, v: Arguments) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.fmt(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.fmt_borrowed(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn error(&mut self, v: Source) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.error(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.error_borrowed(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn error_str(&mut self, v: &str) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.error_str(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.i64(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.u64(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn u8(&mut self, v: u8) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.u8(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.u16(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.u32(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.i8(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.i16(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.i32(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.i128(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.u128(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.f64(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn f32(&mut self, v: f32) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.f32(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn bool(&mut self, v: bool) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.bool(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn char(&mut self, v: char) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.char(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn str(&mut self, v: &str) -> Result {
+        if self.next(Event::Primitive(Some(v)))? {
+            self.inner.str(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        if self.next(Event::Primitive(Some(v)))? {
+            self.inner.typed_str(type_name, v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        if self.next(Event::Begin)? {
+            self.inner.typed_begin(tag)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn typed_end(&mut self) -> Result {
+        if self.next(Event::End)? {
+            self.inner.typed_end()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        if self.next(Event::Begin)? {
+            self.inner.tagged_begin(tag)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        if self.next(Event::End)? {
+            self.inner.tagged_end(tag)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        if self.next(Event::Primitive(Some(v)))? {
+            self.inner.str_borrowed(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        if self.next(Event::Primitive(Some(&v)))? {
+            self.inner.str_owned(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.bytes(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.bytes_borrowed(v)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn none(&mut self) -> Result {
+        if self.next(Event::Primitive(None))? {
+            self.inner.none()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        // Entries may be dropped, so the length hint can't be trusted
+        if self.next(Event::Begin)? {
+            self.inner.map_begin(None)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        if self.next(Event::Begin)? {
+            self.inner.map_begin_unknown()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn map_key(&mut self) -> Result {
+        if self.next(Event::MapKey)? {
+            self.inner.map_key()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.map_key()?;
+        k.stream_owned(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_value(&mut self) -> Result {
+        if self.next(Event::MapValue)? {
+            self.inner.map_value()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.map_value()?;
+        v.stream_owned(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        if self.next(Event::End)? {
+            self.inner.map_end()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        if self.next(Event::Begin)? {
+            self.inner.seq_begin(len)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        if self.next(Event::Begin)? {
+            self.inner.seq_begin_unknown()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        if self.next(Event::SeqElem)? {
+            self.inner.seq_elem()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        if self.next(Event::SeqElem)? {
+            self.inner.seq_elem_end()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.seq_elem()?;
+        v.stream_owned(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        if self.next(Event::End)? {
+            self.inner.seq_end()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            collections::BTreeMap,
+            vec,
+            vec::Vec,
+        },
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn filter(v: &impl crate::value::Value, predicate: impl FnMut(&str) -> bool) -> Vec<Token> {
+        let mut filter = KeyFilter::new(Buffer::new(), predicate);
+        crate::stream(&mut filter, v).unwrap();
+
+        test::tokens(&filter.into_inner())
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn filter_drops_rejected_keys() {
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![1]);
+        map.insert("secret", vec![2, 3]);
+        map.insert("z", vec![]);
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(None),
+                Token::Str("a".into()),
+                Token::SeqBegin(Some(1)),
+                Token::I32(1),
+                Token::SeqEnd,
+                Token::Str("z".into()),
+                Token::SeqBegin(Some(0)),
+                Token::SeqEnd,
+                Token::MapEnd,
+            ],
+            filter(&map, |k| k != "secret")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn filter_nested_maps() {
+        let mut inner = BTreeMap::new();
+        inner.insert("secret", 1);
+        inner.insert("b", 2);
+
+        let mut outer = BTreeMap::new();
+        outer.insert("a", inner.clone());
+        outer.insert("secret", inner);
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(None),
+                Token::Str("a".into()),
+                Token::MapBegin(None),
+                Token::Str("b".into()),
+                Token::I32(2),
+                Token::MapEnd,
+                Token::MapEnd,
+            ],
+            filter(&outer, |k| k != "secret")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn filter_forwards_non_string_keys() {
+        let mut map = BTreeMap::new();
+        map.insert(1, "a");
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(None),
+                Token::I32(1),
+                Token::Str("a".into()),
+                Token::MapEnd,
+            ],
+            filter(&map, |_| false)
+        );
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn filter_length_checking_encoder() {
+        let mut map = BTreeMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+
+        let mut filter = KeyFilter::new(crate::cbor::Encoder::new(Vec::new()), |k: &str| k == "a");
+        crate::stream(&mut filter, &map).unwrap();
+
+        // The filtered map is encoded without a length
+        let expected = crate::value::MapAdapter::new(vec![("a", 1)].into_iter());
+
+        let mut encoder = crate::cbor::Encoder::new(Vec::new());
+        crate::stream(&mut encoder, &expected).unwrap();
+
+        assert_eq!(encoder.into_inner(), filter.into_inner().into_inner());
+    }
+}
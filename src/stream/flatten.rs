@@ -0,0 +1,450 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that unwraps sequences containing a single element.
+
+Some formats treat a sequence of one element the same as the element
+itself. A sequence that begins with a length of exactly `1` has its
+begin, element, and end dropped so only its element is forwarded to
+the inner stream. All other sequences are forwarded unchanged.
+
+Sequences are only unwrapped in the first 64 levels of nesting.
+Sequences that are nested more deeply are always forwarded.
+*/
+pub struct FlattenStream<S> {
+    inner: S,
+    depth: usize,
+    flattened: u64,
+    has_elem: u64,
+}
+
+impl<S> FlattenStream<S> {
+    /**
+    Wrap a stream so single-element sequences are unwrapped.
+    */
+    pub fn new(inner: S) -> Self {
+        FlattenStream {
+            inner,
+            depth: 0,
+            flattened: 0,
+            has_elem: 0,
+        }
+    }
+
+    /**
+    Get the inner stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /**
+    Get the bit that tracks the map or sequence at the current depth.
+    */
+    fn bit(&self) -> Option<u64> {
+        match self.depth {
+            depth @ 1..=64 => Some(1 << (depth - 1)),
+            _ => None,
+        }
+    }
+
+    /**
+    Whether the sequence at the current depth is being unwrapped.
+    */
+    fn is_flattened(&self) -> bool {
+        self.bit()
+            .map(|bit| self.flattened & bit != 0)
+            .unwrap_or(false)
+    }
+
+    /**
+    Begin a map or sequence, returning whether it can be unwrapped.
+    */
+    fn begin(&mut self, flatten: bool) -> bool {
+        self.depth += 1;
+
+        match self.bit() {
+            Some(bit) => {
+                self.has_elem &= !bit;
+
+                if flatten {
+                    self.flattened |= bit;
+                } else {
+                    self.flattened &= !bit;
+                }
+
+                flatten
+            }
+            None => false,
+        }
+    }
+
+    /**
+    End a map or sequence.
+    */
+    fn end(&mut self) {
+        if let Some(bit) = self.bit() {
+            self.flattened &= !bit;
+            self.has_elem &= !bit;
+        }
+
+        self.depth = self.depth.saturating_sub(1);
+    }
+}
+
+impl<'v, S> Stream<'v> for FlattenStream<S>
+where
+    S: Stream<'v>,
+{
+    fn hint_skip(&self) -> bool {
+        // The element of an unwrapped sequence is always needed
+        false
+    }
+
+    fn fmt(&mut self, v: Arguments) -> Result {
+        self.inner.fmt(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.inner.fmt_borrowed(v)
+    }
+
+    fn error(&mut self, v: Source) -> Result {
+        self.inner.error(v)
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.inner.error_borrowed(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> Result {
+        self.inner.error_str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        self.inner.i64(v)
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        self.inner.u64(v)
+    }
+
+    fn u8(&mut self, v: u8) -> Result {
+        self.inner.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        self.inner.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        self.inner.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        self.inner.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        self.inner.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        self.inner.i32(v)
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        self.inner.i128(v)
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        self.inner.u128(v)
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        self.inner.f64(v)
+    }
+
+    fn f32(&mut self, v: f32) -> Result {
+        self.inner.f32(v)
+    }
+
+    fn bool(&mut self, v: bool) -> Result {
+        self.inner.bool(v)
+    }
+
+    fn char(&mut self, v: char) -> Result {
+        self.inner.char(v)
+    }
+
+    fn str(&mut self, v: &str) -> Result {
+        self.inner.str(v)
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        self.inner.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        self.inner.typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> Result {
+        self.inner.typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.inner.tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        self.inner.tagged_end(tag)
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.inner.str_borrowed(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.inner.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.inner.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.inner.bytes_borrowed(v)
+    }
+
+    fn none(&mut self) -> Result {
+        self.inner.none()
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> Result {
+        self.begin(false);
+        self.inner.map_begin(len)
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        self.begin(false);
+        self.inner.map_begin_unknown()
+    }
+
+    fn map_key(&mut self) -> Result {
+        self.inner.map_key()
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.map_key()?;
+        k.stream_owned(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_value(&mut self) -> Result {
+        self.inner.map_value()
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.map_value()?;
+        v.stream_owned(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.end();
+        self.inner.map_end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        if self.begin(len == Some(1)) {
+            return Ok(());
+        }
+
+        self.inner.seq_begin(len)
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.begin(false);
+        self.inner.seq_begin_unknown()
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        if !self.is_flattened() {
+            return self.inner.seq_elem();
+        }
+
+        // The sequence is unwrapped, so it can't carry more than its one element
+        match self.bit() {
+            Some(bit) if self.has_elem & bit == 0 => {
+                self.has_elem |= bit;
+
+                Ok(())
+            }
+            _ => Err(crate::Error::msg(
+                "a sequence with a length of 1 received more than one element",
+            )),
+        }
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        // The element of an unwrapped sequence is forwarded on its own
+        if self.is_flattened() {
+            return Ok(());
+        }
+
+        self.inner.seq_elem_end()
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.seq_elem()?;
+        v.stream_owned(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        if !self.is_flattened() {
+            self.end();
+            return self.inner.seq_end();
+        }
+
+        let has_elem = self
+            .bit()
+            .map(|bit| self.has_elem & bit != 0)
+            .unwrap_or(false);
+        self.end();
+
+        if has_elem {
+            Ok(())
+        } else {
+            Err(crate::Error::msg(
+                "a sequence with a length of 1 received no elements",
+            ))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            vec,
+            vec::Vec,
+        },
+        stream::NullSink,
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn flatten(v: &(impl crate::Value + ?Sized)) -> Result<Vec<Token>> {
+        let mut flatten = FlattenStream::new(Buffer::new());
+        crate::stream(&mut flatten, v)?;
+
+        Ok(test::tokens(&flatten.into_inner()))
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_single_elem() {
+        assert_eq!(vec![Token::I32(1)], flatten(&vec![1]).unwrap());
+        assert_eq!(vec![Token::I32(1)], flatten(&vec![vec![1]]).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_other_seqs_unchanged() {
+        let v: Vec<Vec<i32>> = vec![vec![], vec![1, 2]];
+
+        assert_eq!(test::tokens(&v), flatten(&v).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_nested_in_map() {
+        let mut map = crate::std::collections::BTreeMap::new();
+        map.insert("a", vec![1]);
+        map.insert("b", vec![1, 2]);
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(2)),
+                Token::Str("a".into()),
+                Token::I32(1),
+                Token::Str("b".into()),
+                Token::SeqBegin(Some(2)),
+                Token::I32(1),
+                Token::I32(2),
+                Token::SeqEnd,
+                Token::MapEnd,
+            ],
+            flatten(&map).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_wrong_len() {
+        let mut flatten = FlattenStream::new(Buffer::new());
+
+        flatten.seq_begin(Some(1)).unwrap();
+        flatten.seq_elem().unwrap();
+        flatten.i32(1).unwrap();
+        assert!(flatten.seq_elem().is_err());
+
+        let mut flatten = FlattenStream::new(Buffer::new());
+
+        flatten.seq_begin(Some(1)).unwrap();
+        assert!(flatten.seq_end().is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_lazy_with_skipping_stream() {
+        let v = vec![crate::value::Lazy::new(|| 1)];
+
+        let mut flatten = FlattenStream::new(NullSink::new());
+        assert!(!flatten.hint_skip());
+
+        crate::stream(&mut flatten, &v).unwrap();
+    }
+}
@@ -3,8 +3,10 @@ use crate::std::fmt;
 /**
 A formattable value.
 */
+#[derive(Clone, Copy)]
 pub struct Arguments<'v>(ArgumentsInner<'v>);
 
+#[derive(Clone, Copy)]
 enum ArgumentsInner<'v> {
     Debug(&'v dyn fmt::Debug),
     Display(&'v dyn fmt::Display),
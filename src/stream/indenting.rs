@@ -0,0 +1,358 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that writes text and can receive whitespace between tokens.
+
+Whitespace given to the stream must not change the meaning of the
+text it writes. That's the case for formats like JSON, where whitespace
+between tokens is ignored. `sval_json::Formatter` implements this trait.
+*/
+pub trait Whitespace {
+    /**
+    Write some whitespace.
+    */
+    fn whitespace(&mut self, ws: &str) -> Result;
+}
+
+impl<T: ?Sized> Whitespace for &mut T
+where
+    T: Whitespace,
+{
+    #[inline]
+    fn whitespace(&mut self, ws: &str) -> Result {
+        (**self).whitespace(ws)
+    }
+}
+
+/**
+A stream that pretty-prints the text written by another one.
+
+Each map key and sequence element begins on a new line, indented
+by some number of spaces for each level of nesting. The ends of
+non-empty maps and sequences also begin on a new line. Map values
+are separated from their keys by a single space.
+
+The inner stream receives whitespace through the [`Whitespace`] trait.
+
+[`Whitespace`]: trait.Whitespace.html
+*/
+pub struct IndentingStream<S> {
+    inner: S,
+    width: usize,
+    depth: usize,
+    is_current_depth_empty: bool,
+}
+
+impl<S> IndentingStream<S> {
+    /**
+    Wrap a stream so each level of nesting is indented by `width` spaces.
+    */
+    pub fn new(inner: S, width: usize) -> Self {
+        IndentingStream {
+            inner,
+            width,
+            depth: 0,
+            is_current_depth_empty: true,
+        }
+    }
+
+    /**
+    Get the inner stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> IndentingStream<S>
+where
+    S: Whitespace,
+{
+    /**
+    Begin a new line at the current depth.
+    */
+    fn newline(&mut self) -> Result {
+        const SPACES: &str = "                ";
+
+        self.inner.whitespace("\n")?;
+
+        let mut remaining = self.depth * self.width;
+        while remaining > 0 {
+            let len = remaining.min(SPACES.len());
+            self.inner.whitespace(&SPACES[..len])?;
+
+            remaining -= len;
+        }
+
+        Ok(())
+    }
+
+    /**
+    Begin a map or sequence.
+    */
+    fn begin(&mut self) {
+        self.depth += 1;
+        self.is_current_depth_empty = true;
+    }
+
+    /**
+    Begin a map key or sequence element on a new line.
+    */
+    fn item(&mut self) -> Result {
+        self.is_current_depth_empty = false;
+        self.newline()
+    }
+
+    /**
+    End a map or sequence, putting its end on a new line if it wasn't empty.
+    */
+    fn end(&mut self) -> Result {
+        self.depth = self.depth.saturating_sub(1);
+
+        if !self.is_current_depth_empty {
+            self.newline()?;
+        }
+
+        // The map or sequence that just ended is an item
+        // in its parent, so the parent isn't empty
+        self.is_current_depth_empty = false;
+
+        Ok(())
+    }
+}
+
+impl<'v, S> Stream<'v> for IndentingStream<S>
+where
+    S: Stream<'v> + Whitespace,
+{
+    fn hint_skip(&self) -> bool {
+        self.inner.hint_skip()
+    }
+
+    fn fmt(&mut self, v: Arguments) -> Result {
+        self.inner.fmt(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.inner.fmt_borrowed(v)
+    }
+
+    fn error(&mut self, v: Source) -> Result {
+        self.inner.error(v)
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.inner.error_borrowed(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> Result {
+        self.inner.error_str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        self.inner.i64(v)
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        self.inner.u64(v)
+    }
+
+    fn u8(&mut self, v: u8) -> Result {
+        self.inner.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        self.inner.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        self.inner.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        self.inner.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        self.inner.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        self.inner.i32(v)
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        self.inner.i128(v)
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        self.inner.u128(v)
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        self.inner.f64(v)
+    }
+
+    fn f32(&mut self, v: f32) -> Result {
+        self.inner.f32(v)
+    }
+
+    fn bool(&mut self, v: bool) -> Result {
+        self.inner.bool(v)
+    }
+
+    fn char(&mut self, v: char) -> Result {
+        self.inner.char(v)
+    }
+
+    fn str(&mut self, v: &str) -> Result {
+        self.inner.str(v)
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        self.inner.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        self.inner.typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> Result {
+        self.inner.typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.inner.tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        self.inner.tagged_end(tag)
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.inner.str_borrowed(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.inner.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.inner.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.inner.bytes_borrowed(v)
+    }
+
+    fn none(&mut self) -> Result {
+        self.inner.none()
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> Result {
+        self.inner.map_begin(len)?;
+        self.begin();
+
+        Ok(())
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        self.inner.map_begin_unknown()?;
+        self.begin();
+
+        Ok(())
+    }
+
+    fn map_key(&mut self) -> Result {
+        self.inner.map_key()?;
+        self.item()
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.map_key()?;
+        k.stream_owned(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_value(&mut self) -> Result {
+        self.inner.map_value()?;
+        self.inner.whitespace(" ")
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.map_value()?;
+        v.stream_owned(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.end()?;
+        self.inner.map_end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        self.inner.seq_begin(len)?;
+        self.begin();
+
+        Ok(())
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.inner.seq_begin_unknown()?;
+        self.begin();
+
+        Ok(())
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        self.inner.seq_elem()?;
+        self.item()
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        self.inner.seq_elem_end()
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.seq_elem()?;
+        v.stream_owned(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.end()?;
+        self.inner.seq_end()
+    }
+}
@@ -0,0 +1,379 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that fails once it's been given too many tokens.
+
+Every call to a method on the stream, like beginning a map or
+streaming a primitive, counts as a token. Once the limit is reached,
+any further tokens return an error instead of being forwarded to the
+inner stream. This is useful for capping the size of untrusted data.
+*/
+pub struct LimitedStream<S> {
+    inner: S,
+    remaining: usize,
+}
+
+impl<S> LimitedStream<S> {
+    /**
+    Wrap a stream so it accepts at most `max_tokens` tokens.
+    */
+    pub fn new(inner: S, max_tokens: usize) -> Self {
+        LimitedStream {
+            inner,
+            remaining: max_tokens,
+        }
+    }
+
+    /**
+    Get the inner stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /**
+    Count the next token, failing if there are none remaining.
+    */
+    fn next(&mut self) -> Result {
+        if self.remaining == 0 {
+            return Err(crate::Error::msg(
+                "the stream received more tokens than its limit",
+            ));
+        }
+
+        self.remaining -= 1;
+
+        Ok(())
+    }
+}
+
+impl<'v, S> Stream<'v> for LimitedStream<S>
+where
+    S: Stream<'v>,
+{
+    fn hint_skip(&self) -> bool {
+        self.inner.hint_skip()
+    }
+
+    fn fmt(&mut self, v: Arguments) -> Result {
+        self.next()?;
+        self.inner.fmt(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.next()?;
+        self.inner.fmt_borrowed(v)
+    }
+
+    fn error(&mut self, v: Source) -> Result {
+        self.next()?;
+        self.inner.error(v)
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.next()?;
+        self.inner.error_borrowed(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> Result {
+        self.next()?;
+        self.inner.error_str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        self.next()?;
+        self.inner.i64(v)
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        self.next()?;
+        self.inner.u64(v)
+    }
+
+    fn u8(&mut self, v: u8) -> Result {
+        self.next()?;
+        self.inner.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        self.next()?;
+        self.inner.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        self.next()?;
+        self.inner.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        self.next()?;
+        self.inner.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        self.next()?;
+        self.inner.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        self.next()?;
+        self.inner.i32(v)
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        self.next()?;
+        self.inner.i128(v)
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        self.next()?;
+        self.inner.u128(v)
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        self.next()?;
+        self.inner.f64(v)
+    }
+
+    fn f32(&mut self, v: f32) -> Result {
+        self.next()?;
+        self.inner.f32(v)
+    }
+
+    fn bool(&mut self, v: bool) -> Result {
+        self.next()?;
+        self.inner.bool(v)
+    }
+
+    fn char(&mut self, v: char) -> Result {
+        self.next()?;
+        self.inner.char(v)
+    }
+
+    fn str(&mut self, v: &str) -> Result {
+        self.next()?;
+        self.inner.str(v)
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        self.next()?;
+        self.inner.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        self.next()?;
+        self.inner.typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> Result {
+        self.next()?;
+        self.inner.typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.next()?;
+        self.inner.tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        self.next()?;
+        self.inner.tagged_end(tag)
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.next()?;
+        self.inner.str_borrowed(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.next()?;
+        self.inner.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.next()?;
+        self.inner.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.next()?;
+        self.inner.bytes_borrowed(v)
+    }
+
+    fn none(&mut self) -> Result {
+        self.next()?;
+        self.inner.none()
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> Result {
+        self.next()?;
+        self.inner.map_begin(len)
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        self.next()?;
+        self.inner.map_begin_unknown()
+    }
+
+    fn map_key(&mut self) -> Result {
+        self.next()?;
+        self.inner.map_key()
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.map_key()?;
+        k.stream_owned(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_value(&mut self) -> Result {
+        self.next()?;
+        self.inner.map_value()
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.map_value()?;
+        v.stream_owned(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.next()?;
+        self.inner.map_end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        self.next()?;
+        self.inner.seq_begin(len)
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.next()?;
+        self.inner.seq_begin_unknown()
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        self.next()?;
+        self.inner.seq_elem()
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        self.inner.seq_elem_end()
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.seq_elem()?;
+        v.stream_owned(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.next()?;
+        self.inner.seq_end()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            vec,
+            vec::Vec,
+        },
+        stream::CountingStream,
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn limited_within_limit() {
+        // A sequence begin and end, plus an element and value for each item
+        let v = vec![1, 2, 3];
+
+        let mut limited = LimitedStream::new(Buffer::new(), 8);
+        crate::stream(&mut limited, &v).unwrap();
+
+        assert_eq!(test::tokens(&v), test::tokens(&limited.into_inner()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn limited_exceeds_limit() {
+        let v = vec![1, 2, 3];
+
+        let mut limited = LimitedStream::new(Buffer::new(), 7);
+        assert!(crate::stream(&mut limited, &v).is_err());
+
+        // The end of the sequence was never forwarded
+        let mut buffer = limited.into_inner();
+        buffer.push_token(crate::buffer::Token::SeqEnd);
+
+        assert_eq!(test::tokens(&v), test::tokens(&buffer));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn limited_no_calls_after_limit() {
+        let mut limited = LimitedStream::new(CountingStream::new(), 2);
+
+        limited.seq_begin(None).unwrap();
+        limited.seq_elem().unwrap();
+
+        assert!(limited.i32(1).is_err());
+        assert!(limited.seq_end().is_err());
+
+        let counts = limited.into_inner();
+        assert_eq!(1, counts.seq_count());
+        assert_eq!(0, counts.primitive_count());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn limited_zero() {
+        let mut limited = LimitedStream::new(Buffer::new(), 0);
+
+        assert!(crate::stream(&mut limited, &1).is_err());
+        assert_eq!(Vec::<Token>::new(), test::tokens(&limited.into_inner()));
+    }
+}
@@ -8,16 +8,52 @@ A [`Stream`] is a type that receives and works with abstract data-structures.
 [`Value`]: ../value/trait.Value.html
 */
 
+mod counting;
+mod depth_limited;
 mod error;
+mod filter;
+mod flatten;
 mod fmt;
+mod indenting;
+mod limited;
+mod null;
+mod tee;
+mod validating;
 mod value;
 
+#[cfg(feature = "alloc")]
+mod recording;
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
 pub use self::{
+    counting::CountingStream,
+    depth_limited::DepthLimitedStream,
     error::Source,
+    filter::KeyFilter,
+    flatten::FlattenStream,
     fmt::Arguments,
+    indenting::{
+        IndentingStream,
+        Whitespace,
+    },
+    limited::LimitedStream,
+    null::NullSink,
+    tee::TeeStream,
+    validating::{
+        validate,
+        ValidatingStream,
+    },
     value::Value,
 };
 
+#[cfg(feature = "alloc")]
+pub use self::recording::{
+    RecordedCall,
+    RecordingStream,
+};
+
 /**
 A receiver for the structure of a value.
 
@@ -35,6 +71,20 @@ implementing methods on the trait. Other methods default to returning
 [`Error::unsupported`]: struct.Error.html#method.unsupported
 */
 pub trait Stream<'v> {
+    /**
+    Whether or not this stream will ignore any values given to it.
+
+    Implementations of [`Value`](../value/trait.Value.html) may check this hint before doing any
+    expensive work, and stream nothing at all if it returns `true`.
+    Streams that return `true` must accept values that stream nothing.
+    */
+    #[cfg(not(test))]
+    fn hint_skip(&self) -> bool {
+        false
+    }
+    #[cfg(test)]
+    fn hint_skip(&self) -> bool;
+
     /**
     Stream a formattable type. Implementors should override this method if they
     expect to accept formattable types.
@@ -81,6 +131,84 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn u64(&mut self, v: u64) -> Result;
 
+    /**
+    Stream a 8bit unsigned integer. Implementors should override this method if they
+    expect to accept 8bit unsigned integers.
+
+    By default, the integer is widened and streamed as a `u64`.
+    */
+    #[cfg(not(test))]
+    fn u8(&mut self, v: u8) -> Result {
+        self.u64(u64::from(v))
+    }
+    #[cfg(test)]
+    fn u8(&mut self, v: u8) -> Result;
+
+    /**
+    Stream a 16bit unsigned integer. Implementors should override this method if they
+    expect to accept 16bit unsigned integers.
+
+    By default, the integer is widened and streamed as a `u64`.
+    */
+    #[cfg(not(test))]
+    fn u16(&mut self, v: u16) -> Result {
+        self.u64(u64::from(v))
+    }
+    #[cfg(test)]
+    fn u16(&mut self, v: u16) -> Result;
+
+    /**
+    Stream a 32bit unsigned integer. Implementors should override this method if they
+    expect to accept 32bit unsigned integers.
+
+    By default, the integer is widened and streamed as a `u64`.
+    */
+    #[cfg(not(test))]
+    fn u32(&mut self, v: u32) -> Result {
+        self.u64(u64::from(v))
+    }
+    #[cfg(test)]
+    fn u32(&mut self, v: u32) -> Result;
+
+    /**
+    Stream a 8bit signed integer. Implementors should override this method if they
+    expect to accept 8bit signed integers.
+
+    By default, the integer is widened and streamed as an `i64`.
+    */
+    #[cfg(not(test))]
+    fn i8(&mut self, v: i8) -> Result {
+        self.i64(i64::from(v))
+    }
+    #[cfg(test)]
+    fn i8(&mut self, v: i8) -> Result;
+
+    /**
+    Stream a 16bit signed integer. Implementors should override this method if they
+    expect to accept 16bit signed integers.
+
+    By default, the integer is widened and streamed as an `i64`.
+    */
+    #[cfg(not(test))]
+    fn i16(&mut self, v: i16) -> Result {
+        self.i64(i64::from(v))
+    }
+    #[cfg(test)]
+    fn i16(&mut self, v: i16) -> Result;
+
+    /**
+    Stream a 32bit signed integer. Implementors should override this method if they
+    expect to accept 32bit signed integers.
+
+    By default, the integer is widened and streamed as an `i64`.
+    */
+    #[cfg(not(test))]
+    fn i32(&mut self, v: i32) -> Result {
+        self.i64(i64::from(v))
+    }
+    #[cfg(test)]
+    fn i32(&mut self, v: i32) -> Result;
+
     /**
     Stream a 128bit signed integer. Implementors should override this method if they
     expect to accept 128bit signed integers.
@@ -117,6 +245,19 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn f64(&mut self, v: f64) -> Result;
 
+    /**
+    Stream a 32bit floating point value. Implementors should override this method if they
+    expect to accept 32bit floating point numbers.
+
+    By default, the number is widened and streamed as a `f64`.
+    */
+    #[cfg(not(test))]
+    fn f32(&mut self, v: f32) -> Result {
+        self.f64(f64::from(v))
+    }
+    #[cfg(test)]
+    fn f32(&mut self, v: f32) -> Result;
+
     /**
     Stream a boolean. Implementors should override this method if they
     expect to accept booleans.
@@ -152,6 +293,100 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn str(&mut self, v: &str) -> Result;
 
+    /**
+    Stream a UTF-8 string slice that's tagged with the name of its type,
+    like a date-time.
+
+    By default, the type name is ignored and the string is streamed
+    the same as [`str`](#method.str).
+    */
+    #[cfg(not(test))]
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        let _ = type_name;
+        self.str(v)
+    }
+    #[cfg(test)]
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result;
+
+    /**
+    Begin a value that's tagged with the name of its type.
+
+    The tagged value is streamed after this method is called,
+    and is followed by a call to [`typed_end`](#method.typed_end).
+
+    By default, the tag is ignored and only the value itself is streamed.
+    */
+    #[cfg(not(test))]
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        let _ = tag;
+        Ok(())
+    }
+    #[cfg(test)]
+    fn typed_begin(&mut self, tag: &str) -> Result;
+
+    /**
+    End a value that's tagged with the name of its type.
+    */
+    #[cfg(not(test))]
+    fn typed_end(&mut self) -> Result {
+        Ok(())
+    }
+    #[cfg(test)]
+    fn typed_end(&mut self) -> Result;
+
+    /**
+    Begin a value with a semantic tag, like a date-time.
+
+    Tags describe how a value should be interpreted, and may be
+    mapped onto a format's own tags, like CBOR's semantic tags.
+    The tagged value is streamed after this method is called,
+    and is followed by a call to [`tagged_end`](#method.tagged_end)
+    with the same tag.
+
+    By default, the tag is treated the same as one given to
+    [`typed_begin`](#method.typed_begin).
+    */
+    #[cfg(not(test))]
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.typed_begin(tag)
+    }
+    #[cfg(test)]
+    fn tagged_begin(&mut self, tag: &str) -> Result;
+
+    /**
+    End a value with a semantic tag.
+
+    By default, the tag is treated the same as one given to
+    [`typed_begin`](#method.typed_begin).
+    */
+    #[cfg(not(test))]
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        let _ = tag;
+        self.typed_end()
+    }
+    #[cfg(test)]
+    fn tagged_end(&mut self, tag: &str) -> Result;
+
+    /**
+    Stream a slice of bytes. Implementors should override this method if they
+    expect to accept byte slices.
+
+    By default, the bytes are streamed as a sequence of unsigned integers.
+    */
+    #[cfg(not(test))]
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.seq_begin(Some(v.len()))?;
+
+        for b in v {
+            self.seq_elem()?;
+            self.u64(u64::from(*b))?;
+        }
+
+        self.seq_end()
+    }
+    #[cfg(test)]
+    fn bytes(&mut self, v: &[u8]) -> Result;
+
     /**
     Stream an empty value. Implementors should override this method if they
     expect to accept empty values.
@@ -175,6 +410,20 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn map_begin(&mut self, len: Option<usize>) -> Result;
 
+    /**
+    Begin a map whose length isn't known upfront.
+
+    By default this forwards to `map_begin` with a length of `None`.
+    Implementors should override this method if they need to tell
+    an unknown length apart from other sizing hints.
+    */
+    #[cfg(not(test))]
+    fn map_begin_unknown(&mut self) -> Result {
+        self.map_begin(None)
+    }
+    #[cfg(test)]
+    fn map_begin_unknown(&mut self) -> Result;
+
     /**
     Begin a map key. Implementors should override this method if they
     expect to accept maps.
@@ -224,6 +473,20 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn seq_begin(&mut self, len: Option<usize>) -> Result;
 
+    /**
+    Begin a sequence whose length isn't known upfront.
+
+    By default this forwards to `seq_begin` with a length of `None`.
+    Implementors should override this method if they need to tell
+    an unknown length apart from other sizing hints.
+    */
+    #[cfg(not(test))]
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.seq_begin(None)
+    }
+    #[cfg(test)]
+    fn seq_begin_unknown(&mut self) -> Result;
+
     /**
     Begin a sequence element. Implementors should override this method if they
     expect to accept sequences.
@@ -237,6 +500,20 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn seq_elem(&mut self) -> Result;
 
+    /**
+    End a sequence element.
+
+    Ending an element is optional, because elements are implicitly ended
+    by the stream methods that follow them. Streams that don't need to know
+    when an element ends can ignore this method.
+    */
+    #[cfg(not(test))]
+    fn seq_elem_end(&mut self) -> Result {
+        Ok(())
+    }
+    #[cfg(test)]
+    fn seq_elem_end(&mut self) -> Result;
+
     /**
     End a sequence. Implementors should override this method if they
     expect to accept sequences.
@@ -270,6 +547,21 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn map_value_collect(&mut self, v: Value) -> Result;
 
+    /**
+    Collect a map key and its value together.
+
+    By default, this is the same as calling [`map_key_collect`](#method.map_key_collect)
+    followed by [`map_value_collect`](#method.map_value_collect). Implementors may
+    override this method to work with entries as a whole.
+    */
+    #[cfg(not(test))]
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+    #[cfg(test)]
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result;
+
     /**
     Collect a sequence element.
     */
@@ -295,6 +587,20 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn error_borrowed(&mut self, v: Source<'v>) -> Result;
 
+    /**
+    Stream an error that's described by a message.
+
+    This is useful in no-std environments where the `Error`
+    trait isn't available. By default the message is streamed
+    the same as [`str`](#method.str).
+    */
+    #[cfg(not(test))]
+    fn error_str(&mut self, v: &str) -> Result {
+        self.str(v)
+    }
+    #[cfg(test)]
+    fn error_str(&mut self, v: &str) -> Result;
+
     /**
     Stream a borrowed UTF-8 string slice.
     */
@@ -305,6 +611,30 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn str_borrowed(&mut self, v: &'v str) -> Result;
 
+    /**
+    Stream an owned UTF-8 string.
+
+    Implementors may override this method to take ownership of
+    the string instead of copying it. By default it's streamed
+    the same as [`str`](#method.str).
+    */
+    #[cfg(all(feature = "alloc", not(test)))]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.str(&v)
+    }
+    #[cfg(all(feature = "alloc", test))]
+    fn str_owned(&mut self, v: String) -> Result;
+
+    /**
+    Stream a borrowed slice of bytes.
+    */
+    #[cfg(not(test))]
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.bytes(v)
+    }
+    #[cfg(test)]
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result;
+
     #[cfg(not(test))]
     fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
         self.map_key_collect(k)
@@ -319,6 +649,14 @@ pub trait Stream<'v> {
     #[cfg(test)]
     fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result;
 
+    #[cfg(not(test))]
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+    #[cfg(test)]
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result;
+
     #[cfg(not(test))]
     fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
         self.seq_elem_collect(v)
@@ -331,6 +669,10 @@ impl<'s, 'v, T: ?Sized> Stream<'v> for &'s mut T
 where
     T: Stream<'v>,
 {
+    fn hint_skip(&self) -> bool {
+        (**self).hint_skip()
+    }
+
     fn fmt(&mut self, v: Arguments) -> Result {
         (**self).fmt(v)
     }
@@ -347,6 +689,10 @@ where
         (**self).error_borrowed(v)
     }
 
+    fn error_str(&mut self, v: &str) -> Result {
+        (**self).error_str(v)
+    }
+
     fn i64(&mut self, v: i64) -> Result {
         (**self).i64(v)
     }
@@ -355,6 +701,30 @@ where
         (**self).u64(v)
     }
 
+    fn u8(&mut self, v: u8) -> Result {
+        (**self).u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        (**self).u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        (**self).u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        (**self).i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        (**self).i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        (**self).i32(v)
+    }
+
     fn i128(&mut self, v: i128) -> Result {
         (**self).i128(v)
     }
@@ -367,6 +737,10 @@ where
         (**self).f64(v)
     }
 
+    fn f32(&mut self, v: f32) -> Result {
+        (**self).f32(v)
+    }
+
     fn bool(&mut self, v: bool) -> Result {
         (**self).bool(v)
     }
@@ -379,10 +753,43 @@ where
         (**self).str(v)
     }
 
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        (**self).typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        (**self).typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> Result {
+        (**self).typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        (**self).tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        (**self).tagged_end(tag)
+    }
+
     fn str_borrowed(&mut self, v: &'v str) -> Result {
         (**self).str_borrowed(v)
     }
 
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        (**self).str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        (**self).bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        (**self).bytes_borrowed(v)
+    }
+
     fn none(&mut self) -> Result {
         (**self).none()
     }
@@ -391,6 +798,10 @@ where
         (**self).map_begin(len)
     }
 
+    fn map_begin_unknown(&mut self) -> Result {
+        (**self).map_begin_unknown()
+    }
+
     fn map_key(&mut self) -> Result {
         (**self).map_key()
     }
@@ -415,6 +826,14 @@ where
         (**self).map_value_collect_borrowed(v)
     }
 
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        (**self).map_flat_entry_collect(k, v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        (**self).map_flat_entry_collect_borrowed(k, v)
+    }
+
     fn map_end(&mut self) -> Result {
         (**self).map_end()
     }
@@ -423,10 +842,18 @@ where
         (**self).seq_begin(len)
     }
 
+    fn seq_begin_unknown(&mut self) -> Result {
+        (**self).seq_begin_unknown()
+    }
+
     fn seq_elem(&mut self) -> Result {
         (**self).seq_elem()
     }
 
+    fn seq_elem_end(&mut self) -> Result {
+        (**self).seq_elem_end()
+    }
+
     fn seq_elem_collect(&mut self, v: Value) -> Result {
         (**self).seq_elem_collect(v)
     }
@@ -453,4 +880,18 @@ mod tests {
     fn stream_is_object_safe() {
         fn _safe(_: &mut dyn Stream) {}
     }
+
+    #[test]
+    fn stream_mut_ref_is_stream() {
+        fn _is_stream<'v>(_: impl Stream<'v>) {}
+
+        fn _dyn<'v>(stream: &mut dyn Stream<'v>) {
+            _is_stream(stream)
+        }
+
+        fn _generic<'v>(mut stream: impl Stream<'v>) {
+            _is_stream(&mut stream);
+            _is_stream(&mut &mut stream);
+        }
+    }
 }
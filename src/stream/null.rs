@@ -0,0 +1,252 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that discards everything it receives.
+
+Every method returns `Ok(())` without doing any work. The stream
+also hints that values can be skipped, so values that check
+[`Stream::hint_skip`] may stream nothing at all.
+
+This is useful for measuring the cost of traversing a value
+separately from the cost of serializing it.
+
+[`Stream::hint_skip`]: trait.Stream.html#method.hint_skip
+*/
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NullSink;
+
+impl NullSink {
+    /**
+    Create a new stream that discards everything.
+    */
+    pub fn new() -> Self {
+        NullSink
+    }
+}
+
+impl<'v> Stream<'v> for NullSink {
+    fn hint_skip(&self) -> bool {
+        true
+    }
+
+    fn fmt(&mut self, _: Arguments) -> Result {
+        Ok(())
+    }
+
+    fn fmt_borrowed(&mut self, _: Arguments<'v>) -> Result {
+        Ok(())
+    }
+
+    fn error(&mut self, _: Source) -> Result {
+        Ok(())
+    }
+
+    fn error_borrowed(&mut self, _: Source<'v>) -> Result {
+        Ok(())
+    }
+
+    fn error_str(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn i64(&mut self, _: i64) -> Result {
+        Ok(())
+    }
+
+    fn u64(&mut self, _: u64) -> Result {
+        Ok(())
+    }
+
+    fn u8(&mut self, _: u8) -> Result {
+        Ok(())
+    }
+
+    fn u16(&mut self, _: u16) -> Result {
+        Ok(())
+    }
+
+    fn u32(&mut self, _: u32) -> Result {
+        Ok(())
+    }
+
+    fn i8(&mut self, _: i8) -> Result {
+        Ok(())
+    }
+
+    fn i16(&mut self, _: i16) -> Result {
+        Ok(())
+    }
+
+    fn i32(&mut self, _: i32) -> Result {
+        Ok(())
+    }
+
+    fn i128(&mut self, _: i128) -> Result {
+        Ok(())
+    }
+
+    fn u128(&mut self, _: u128) -> Result {
+        Ok(())
+    }
+
+    fn f64(&mut self, _: f64) -> Result {
+        Ok(())
+    }
+
+    fn f32(&mut self, _: f32) -> Result {
+        Ok(())
+    }
+
+    fn bool(&mut self, _: bool) -> Result {
+        Ok(())
+    }
+
+    fn char(&mut self, _: char) -> Result {
+        Ok(())
+    }
+
+    fn str(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn typed_str(&mut self, _: &str, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn typed_begin(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, _: &str) -> Result {
+        Ok(())
+    }
+
+    fn str_borrowed(&mut self, _: &'v str) -> Result {
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, _: String) -> Result {
+        Ok(())
+    }
+
+    fn bytes(&mut self, _: &[u8]) -> Result {
+        Ok(())
+    }
+
+    fn bytes_borrowed(&mut self, _: &'v [u8]) -> Result {
+        Ok(())
+    }
+
+    fn none(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> Result {
+        Ok(())
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_key(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_key_collect(&mut self, _: Value) -> Result {
+        Ok(())
+    }
+
+    fn map_key_collect_borrowed(&mut self, _: Value<'v>) -> Result {
+        Ok(())
+    }
+
+    fn map_value(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn map_value_collect(&mut self, _: Value) -> Result {
+        Ok(())
+    }
+
+    fn map_value_collect_borrowed(&mut self, _: Value<'v>) -> Result {
+        Ok(())
+    }
+
+    fn map_flat_entry_collect(&mut self, _: Value, _: Value) -> Result {
+        Ok(())
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, _: Value<'v>, _: Value<'v>) -> Result {
+        Ok(())
+    }
+
+    fn map_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> Result {
+        Ok(())
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, _: Value) -> Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, _: Value<'v>) -> Result {
+        Ok(())
+    }
+
+    fn seq_end(&mut self) -> Result {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn null_sink_accepts_anything() {
+        crate::stream(NullSink::new(), &1).unwrap();
+        crate::stream(NullSink::new(), &[Some("a"), None][..]).unwrap();
+
+        let mut sink = NullSink::new();
+        sink.map_begin(None).unwrap();
+        sink.seq_end().unwrap();
+    }
+}
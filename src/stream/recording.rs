@@ -0,0 +1,590 @@
+use crate::{
+    std::{
+        fmt,
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    value::owned::OwnedSource,
+};
+
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+/**
+A call made on a [`RecordingStream`].
+
+All data in a call is owned. Borrowed and owned variants of methods,
+like `str_borrowed` and `str_owned`, are recorded as their plain
+counterpart. Calls that collect a value are followed by the calls
+made while streaming that value. A flat map entry is recorded as a
+collected key followed by a collected value.
+*/
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordedCall {
+    Fmt(String),
+    Error(String),
+    ErrorStr(String),
+    I64(i64),
+    U64(u64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I128(i128),
+    U128(u128),
+    F64(f64),
+    F32(f32),
+    Bool(bool),
+    Char(char),
+    Str(String),
+    TypedStr(String, String),
+    TypedBegin(String),
+    TypedEnd,
+    TaggedBegin(String),
+    TaggedEnd(String),
+    Bytes(Vec<u8>),
+    None,
+    MapBegin(Option<usize>),
+    MapBeginUnknown,
+    MapKey,
+    MapKeyCollect,
+    MapValue,
+    MapValueCollect,
+    MapEnd,
+    SeqBegin(Option<usize>),
+    SeqBeginUnknown,
+    SeqElem,
+    SeqElemCollect,
+    SeqElemEnd,
+    SeqEnd,
+}
+
+impl RecordedCall {
+    fn replay<'v>(&self, stream: &mut impl Stream<'v>) -> Result {
+        match *self {
+            RecordedCall::Fmt(ref v) => stream.fmt(Arguments::display(v)),
+            RecordedCall::Error(ref v) => {
+                let source = OwnedSource::msg(v.clone());
+                stream.error(Source::from(&source))
+            }
+            RecordedCall::ErrorStr(ref v) => stream.error_str(v),
+            RecordedCall::I64(v) => stream.i64(v),
+            RecordedCall::U64(v) => stream.u64(v),
+            RecordedCall::U8(v) => stream.u8(v),
+            RecordedCall::U16(v) => stream.u16(v),
+            RecordedCall::U32(v) => stream.u32(v),
+            RecordedCall::I8(v) => stream.i8(v),
+            RecordedCall::I16(v) => stream.i16(v),
+            RecordedCall::I32(v) => stream.i32(v),
+            RecordedCall::I128(v) => stream.i128(v),
+            RecordedCall::U128(v) => stream.u128(v),
+            RecordedCall::F64(v) => stream.f64(v),
+            RecordedCall::F32(v) => stream.f32(v),
+            RecordedCall::Bool(v) => stream.bool(v),
+            RecordedCall::Char(v) => stream.char(v),
+            RecordedCall::Str(ref v) => stream.str(v),
+            RecordedCall::TypedStr(ref type_name, ref v) => stream.typed_str(type_name, v),
+            RecordedCall::TypedBegin(ref tag) => stream.typed_begin(tag),
+            RecordedCall::TypedEnd => stream.typed_end(),
+            RecordedCall::TaggedBegin(ref tag) => stream.tagged_begin(tag),
+            RecordedCall::TaggedEnd(ref tag) => stream.tagged_end(tag),
+            RecordedCall::Bytes(ref v) => stream.bytes(v),
+            RecordedCall::None => stream.none(),
+            RecordedCall::MapBegin(len) => stream.map_begin(len),
+            RecordedCall::MapBeginUnknown => stream.map_begin_unknown(),
+            // The calls for a collected value follow its marker
+            RecordedCall::MapKey | RecordedCall::MapKeyCollect => stream.map_key(),
+            RecordedCall::MapValue | RecordedCall::MapValueCollect => stream.map_value(),
+            RecordedCall::MapEnd => stream.map_end(),
+            RecordedCall::SeqBegin(len) => stream.seq_begin(len),
+            RecordedCall::SeqBeginUnknown => stream.seq_begin_unknown(),
+            RecordedCall::SeqElem | RecordedCall::SeqElemCollect => stream.seq_elem(),
+            RecordedCall::SeqElemEnd => stream.seq_elem_end(),
+            RecordedCall::SeqEnd => stream.seq_end(),
+        }
+    }
+}
+
+impl fmt::Display for RecordedCall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RecordedCall::Fmt(ref v) => write!(f, "fmt({:?})", v),
+            RecordedCall::Error(ref v) => write!(f, "error({:?})", v),
+            RecordedCall::ErrorStr(ref v) => write!(f, "error_str({:?})", v),
+            RecordedCall::I64(v) => write!(f, "i64({})", v),
+            RecordedCall::U64(v) => write!(f, "u64({})", v),
+            RecordedCall::U8(v) => write!(f, "u8({})", v),
+            RecordedCall::U16(v) => write!(f, "u16({})", v),
+            RecordedCall::U32(v) => write!(f, "u32({})", v),
+            RecordedCall::I8(v) => write!(f, "i8({})", v),
+            RecordedCall::I16(v) => write!(f, "i16({})", v),
+            RecordedCall::I32(v) => write!(f, "i32({})", v),
+            RecordedCall::I128(v) => write!(f, "i128({})", v),
+            RecordedCall::U128(v) => write!(f, "u128({})", v),
+            RecordedCall::F64(v) => write!(f, "f64({:?})", v),
+            RecordedCall::F32(v) => write!(f, "f32({:?})", v),
+            RecordedCall::Bool(v) => write!(f, "bool({})", v),
+            RecordedCall::Char(v) => write!(f, "char({:?})", v),
+            RecordedCall::Str(ref v) => write!(f, "str({:?})", v),
+            RecordedCall::TypedStr(ref type_name, ref v) => {
+                write!(f, "typed_str({:?}, {:?})", type_name, v)
+            }
+            RecordedCall::TypedBegin(ref tag) => write!(f, "typed_begin({:?})", tag),
+            RecordedCall::TypedEnd => f.write_str("typed_end"),
+            RecordedCall::TaggedBegin(ref tag) => write!(f, "tagged_begin({:?})", tag),
+            RecordedCall::TaggedEnd(ref tag) => write!(f, "tagged_end({:?})", tag),
+            RecordedCall::Bytes(ref v) => write!(f, "bytes({:?})", v),
+            RecordedCall::None => f.write_str("none"),
+            RecordedCall::MapBegin(len) => write!(f, "map_begin({:?})", len),
+            RecordedCall::MapBeginUnknown => f.write_str("map_begin_unknown"),
+            RecordedCall::MapKey => f.write_str("map_key"),
+            RecordedCall::MapKeyCollect => f.write_str("map_key_collect"),
+            RecordedCall::MapValue => f.write_str("map_value"),
+            RecordedCall::MapValueCollect => f.write_str("map_value_collect"),
+            RecordedCall::MapEnd => f.write_str("map_end"),
+            RecordedCall::SeqBegin(len) => write!(f, "seq_begin({:?})", len),
+            RecordedCall::SeqBeginUnknown => f.write_str("seq_begin_unknown"),
+            RecordedCall::SeqElem => f.write_str("seq_elem"),
+            RecordedCall::SeqElemCollect => f.write_str("seq_elem_collect"),
+            RecordedCall::SeqElemEnd => f.write_str("seq_elem_end"),
+            RecordedCall::SeqEnd => f.write_str("seq_end"),
+        }
+    }
+}
+
+/**
+A stream that records every call made on it.
+
+The recording can be printed as a log, or replayed to another stream.
+It's useful for debugging why a value produces unexpected output.
+Unlike a [`Buffer`], a recording isn't itself a value and
+keeps more detail about the calls that were made.
+
+```
+# fn main() -> Result<(), sval::Error> {
+use sval::stream::{RecordedCall, RecordingStream};
+
+let mut recording = RecordingStream::new();
+sval::stream(&mut recording, &[1, 2][..])?;
+
+assert_eq!(RecordedCall::SeqBegin(Some(2)), recording.calls()[0]);
+
+// Replay the recording to another stream
+let mut replayed = RecordingStream::new();
+recording.replay(&mut replayed)?;
+# Ok(())
+# }
+```
+
+[`Buffer`]: ../buffer/struct.Buffer.html
+*/
+#[derive(Debug, Default, Clone)]
+pub struct RecordingStream {
+    calls: Vec<RecordedCall>,
+}
+
+impl RecordingStream {
+    /**
+    Create a new, empty recording.
+    */
+    pub fn new() -> Self {
+        RecordingStream::default()
+    }
+
+    /**
+    The calls that have been recorded.
+    */
+    pub fn calls(&self) -> &[RecordedCall] {
+        &self.calls
+    }
+
+    /**
+    Clear the recording so that it can be re-used.
+    */
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    /**
+    Make the recorded calls on another stream.
+
+    Calls that collected a value are replayed as their plain
+    counterpart, followed by the calls for that value.
+    */
+    pub fn replay<'v>(&self, stream: &mut impl Stream<'v>) -> Result {
+        for call in &self.calls {
+            call.replay(stream)?;
+        }
+
+        Ok(())
+    }
+
+    /**
+    Print the recorded calls to standard output, one per line.
+
+    The recording's `Display` implementation can be used
+    to write the same log somewhere else.
+    */
+    #[cfg(feature = "std")]
+    pub fn print_log(&self) {
+        println!("{}", self);
+    }
+
+    fn push(&mut self, call: RecordedCall) -> Result {
+        self.calls.push(call);
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for RecordingStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut depth = 0usize;
+
+        for call in &self.calls {
+            if let RecordedCall::MapEnd | RecordedCall::SeqEnd = call {
+                depth = depth.saturating_sub(1);
+            }
+
+            writeln!(f, "{:width$}{}", "", call, width = depth * 2)?;
+
+            if let RecordedCall::MapBegin(_)
+            | RecordedCall::MapBeginUnknown
+            | RecordedCall::SeqBegin(_)
+            | RecordedCall::SeqBeginUnknown = call
+            {
+                depth += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'v> Stream<'v> for RecordingStream {
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, v: Arguments) -> Result {
+        self.push(RecordedCall::Fmt(v.to_string()))
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, v: Source) -> Result {
+        self.push(RecordedCall::Error(v.to_string()))
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> Result {
+        self.push(RecordedCall::ErrorStr(v.into()))
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        self.push(RecordedCall::I64(v))
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        self.push(RecordedCall::U64(v))
+    }
+
+    fn u8(&mut self, v: u8) -> Result {
+        self.push(RecordedCall::U8(v))
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        self.push(RecordedCall::U16(v))
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        self.push(RecordedCall::U32(v))
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        self.push(RecordedCall::I8(v))
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        self.push(RecordedCall::I16(v))
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        self.push(RecordedCall::I32(v))
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        self.push(RecordedCall::I128(v))
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        self.push(RecordedCall::U128(v))
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        self.push(RecordedCall::F64(v))
+    }
+
+    fn f32(&mut self, v: f32) -> Result {
+        self.push(RecordedCall::F32(v))
+    }
+
+    fn bool(&mut self, v: bool) -> Result {
+        self.push(RecordedCall::Bool(v))
+    }
+
+    fn char(&mut self, v: char) -> Result {
+        self.push(RecordedCall::Char(v))
+    }
+
+    fn str(&mut self, v: &str) -> Result {
+        self.push(RecordedCall::Str(v.into()))
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        self.push(RecordedCall::TypedStr(type_name.into(), v.into()))
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        self.push(RecordedCall::TypedBegin(tag.into()))
+    }
+
+    fn typed_end(&mut self) -> Result {
+        self.push(RecordedCall::TypedEnd)
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.push(RecordedCall::TaggedBegin(tag.into()))
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        self.push(RecordedCall::TaggedEnd(tag.into()))
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.str(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> Result {
+        self.push(RecordedCall::Str(v))
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.push(RecordedCall::Bytes(v.into()))
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> Result {
+        self.push(RecordedCall::None)
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> Result {
+        self.push(RecordedCall::MapBegin(len))
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        self.push(RecordedCall::MapBeginUnknown)
+    }
+
+    fn map_key(&mut self) -> Result {
+        self.push(RecordedCall::MapKey)
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.push(RecordedCall::MapKeyCollect)?;
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> Result {
+        self.push(RecordedCall::MapValue)
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.push(RecordedCall::MapValueCollect)?;
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.push(RecordedCall::MapEnd)
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        self.push(RecordedCall::SeqBegin(len))
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.push(RecordedCall::SeqBeginUnknown)
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        self.push(RecordedCall::SeqElem)
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        self.push(RecordedCall::SeqElemEnd)
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.push(RecordedCall::SeqElemCollect)?;
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.push(RecordedCall::SeqEnd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn record(v: impl crate::value::Value) -> RecordingStream {
+        let mut recording = RecordingStream::new();
+        crate::stream(&mut recording, &v).unwrap();
+
+        recording
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn record_calls() {
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![1]);
+
+        let recording = record(&map);
+
+        assert_eq!(
+            &[
+                RecordedCall::MapBegin(Some(1)),
+                RecordedCall::MapKeyCollect,
+                RecordedCall::Str("a".into()),
+                RecordedCall::MapValueCollect,
+                RecordedCall::SeqBegin(Some(1)),
+                RecordedCall::SeqElemCollect,
+                RecordedCall::I32(1),
+                RecordedCall::SeqEnd,
+                RecordedCall::MapEnd,
+            ][..],
+            recording.calls()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn record_log() {
+        let recording = record(&vec![vec![true], vec![]]);
+
+        assert_eq!(
+            "seq_begin(Some(2))\n  seq_elem_collect\n  seq_begin(Some(1))\n    seq_elem_collect\n    bool(true)\n  seq_end\n  seq_elem_collect\n  seq_begin(Some(0))\n  seq_end\nseq_end\n",
+            recording.to_string()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn record_replay() {
+        let mut map = BTreeMap::new();
+        map.insert("a", Some(1.5));
+        map.insert("b", None);
+
+        let recording = record(&map);
+
+        // Collected values are replayed as their plain calls
+        let mut replayed = RecordingStream::new();
+        recording.replay(&mut replayed).unwrap();
+        assert_eq!(
+            &[
+                RecordedCall::MapBegin(Some(2)),
+                RecordedCall::MapKey,
+                RecordedCall::Str("a".into()),
+                RecordedCall::MapValue,
+                RecordedCall::F64(1.5),
+                RecordedCall::MapKey,
+                RecordedCall::Str("b".into()),
+                RecordedCall::MapValue,
+                RecordedCall::None,
+                RecordedCall::MapEnd,
+            ][..],
+            replayed.calls()
+        );
+
+        let mut buffer = crate::buffer::Buffer::new();
+        recording.replay(&mut buffer).unwrap();
+        assert_eq!(test::tokens(&map), test::tokens(&buffer));
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(2)),
+                Token::Str("a".into()),
+                Token::Float(1.5),
+                Token::Str("b".into()),
+                Token::None,
+                Token::MapEnd,
+            ],
+            test::tokens(&buffer)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn record_clear() {
+        let mut recording = record(&1);
+        assert_eq!(1, recording.calls().len());
+
+        recording.clear();
+        assert!(recording.calls().is_empty());
+    }
+}
@@ -0,0 +1,412 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A stream that forwards every call to two other streams.
+
+Calls are made on the first stream, then the second.
+If either stream returns an error then that error is returned,
+and the other stream may have only seen part of the value.
+This is useful for writing a value to some format and logging
+it at the same time.
+*/
+pub struct TeeStream<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> TeeStream<A, B> {
+    /**
+    Forward calls to both `a` and `b`.
+    */
+    pub fn new(a: A, b: B) -> Self {
+        TeeStream { a, b }
+    }
+
+    /**
+    Get the inner streams.
+    */
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<'v, A, B> Stream<'v> for TeeStream<A, B>
+where
+    A: Stream<'v>,
+    B: Stream<'v>,
+{
+    #[inline]
+    fn hint_skip(&self) -> bool {
+        self.a.hint_skip() && self.b.hint_skip()
+    }
+
+    #[inline]
+    fn fmt(&mut self, v: Arguments) -> Result {
+        self.a.fmt(v)?;
+        self.b.fmt(v)
+    }
+
+    #[inline]
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.a.fmt_borrowed(v)?;
+        self.b.fmt_borrowed(v)
+    }
+
+    #[inline]
+    fn error(&mut self, v: Source) -> Result {
+        self.a.error(v)?;
+        self.b.error(v)
+    }
+
+    #[inline]
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.a.error_borrowed(v)?;
+        self.b.error_borrowed(v)
+    }
+
+    #[inline]
+    fn error_str(&mut self, v: &str) -> Result {
+        self.a.error_str(v)?;
+        self.b.error_str(v)
+    }
+
+    #[inline]
+    fn i64(&mut self, v: i64) -> Result {
+        self.a.i64(v)?;
+        self.b.i64(v)
+    }
+
+    #[inline]
+    fn u64(&mut self, v: u64) -> Result {
+        self.a.u64(v)?;
+        self.b.u64(v)
+    }
+
+    #[inline]
+    fn u8(&mut self, v: u8) -> Result {
+        self.a.u8(v)?;
+        self.b.u8(v)
+    }
+
+    #[inline]
+    fn u16(&mut self, v: u16) -> Result {
+        self.a.u16(v)?;
+        self.b.u16(v)
+    }
+
+    #[inline]
+    fn u32(&mut self, v: u32) -> Result {
+        self.a.u32(v)?;
+        self.b.u32(v)
+    }
+
+    #[inline]
+    fn i8(&mut self, v: i8) -> Result {
+        self.a.i8(v)?;
+        self.b.i8(v)
+    }
+
+    #[inline]
+    fn i16(&mut self, v: i16) -> Result {
+        self.a.i16(v)?;
+        self.b.i16(v)
+    }
+
+    #[inline]
+    fn i32(&mut self, v: i32) -> Result {
+        self.a.i32(v)?;
+        self.b.i32(v)
+    }
+
+    #[inline]
+    fn i128(&mut self, v: i128) -> Result {
+        self.a.i128(v)?;
+        self.b.i128(v)
+    }
+
+    #[inline]
+    fn u128(&mut self, v: u128) -> Result {
+        self.a.u128(v)?;
+        self.b.u128(v)
+    }
+
+    #[inline]
+    fn f64(&mut self, v: f64) -> Result {
+        self.a.f64(v)?;
+        self.b.f64(v)
+    }
+
+    #[inline]
+    fn f32(&mut self, v: f32) -> Result {
+        self.a.f32(v)?;
+        self.b.f32(v)
+    }
+
+    #[inline]
+    fn bool(&mut self, v: bool) -> Result {
+        self.a.bool(v)?;
+        self.b.bool(v)
+    }
+
+    #[inline]
+    fn char(&mut self, v: char) -> Result {
+        self.a.char(v)?;
+        self.b.char(v)
+    }
+
+    #[inline]
+    fn str(&mut self, v: &str) -> Result {
+        self.a.str(v)?;
+        self.b.str(v)
+    }
+
+    #[inline]
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        self.a.typed_str(type_name, v)?;
+        self.b.typed_str(type_name, v)
+    }
+
+    #[inline]
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        self.a.typed_begin(tag)?;
+        self.b.typed_begin(tag)
+    }
+
+    #[inline]
+    fn typed_end(&mut self) -> Result {
+        self.a.typed_end()?;
+        self.b.typed_end()
+    }
+
+    #[inline]
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.a.tagged_begin(tag)?;
+        self.b.tagged_begin(tag)
+    }
+
+    #[inline]
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        self.a.tagged_end(tag)?;
+        self.b.tagged_end(tag)
+    }
+
+    #[inline]
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.a.str_borrowed(v)?;
+        self.b.str_borrowed(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.a.str(&v)?;
+        self.b.str_owned(v)
+    }
+
+    #[inline]
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.a.bytes(v)?;
+        self.b.bytes(v)
+    }
+
+    #[inline]
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.a.bytes_borrowed(v)?;
+        self.b.bytes_borrowed(v)
+    }
+
+    #[inline]
+    fn none(&mut self) -> Result {
+        self.a.none()?;
+        self.b.none()
+    }
+
+    #[inline]
+    fn map_begin(&mut self, len: Option<usize>) -> Result {
+        self.a.map_begin(len)?;
+        self.b.map_begin(len)
+    }
+
+    #[inline]
+    fn map_begin_unknown(&mut self) -> Result {
+        self.a.map_begin_unknown()?;
+        self.b.map_begin_unknown()
+    }
+
+    #[inline]
+    fn map_key(&mut self) -> Result {
+        self.a.map_key()?;
+        self.b.map_key()
+    }
+
+    #[inline]
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.a.map_key_collect(k)?;
+        self.b.map_key_collect(k)
+    }
+
+    #[inline]
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.a.map_key_collect_borrowed(k)?;
+        self.b.map_key_collect_borrowed(k)
+    }
+
+    #[inline]
+    fn map_value(&mut self) -> Result {
+        self.a.map_value()?;
+        self.b.map_value()
+    }
+
+    #[inline]
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.a.map_value_collect(v)?;
+        self.b.map_value_collect(v)
+    }
+
+    #[inline]
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.a.map_value_collect_borrowed(v)?;
+        self.b.map_value_collect_borrowed(v)
+    }
+
+    #[inline]
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.a.map_flat_entry_collect(k, v)?;
+        self.b.map_flat_entry_collect(k, v)
+    }
+
+    #[inline]
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.a.map_flat_entry_collect_borrowed(k, v)?;
+        self.b.map_flat_entry_collect_borrowed(k, v)
+    }
+
+    #[inline]
+    fn map_end(&mut self) -> Result {
+        self.a.map_end()?;
+        self.b.map_end()
+    }
+
+    #[inline]
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        self.a.seq_begin(len)?;
+        self.b.seq_begin(len)
+    }
+
+    #[inline]
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.a.seq_begin_unknown()?;
+        self.b.seq_begin_unknown()
+    }
+
+    #[inline]
+    fn seq_elem(&mut self) -> Result {
+        self.a.seq_elem()?;
+        self.b.seq_elem()
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        self.a.seq_elem_end()?;
+        self.b.seq_elem_end()
+    }
+
+    #[inline]
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.a.seq_elem_collect(v)?;
+        self.b.seq_elem_collect(v)
+    }
+
+    #[inline]
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.a.seq_elem_collect_borrowed(v)?;
+        self.b.seq_elem_collect_borrowed(v)
+    }
+
+    #[inline]
+    fn seq_end(&mut self) -> Result {
+        self.a.seq_end()?;
+        self.b.seq_end()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+        stream::{
+            LimitedStream,
+            RecordingStream,
+        },
+        test,
+        value::{
+            Bytes,
+            Transparent,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn tee_identical_tokens() {
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![Some(1.5), None]);
+        map.insert("b", vec![]);
+
+        let mut tee = TeeStream::new(Buffer::new(), Buffer::new());
+        crate::stream(&mut tee, &map).unwrap();
+
+        let (a, b) = tee.into_inner();
+        assert_eq!(test::tokens(&map), test::tokens(&a));
+        assert_eq!(test::tokens(&a), test::tokens(&b));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn tee_identical_calls() {
+        let mut map = BTreeMap::new();
+        map.insert('a', 1u8);
+
+        let v = (
+            Transparent::new("tag", "b"),
+            Bytes::new(b"c"),
+            map,
+            None::<i32>,
+        );
+
+        let mut tee = TeeStream::new(RecordingStream::new(), RecordingStream::new());
+        crate::stream(&mut tee, &v).unwrap();
+
+        let (a, b) = tee.into_inner();
+        assert!(!a.calls().is_empty());
+        assert_eq!(a.calls(), b.calls());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn tee_error() {
+        let v = vec![1, 2, 3];
+
+        let mut tee = TeeStream::new(Buffer::new(), LimitedStream::new(Buffer::new(), 2));
+        assert!(crate::stream(&mut tee, &v).is_err());
+
+        let mut tee = TeeStream::new(LimitedStream::new(Buffer::new(), 2), Buffer::new());
+        assert!(crate::stream(&mut tee, &v).is_err());
+    }
+}
@@ -0,0 +1,524 @@
+use super::{
+    Arguments,
+    Result,
+    Source,
+    Stream,
+    Value,
+};
+
+use sval_stack::stack2::{
+    Pos,
+    Stack,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+Wrap a stream so that it only receives well-formed values.
+
+See [`ValidatingStream`] for more details.
+*/
+pub fn validate<'v, S>(stream: S) -> ValidatingStream<S>
+where
+    S: Stream<'v>,
+{
+    ValidatingStream::new(stream)
+}
+
+/**
+A stream that checks the calls made on it are well-formed.
+
+Each call is checked against a stack before it's forwarded to the inner
+stream. Calls that aren't valid, like ending a sequence that was never
+started or streaming a map value without a key, return an error instead
+of being forwarded. This makes it safe to pass values that can't be
+trusted to streams that assume their input is well-formed.
+
+Only maps, sequences, and primitives are checked. Calls to
+[`Stream::typed_begin`], [`Stream::typed_end`], [`Stream::tagged_begin`],
+and [`Stream::tagged_end`] are forwarded without any checks, so streams
+that rely on these being balanced need to check them themselves.
+
+Values can be nested up to 12 levels deep.
+
+[`Stream::typed_begin`]: trait.Stream.html#method.typed_begin
+[`Stream::typed_end`]: trait.Stream.html#method.typed_end
+[`Stream::tagged_begin`]: trait.Stream.html#method.tagged_begin
+[`Stream::tagged_end`]: trait.Stream.html#method.tagged_end
+*/
+pub struct ValidatingStream<S> {
+    inner: S,
+    stack: Stack,
+}
+
+impl<S> ValidatingStream<S> {
+    /**
+    Wrap a stream so that it only receives well-formed values.
+    */
+    pub fn new(inner: S) -> Self {
+        ValidatingStream {
+            inner,
+            stack: Stack::new(),
+        }
+    }
+
+    /**
+    Check that a complete value has been streamed.
+    */
+    pub fn end(&mut self) -> Result {
+        self.stack
+            .end()
+            .map_err(|err| stack_error(err, "the value is incomplete"))
+    }
+
+    /**
+    Get the inner stream.
+    */
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn check(
+        &mut self,
+        op: fn(&mut Stack) -> crate::std::result::Result<Pos, sval_stack::Error>,
+        msg: &'static str,
+    ) -> Result {
+        op(&mut self.stack)
+            .map(|_| ())
+            .map_err(|err| stack_error(err, msg))
+    }
+
+    fn primitive(&mut self) -> Result {
+        self.check(Stack::primitive, "unexpected primitive")
+    }
+}
+
+// With an allocator, errors include the position in the stack they happened at
+#[cfg(feature = "alloc")]
+fn stack_error(err: sval_stack::Error, _: &'static str) -> crate::Error {
+    crate::Error::custom(err)
+}
+
+#[cfg(not(feature = "alloc"))]
+fn stack_error(_: sval_stack::Error, msg: &'static str) -> crate::Error {
+    crate::Error::msg(msg)
+}
+
+impl<'v, S> Stream<'v> for ValidatingStream<S>
+where
+    S: Stream<'v>,
+{
+    fn hint_skip(&self) -> bool {
+        // Values are always needed to validate them
+        false
+    }
+
+    fn fmt(&mut self, v: Arguments) -> Result {
+        self.primitive()?;
+        self.inner.fmt(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: Arguments<'v>) -> Result {
+        self.primitive()?;
+        self.inner.fmt_borrowed(v)
+    }
+
+    fn error(&mut self, v: Source) -> Result {
+        self.primitive()?;
+        self.inner.error(v)
+    }
+
+    fn error_borrowed(&mut self, v: Source<'v>) -> Result {
+        self.primitive()?;
+        self.inner.error_borrowed(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> Result {
+        self.primitive()?;
+        self.inner.error_str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> Result {
+        self.primitive()?;
+        self.inner.i64(v)
+    }
+
+    fn u64(&mut self, v: u64) -> Result {
+        self.primitive()?;
+        self.inner.u64(v)
+    }
+
+    fn u8(&mut self, v: u8) -> Result {
+        self.primitive()?;
+        self.inner.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> Result {
+        self.primitive()?;
+        self.inner.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> Result {
+        self.primitive()?;
+        self.inner.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> Result {
+        self.primitive()?;
+        self.inner.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> Result {
+        self.primitive()?;
+        self.inner.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> Result {
+        self.primitive()?;
+        self.inner.i32(v)
+    }
+
+    fn i128(&mut self, v: i128) -> Result {
+        self.primitive()?;
+        self.inner.i128(v)
+    }
+
+    fn u128(&mut self, v: u128) -> Result {
+        self.primitive()?;
+        self.inner.u128(v)
+    }
+
+    fn f64(&mut self, v: f64) -> Result {
+        self.primitive()?;
+        self.inner.f64(v)
+    }
+
+    fn f32(&mut self, v: f32) -> Result {
+        self.primitive()?;
+        self.inner.f32(v)
+    }
+
+    fn bool(&mut self, v: bool) -> Result {
+        self.primitive()?;
+        self.inner.bool(v)
+    }
+
+    fn char(&mut self, v: char) -> Result {
+        self.primitive()?;
+        self.inner.char(v)
+    }
+
+    fn str(&mut self, v: &str) -> Result {
+        self.primitive()?;
+        self.inner.str(v)
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> Result {
+        self.primitive()?;
+        self.inner.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> Result {
+        self.inner.typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> Result {
+        self.inner.typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> Result {
+        self.inner.tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> Result {
+        self.inner.tagged_end(tag)
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> Result {
+        self.primitive()?;
+        self.inner.str_borrowed(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> Result {
+        self.primitive()?;
+        self.inner.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> Result {
+        self.primitive()?;
+        self.inner.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> Result {
+        self.primitive()?;
+        self.inner.bytes_borrowed(v)
+    }
+
+    fn none(&mut self) -> Result {
+        self.primitive()?;
+        self.inner.none()
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> Result {
+        self.check(
+            Stack::map_begin,
+            "unexpected or too deeply nested start of a map",
+        )?;
+        self.inner.map_begin(len)
+    }
+
+    fn map_begin_unknown(&mut self) -> Result {
+        self.check(
+            Stack::map_begin,
+            "unexpected or too deeply nested start of a map",
+        )?;
+        self.inner.map_begin_unknown()
+    }
+
+    fn map_key(&mut self) -> Result {
+        self.check(Stack::map_key, "unexpected map key")?;
+        self.inner.map_key()
+    }
+
+    fn map_key_collect(&mut self, k: Value) -> Result {
+        self.map_key()?;
+        k.stream_owned(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: Value<'v>) -> Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_value(&mut self) -> Result {
+        self.check(Stack::map_value, "unexpected map value")?;
+        self.inner.map_value()
+    }
+
+    fn map_value_collect(&mut self, v: Value) -> Result {
+        self.map_value()?;
+        v.stream_owned(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: Value, v: Value) -> Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(&mut self, k: Value<'v>, v: Value<'v>) -> Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+
+    fn map_end(&mut self) -> Result {
+        self.check(Stack::map_end, "unexpected end of a map")?;
+        self.inner.map_end()
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> Result {
+        self.check(
+            Stack::seq_begin,
+            "unexpected or too deeply nested start of a sequence",
+        )?;
+        self.inner.seq_begin(len)
+    }
+
+    fn seq_begin_unknown(&mut self) -> Result {
+        self.check(
+            Stack::seq_begin,
+            "unexpected or too deeply nested start of a sequence",
+        )?;
+        self.inner.seq_begin_unknown()
+    }
+
+    fn seq_elem(&mut self) -> Result {
+        self.check(Stack::seq_elem, "unexpected sequence element")?;
+        self.inner.seq_elem()
+    }
+
+    fn seq_elem_end(&mut self) -> Result {
+        self.check(Stack::seq_elem_end, "unexpected end of a sequence element")?;
+        self.inner.seq_elem_end()
+    }
+
+    fn seq_elem_collect(&mut self, v: Value) -> Result {
+        self.seq_elem()?;
+        v.stream_owned(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: Value<'v>) -> Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_end(&mut self) -> Result {
+        self.check(Stack::seq_end, "unexpected end of a sequence")?;
+        self.inner.seq_end()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            collections::BTreeMap,
+            string::ToString,
+            vec,
+        },
+        stream::{
+            NullSink,
+            RecordedCall,
+            RecordingStream,
+        },
+        test,
+        value,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_valid() {
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![Some(1), None]);
+        map.insert("b", vec![]);
+
+        let mut stream = validate(Buffer::new());
+        crate::stream(&mut stream, &map).unwrap();
+        stream.end().unwrap();
+
+        assert_eq!(test::tokens(&map), test::tokens(&stream.into_inner()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_lazy_with_skipping_stream() {
+        let lazy = value::Lazy::new(|| 1);
+
+        let mut map = BTreeMap::new();
+        map.insert("a", lazy);
+        map.insert("b", lazy);
+
+        // The inner stream would skip lazy values, which would leave map values empty
+        let mut stream = validate(NullSink::new());
+        crate::stream(&mut stream, &map).unwrap();
+        stream.end().unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_unmatched_seq_end() {
+        struct Invalid;
+
+        impl value::Value for Invalid {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(None)?;
+                stream.seq_end()?;
+                stream.seq_end()
+            }
+        }
+
+        let mut stream = validate(RecordingStream::new());
+        assert!(crate::stream(&mut stream, &Invalid).is_err());
+
+        assert_eq!(
+            &[RecordedCall::SeqBegin(None), RecordedCall::SeqEnd][..],
+            stream.into_inner().calls()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_seq_elem_end() {
+        struct Bracketed;
+
+        impl value::Value for Bracketed {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(Some(1))?;
+                stream.seq_elem_begin()?.i32(1)?;
+                stream.seq_elem_end()?;
+                stream.seq_end()
+            }
+        }
+
+        let mut stream = validate(RecordingStream::new());
+        crate::stream(&mut stream, &Bracketed).unwrap();
+        stream.end().unwrap();
+
+        assert_eq!(
+            &[
+                RecordedCall::SeqBegin(Some(1)),
+                RecordedCall::SeqElem,
+                RecordedCall::I32(1),
+                RecordedCall::SeqElemEnd,
+                RecordedCall::SeqEnd,
+            ][..],
+            stream.into_inner().calls()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_incomplete_seq_elem_end() {
+        struct Incomplete;
+
+        impl value::Value for Incomplete {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(None)?;
+                stream.seq_elem_begin()?.seq_begin(None)?;
+
+                // The nested sequence hasn't ended yet
+                stream.seq_elem_end()
+            }
+        }
+
+        let mut stream = validate(RecordingStream::new());
+        assert!(crate::stream(&mut stream, &Incomplete).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_map_value_without_key() {
+        let mut stream = validate(RecordingStream::new());
+
+        stream.map_begin(None).unwrap();
+        assert_eq!(
+            "unexpected map value at depth 1",
+            stream.map_value().unwrap_err().to_string()
+        );
+
+        assert_eq!(
+            &[RecordedCall::MapBegin(None)][..],
+            stream.into_inner().calls()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_incomplete() {
+        let mut stream = validate(RecordingStream::new());
+
+        stream.seq_begin(None).unwrap();
+        stream.seq_elem().unwrap();
+        stream.i32(1).unwrap();
+
+        assert!(stream.i32(2).is_err());
+        assert!(stream.end().is_err());
+
+        stream.seq_end().unwrap();
+        stream.end().unwrap();
+
+        assert!(stream.i32(3).is_err());
+    }
+}
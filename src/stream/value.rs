@@ -6,6 +6,7 @@ use crate::{
 /**
 A value that can emit its structure to a stream.
 */
+#[derive(Clone, Copy)]
 pub struct Value<'v>(&'v dyn value::Value);
 
 impl<'v> From<&'v dyn value::Value> for Value<'v> {
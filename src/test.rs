@@ -26,6 +26,7 @@ mod alloc_support {
             Stream,
         },
         value::{
+            self,
             owned::{
                 OwnedSource,
                 TokenKind,
@@ -47,12 +48,20 @@ mod alloc_support {
         SeqEnd,
         Signed(i64),
         Unsigned(u64),
+        U8(u8),
+        U16(u16),
+        U32(u32),
+        I8(i8),
+        I16(i16),
+        I32(i32),
         Float(f64),
+        Float32(f32),
         BigSigned(i128),
         BigUnsigned(u128),
         Bool(bool),
         Str(String),
         Char(char),
+        Bytes(Vec<u8>),
         Error(Source),
         None,
     }
@@ -77,8 +86,24 @@ mod alloc_support {
 
     /**
     Collect a value into a sequence of tokens.
+
+    This is useful for checking the structure a [`Value`] produces
+    when testing implementations:
+
+    ```
+    # fn main() {
+    # #[cfg(feature = "test")]
+    # {
+    use sval::test::{self, Token};
+
+    assert_eq!(vec![Token::I32(42)], test::tokens(&42));
+    # }
+    # }
+    ```
+
+    [`Value`]: ../value/trait.Value.html
     */
-    pub fn tokens(v: impl Value) -> Vec<Token> {
+    pub fn tokens(v: &(impl Value + ?Sized)) -> Vec<Token> {
         OwnedValue::collect(v)
             .tokens()
             .unwrap()
@@ -90,12 +115,20 @@ mod alloc_support {
                 TokenKind::SeqEnd => Some(Token::SeqEnd),
                 TokenKind::Signed(v) => Some(Token::Signed(v)),
                 TokenKind::Unsigned(v) => Some(Token::Unsigned(v)),
+                TokenKind::U8(v) => Some(Token::U8(v)),
+                TokenKind::U16(v) => Some(Token::U16(v)),
+                TokenKind::U32(v) => Some(Token::U32(v)),
+                TokenKind::I8(v) => Some(Token::I8(v)),
+                TokenKind::I16(v) => Some(Token::I16(v)),
+                TokenKind::I32(v) => Some(Token::I32(v)),
                 TokenKind::BigSigned(v) => Some(Token::BigSigned(v)),
                 TokenKind::BigUnsigned(v) => Some(Token::BigUnsigned(v)),
                 TokenKind::Float(v) => Some(Token::Float(v)),
+                TokenKind::Float32(v) => Some(Token::Float32(v)),
                 TokenKind::Bool(v) => Some(Token::Bool(v)),
                 TokenKind::Char(v) => Some(Token::Char(v)),
                 TokenKind::Str(ref v) => Some(Token::Str((**v).into())),
+                TokenKind::Bytes(ref v) => Some(Token::Bytes((**v).into())),
                 TokenKind::None => Some(Token::None),
                 TokenKind::Error(ref err) => Some(Token::Error(Source((**err).clone()))),
                 _ => None,
@@ -103,6 +136,57 @@ mod alloc_support {
             .collect()
     }
 
+    /**
+    Format a side-by-side comparison of actual and expected tokens.
+
+    Returns `None` if the tokens are equal. This is used by the [`assert_tokens`] macro.
+
+    [`assert_tokens`]: macro.assert_tokens.html
+    */
+    #[cfg(feature = "test")]
+    #[doc(hidden)]
+    pub fn compare_tokens(actual: &[Token], expected: &[Token]) -> Option<String> {
+        use crate::std::{
+            cmp,
+            fmt::Write,
+        };
+
+        if actual == expected {
+            return None;
+        }
+
+        let actual: Vec<String> = actual.iter().map(|t| format!("{:?}", t)).collect();
+        let expected: Vec<String> = expected.iter().map(|t| format!("{:?}", t)).collect();
+
+        let width = actual
+            .iter()
+            .map(|t| t.len())
+            .max()
+            .unwrap_or(0)
+            .max("actual".len());
+
+        let mut msg = String::from("the value produced unexpected tokens\n");
+        let _ = writeln!(msg, "      {:width$} | expected", "actual", width = width);
+
+        for i in 0..cmp::max(actual.len(), expected.len()) {
+            let a = actual.get(i).map(|t| &**t).unwrap_or("");
+            let e = expected.get(i).map(|t| &**t).unwrap_or("");
+            let marker = if a == e { ' ' } else { '!' };
+
+            let _ = writeln!(
+                msg,
+                "{} {:>3} {:width$} | {}",
+                marker,
+                i,
+                a,
+                e,
+                width = width
+            );
+        }
+
+        Some(msg)
+    }
+
     /**
     Ensure a stream is valid for various inputs.
 
@@ -150,6 +234,7 @@ mod alloc_support {
             Box::new(4271.00000000001f64),
             Box::new('山'),
             Box::new("🍔∈🌏"),
+            Box::new(value::Bytes::new(&b"\x00\xf0\x9f\x8d\x94"[..])),
             Box::new(Some(1)),
             Box::new(Option::None::<u8>),
             Box::new(vec![1, 2, 3, 4]),
@@ -221,6 +306,37 @@ mod alloc_support {
         }
     }
 
+    /**
+    Ensure a value survives a round-trip through `serde`.
+
+    The value is converted into a `serde::Serialize` using [`to_serialize`],
+    and then back into a [`Value`] using [`to_value`]. The tokens produced by
+    the round-tripped value are compared with the tokens produced by the original.
+
+    # Panics
+
+    This method will panic if the round-tripped tokens are different.
+
+    [`Value`]: ../value/trait.Value.html
+    [`to_serialize`]: ../serde/v1/fn.to_serialize.html
+    [`to_value`]: ../serde/v1/fn.to_value.html
+    */
+    #[cfg(feature = "serde1_lib")]
+    pub fn roundtrip(v: &(impl Value + ?Sized)) {
+        use crate::serde::v1::{
+            to_serialize,
+            to_value,
+        };
+
+        let expected = tokens(v);
+        let actual = tokens(&to_value(to_serialize(v)));
+
+        assert_eq!(
+            expected, actual,
+            "the value produced different tokens after a round-trip through `serde`"
+        );
+    }
+
     #[cfg(feature = "std")]
     mod std_support {
         use super::*;
@@ -240,3 +356,87 @@ mod alloc_support {
 
 #[cfg(feature = "alloc")]
 pub use self::alloc_support::*;
+
+/**
+Assert that a value produces the given sequence of tokens.
+
+On failure, the actual and expected tokens are printed side-by-side,
+with mismatched tokens marked by a `!`:
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::test::{assert_tokens, Token};
+
+assert_tokens!(Some(42i64), [Token::Signed(42)]);
+assert_tokens!(
+    vec![1, 2],
+    [
+        Token::SeqBegin(Some(2)),
+        Token::I32(1),
+        Token::I32(2),
+        Token::SeqEnd,
+    ]
+);
+# }
+# }
+```
+*/
+#[cfg(feature = "test")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! assert_tokens {
+    ($value:expr, [$($token:expr),* $(,)?] $(,)?) => {{
+        let actual = $crate::test::tokens(&$value);
+        let expected: &[$crate::test::Token] = &[$($token),*];
+
+        if let Some(msg) = $crate::test::compare_tokens(&actual, expected) {
+            panic!("{}", msg);
+        }
+    }};
+}
+
+#[cfg(feature = "test")]
+#[doc(inline)]
+pub use crate::assert_tokens;
+
+#[cfg(all(test, feature = "test"))]
+mod tests {
+    use super::Token;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_tokens_eq() {
+        assert_tokens!(1i64, [Token::Signed(1)]);
+        assert_tokens!(
+            vec!["a"],
+            [
+                Token::SeqBegin(Some(1)),
+                Token::Str("a".into()),
+                Token::SeqEnd,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compare_tokens_ne() {
+        let msg = super::compare_tokens(&[Token::Signed(1), Token::Signed(2)], &[Token::Signed(1)])
+            .unwrap();
+
+        assert_eq!(
+            "the value produced unexpected tokens\n      actual    | expected\n    0 Signed(1) | Signed(1)\n!   1 Signed(2) | \n",
+            msg
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn assert_tokens_ne() {
+        assert_tokens!(1i64, [Token::Signed(2)]);
+    }
+}
@@ -0,0 +1,731 @@
+/*!
+TOML support for `sval`.
+
+Add the `toml` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["toml"]
+```
+
+# Writing TOML to `io::Write`
+
+```
+# fn main() -> Result<(), sval::Error> {
+use std::collections::BTreeMap;
+
+let mut server = BTreeMap::new();
+server.insert("host", "localhost");
+server.insert("port", "8080");
+
+let mut config = BTreeMap::new();
+config.insert("server", server);
+
+let mut encoder = sval::toml::Encoder::new(Vec::new());
+sval::stream(&mut encoder, &config)?;
+
+assert_eq!(
+    "server = { host = \"localhost\", port = \"8080\" }\n",
+    String::from_utf8(encoder.into_inner()).unwrap()
+);
+# Ok(())
+# }
+```
+
+# Mapping values to TOML
+
+TOML documents are tables, so only maps can be written as TOML.
+Other values return an error.
+
+- Each entry in the map being streamed is written as a `key = value` line.
+- Maps within the document are written as inline tables, like `{ a = 1, b = 2 }`.
+- Sequences are written as arrays. Arrays may contain values of
+  different types, which TOML allows since version `1.0`.
+- Bytes are written as arrays of integers.
+- Keys must be strings or characters. Keys that aren't valid bare keys are quoted.
+- Values tagged with `"datetime"` using [`Stream::tagged_begin`] are written
+  as offset date-times rather than strings if they're valid RFC 3339 date-times.
+  Otherwise they're written as strings.
+
+TOML doesn't have empty values, so streaming one returns an error.
+Integers outside the range of an `i64` also return an error.
+
+[`Stream::tagged_begin`]: ../stream/trait.Stream.html#method.tagged_begin
+*/
+
+use crate::{
+    std::{
+        fmt::Write as _,
+        io::Write,
+        string::{
+            String,
+            ToString,
+        },
+        vec::Vec,
+    },
+    stream::{
+        self,
+        Stream,
+    },
+};
+
+/**
+A stream for writing structured data as TOML.
+
+The stream internally wraps a [`std::io::Write`].
+*/
+pub struct Encoder<W> {
+    stack: Vec<Frame>,
+    is_key: bool,
+    is_datetime: bool,
+    out: W,
+}
+
+enum Frame {
+    Document,
+    Table { is_empty: bool },
+    Array { is_empty: bool },
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    /**
+    Create a new TOML stream.
+    */
+    pub fn new(out: W) -> Self {
+        Encoder {
+            stack: Vec::new(),
+            is_key: false,
+            is_datetime: false,
+            out,
+        }
+    }
+
+    /**
+    Get the inner writer back out of the stream without ensuring it's valid.
+    */
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    /**
+    Check that a value can be written in the current position.
+    */
+    fn value_begin(&self) -> stream::Result {
+        if self.stack.is_empty() {
+            return Err(crate::Error::msg("TOML documents must be maps"));
+        }
+
+        if self.is_key {
+            return Err(crate::Error::msg("TOML keys must be strings"));
+        }
+
+        Ok(())
+    }
+
+    /**
+    Complete a value, ending its line if it's an entry in the document.
+    */
+    fn value_end(&mut self) -> stream::Result {
+        if let Some(Frame::Document) = self.stack.last() {
+            self.out.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+
+    fn primitive(&mut self, v: impl crate::std::fmt::Display) -> stream::Result {
+        self.value_begin()?;
+        write!(self.out, "{}", v)?;
+        self.value_end()
+    }
+
+    fn key(&mut self, k: &str) -> stream::Result {
+        self.is_key = false;
+
+        let is_bare = !k.is_empty()
+            && k.bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-');
+
+        if is_bare {
+            self.out.write_all(k.as_bytes())?;
+        } else {
+            self.out.write_all(escape(k).as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn item(&mut self) -> stream::Result {
+        match self.stack.last_mut() {
+            Some(Frame::Document) => (),
+            Some(Frame::Table { is_empty }) => {
+                self.out.write_all(if *is_empty { b" " } else { b", " })?;
+                *is_empty = false;
+            }
+            Some(Frame::Array { is_empty }) => {
+                if !*is_empty {
+                    self.out.write_all(b", ")?;
+                }
+                *is_empty = false;
+            }
+            None => return Err(crate::Error::msg("unexpected map key or sequence element")),
+        }
+
+        Ok(())
+    }
+}
+
+/**
+Write a string as a TOML basic string.
+*/
+fn escape(v: &str) -> String {
+    let mut escaped = String::with_capacity(v.len() + 2);
+
+    escaped.push('"');
+    for c in v.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04X}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+/**
+Check whether a string is an RFC 3339 offset date-time.
+
+Only strings that pass this check are written as bare TOML date-times.
+Anything else is escaped as a string so it can't break out of its value.
+*/
+fn is_datetime(v: &str) -> bool {
+    fn digits(v: &[u8], max: u32) -> Option<&[u8]> {
+        let (d, rest) = (v.get(..2)?, &v[2..]);
+
+        if !d.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let n = u32::from(d[0] - b'0') * 10 + u32::from(d[1] - b'0');
+        if n > max {
+            return None;
+        }
+
+        Some(rest)
+    }
+
+    fn sep(v: &[u8], s: u8) -> Option<&[u8]> {
+        match v.split_first() {
+            Some((&b, rest)) if b == s => Some(rest),
+            _ => None,
+        }
+    }
+
+    fn check(v: &[u8]) -> Option<()> {
+        // date: YYYY-MM-DD
+        let v = digits(v, 99)?;
+        let v = digits(v, 99)?;
+        let v = sep(v, b'-')?;
+        let v = digits(v, 12)?;
+        let v = sep(v, b'-')?;
+        let v = digits(v, 31)?;
+
+        // separator: `T`, `t`, or a space
+        let v = match v.split_first()? {
+            (b'T', v) | (b't', v) | (b' ', v) => v,
+            _ => return None,
+        };
+
+        // time: HH:MM:SS with optional fractional seconds
+        let v = digits(v, 23)?;
+        let v = sep(v, b':')?;
+        let v = digits(v, 59)?;
+        let v = sep(v, b':')?;
+        let mut v = digits(v, 60)?;
+
+        if let Some(rest) = sep(v, b'.') {
+            let frac = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+            if frac == 0 {
+                return None;
+            }
+
+            v = &rest[frac..];
+        }
+
+        // offset: `Z`, `z`, or +HH:MM / -HH:MM
+        let v = match v.split_first()? {
+            (b'Z', v) | (b'z', v) => v,
+            (b'+', v) | (b'-', v) => {
+                let v = digits(v, 23)?;
+                let v = sep(v, b':')?;
+                digits(v, 59)?
+            }
+            _ => return None,
+        };
+
+        if v.is_empty() {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    check(v.as_bytes()).is_some()
+}
+
+/**
+Write a float so that it's always a valid TOML float.
+*/
+fn float(v: f64, debug: impl crate::std::fmt::Debug) -> String {
+    if v.is_nan() {
+        "nan".into()
+    } else if v.is_infinite() {
+        if v > 0.0 { "inf" } else { "-inf" }.into()
+    } else {
+        // The `Debug` representation of a finite float
+        // always has a fractional part or an exponent
+        format!("{:?}", debug)
+    }
+}
+
+impl<'v, W> Stream<'v> for Encoder<W>
+where
+    W: Write,
+{
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
+        self.str(&v.to_string())
+    }
+
+    fn fmt_borrowed(&mut self, v: stream::Arguments<'v>) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, v: stream::Source) -> stream::Result {
+        self.fmt(stream::Arguments::display(&v))
+    }
+
+    fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> stream::Result {
+        self.primitive(v)
+    }
+
+    fn u64(&mut self, v: u64) -> stream::Result {
+        if v > i64::MAX as u64 {
+            return Err(crate::Error::msg(
+                "TOML integers must fit in a 64bit signed integer",
+            ));
+        }
+
+        self.primitive(v)
+    }
+
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.i64(v as i64)
+    }
+
+    fn i128(&mut self, v: i128) -> stream::Result {
+        if v < i64::MIN as i128 || v > i64::MAX as i128 {
+            return Err(crate::Error::msg(
+                "TOML integers must fit in a 64bit signed integer",
+            ));
+        }
+
+        self.i64(v as i64)
+    }
+
+    fn u128(&mut self, v: u128) -> stream::Result {
+        if v > i64::MAX as u128 {
+            return Err(crate::Error::msg(
+                "TOML integers must fit in a 64bit signed integer",
+            ));
+        }
+
+        self.i64(v as i64)
+    }
+
+    fn f64(&mut self, v: f64) -> stream::Result {
+        self.primitive(float(v, v))
+    }
+
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.primitive(float(v as f64, v))
+    }
+
+    fn bool(&mut self, v: bool) -> stream::Result {
+        self.primitive(v)
+    }
+
+    fn char(&mut self, v: char) -> stream::Result {
+        self.str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn str(&mut self, v: &str) -> stream::Result {
+        if self.is_key {
+            return self.key(v);
+        }
+
+        if self.is_datetime && is_datetime(v) {
+            return self.primitive(v);
+        }
+
+        self.primitive(escape(v))
+    }
+
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        if tag == "datetime" {
+            self.is_datetime = true;
+        }
+
+        Ok(())
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> stream::Result {
+        if tag == "datetime" {
+            self.is_datetime = false;
+        }
+
+        Ok(())
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.seq_begin(Some(v.len()))?;
+
+        for b in v {
+            self.seq_elem()?;
+            self.u8(*b)?;
+        }
+
+        self.seq_end()
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        Err(crate::Error::msg("TOML doesn't support empty values"))
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> stream::Result {
+        if self.stack.is_empty() {
+            self.stack.push(Frame::Document);
+
+            return Ok(());
+        }
+
+        self.value_begin()?;
+        self.out.write_all(b"{")?;
+        self.stack.push(Frame::Table { is_empty: true });
+
+        Ok(())
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        self.item()?;
+        self.is_key = true;
+
+        Ok(())
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        if self.is_key {
+            return Err(crate::Error::msg("TOML keys must be strings"));
+        }
+
+        self.out.write_all(b" = ")?;
+
+        Ok(())
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        match self.stack.pop() {
+            Some(Frame::Document) => Ok(()),
+            Some(Frame::Table { is_empty }) => {
+                self.out.write_all(if is_empty { b"}" } else { b" }" })?;
+                self.value_end()
+            }
+            _ => Err(crate::Error::msg("unexpected end of a map")),
+        }
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> stream::Result {
+        self.value_begin()?;
+        self.out.write_all(b"[")?;
+        self.stack.push(Frame::Array { is_empty: true });
+
+        Ok(())
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        self.item()
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        match self.stack.pop() {
+            Some(Frame::Array { .. }) => {
+                self.out.write_all(b"]")?;
+                self.value_end()
+            }
+            _ => Err(crate::Error::msg("unexpected end of a sequence")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::collections::BTreeMap,
+        value::{
+            self,
+            Value,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn encode(v: impl Value) -> Result<String, crate::Error> {
+        let mut encoder = Encoder::new(Vec::new());
+        crate::stream(&mut encoder, &v)?;
+
+        Ok(String::from_utf8(encoder.into_inner()).unwrap())
+    }
+
+    fn entry(k: &'static str, v: impl Value) -> Result<String, crate::Error> {
+        struct Entry<V>(&'static str, V);
+
+        impl<V: Value> Value for Entry<V> {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin(Some(1))?;
+                stream.map_flat_entry(&self.0, &self.1)?;
+                stream.map_end()
+            }
+        }
+
+        encode(Entry(k, v))
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_primitives() {
+        assert_eq!("a = 1\n", entry("a", 1u8).unwrap());
+        assert_eq!("a = -1\n", entry("a", -1i64).unwrap());
+        assert_eq!("a = 1.0\n", entry("a", 1f64).unwrap());
+        assert_eq!("a = 1.1\n", entry("a", 1.1f32).unwrap());
+        assert_eq!("a = 1e100\n", entry("a", 1e100f64).unwrap());
+        assert_eq!("a = nan\n", entry("a", f64::NAN).unwrap());
+        assert_eq!("a = -inf\n", entry("a", f64::NEG_INFINITY).unwrap());
+        assert_eq!("a = true\n", entry("a", true).unwrap());
+        assert_eq!("a = \"b\"\n", entry("a", 'b').unwrap());
+        assert_eq!(
+            "a = \"a \\\"quoted\\\"\\n\\u0000string\"\n",
+            entry("a", "a \"quoted\"\n\u{0}string").unwrap()
+        );
+        assert_eq!(
+            "a = [1, 2]\n",
+            entry("a", value::Bytes::new(&[1, 2])).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_keys() {
+        assert_eq!("a_b-1 = 1\n", entry("a_b-1", 1).unwrap());
+        assert_eq!("\"a.b\" = 1\n", entry("a.b", 1).unwrap());
+        assert_eq!("\"\" = 1\n", entry("", 1).unwrap());
+
+        let mut map = BTreeMap::new();
+        map.insert(1, 1);
+
+        assert!(encode(&map).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_nested() {
+        let mut inner = BTreeMap::new();
+        inner.insert("b", vec![vec![1], vec![]]);
+        inner.insert("c", vec![]);
+
+        let mut map = BTreeMap::new();
+        map.insert("a", inner);
+        map.insert("d", BTreeMap::new());
+
+        assert_eq!(
+            "a = { b = [[1], []], c = [] }\nd = {}\n",
+            encode(&map).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_tagged() {
+        struct DateTime;
+
+        impl Value for DateTime {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.tagged_begin("datetime")?;
+                stream.str("1979-05-27T07:32:00Z")?;
+                stream.tagged_end("datetime")
+            }
+        }
+
+        assert_eq!("a = 1979-05-27T07:32:00Z\n", entry("a", DateTime).unwrap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_tagged_invalid_datetime() {
+        struct DateTime(&'static str);
+
+        impl Value for DateTime {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.tagged_begin("datetime")?;
+                stream.str(self.0)?;
+                stream.tagged_end("datetime")
+            }
+        }
+
+        assert_eq!(
+            "a = \"1979-05-27T07:32:00Z\\nadmin = true\"\n",
+            entry("a", DateTime("1979-05-27T07:32:00Z\nadmin = true")).unwrap()
+        );
+        assert_eq!(
+            "a = \"not a datetime\"\n",
+            entry("a", DateTime("not a datetime")).unwrap()
+        );
+        assert_eq!(
+            "a = 1979-05-27 00:32:00.999999-07:00\n",
+            entry("a", DateTime("1979-05-27 00:32:00.999999-07:00")).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_invalid() {
+        assert!(encode(1).is_err());
+        assert!(encode(vec![1]).is_err());
+        assert!(entry("a", Option::None::<i32>).is_err());
+        assert!(entry("a", vec![Some(1), None]).is_err());
+        assert!(entry("a", u64::MAX).is_err());
+        assert!(entry("a", i128::MIN).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    stream::{
+        self,
+        Stream,
+    },
+    value::Value,
+    Error,
+};
+
+/**
+Transcode a [`Value`] into a [`Stream`], checking that it's well-formed.
+
+This is like [`stream_owned`](fn.stream_owned.html), except the structure
+produced by the value is validated by a [`ValidatingStream`] before it
+reaches the stream. An error is returned if the value:
+
+- ends a map or sequence that it didn't begin,
+- ends a map with a sequence end or a sequence with a map end,
+- streams map keys or values outside of a map, or out of order,
+- streams sequence elements outside of a sequence,
+- nests maps and sequences deeper than a `ValidatingStream` supports,
+- doesn't end all of the maps and sequences it begins.
+
+```
+# fn main() -> Result<(), sval::Error> {
+# #[cfg(feature = "alloc")]
+# {
+use sval::buffer::Buffer;
+
+let mut buffer = Buffer::new();
+sval::transcode(&vec![1, 2, 3], &mut buffer)?;
+# }
+# Ok(())
+# }
+```
+
+[`Value`]: value/trait.Value.html
+[`Stream`]: stream/trait.Stream.html
+[`ValidatingStream`]: stream/struct.ValidatingStream.html
+*/
+pub fn transcode<'a>(value: impl Value, stream: impl Stream<'a>) -> Result<(), Error> {
+    let mut validate = stream::validate(stream);
+
+    crate::stream_owned(&mut validate, value)?;
+
+    validate.end()
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        buffer::Buffer,
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+        test,
+        value,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    struct Tokens(fn(&mut value::Stream) -> value::Result);
+
+    impl Value for Tokens {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            (self.0)(&mut stream)
+        }
+    }
+
+    fn transcode_err(f: fn(&mut value::Stream) -> value::Result) {
+        assert!(transcode(Tokens(f), Buffer::new()).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn transcode_valid() {
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![vec![1], vec![]]);
+        map.insert("b", vec![]);
+
+        let mut buffer = Buffer::new();
+        transcode(&map, &mut buffer).unwrap();
+
+        assert_eq!(test::tokens(&map), test::tokens(&buffer));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn transcode_unmatched_end() {
+        transcode_err(|stream| stream.seq_end());
+        transcode_err(|stream| stream.map_end());
+        transcode_err(|stream| {
+            stream.seq_begin(None)?;
+            stream.map_end()
+        });
+        transcode_err(|stream| {
+            stream.map_begin(None)?;
+            stream.seq_end()
+        });
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn transcode_unterminated() {
+        transcode_err(|stream| stream.seq_begin(None));
+        transcode_err(|stream| {
+            stream.map_begin(None)?;
+            stream.map_key(&1)?;
+            stream.map_value_begin()?.seq_begin(None)?;
+            stream.map_end()
+        });
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn transcode_misplaced_items() {
+        transcode_err(|stream| stream.map_key(&1));
+        transcode_err(|stream| {
+            stream.seq_begin(None)?;
+            stream.map_key(&1)
+        });
+        transcode_err(|stream| {
+            stream.map_begin(None)?;
+            stream.map_value(&1)
+        });
+        transcode_err(|stream| {
+            stream.map_begin(None)?;
+            stream.map_key(&1)?;
+            stream.map_end()
+        });
+        transcode_err(|stream| {
+            stream.map_begin(None)?;
+            stream.seq_elem(&1)
+        });
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn transcode_too_deep() {
+        transcode_err(|stream| {
+            for _ in 0..=sval_stack::stack2::Stack::MAX_DEPTH {
+                stream.seq_begin(None)?;
+                stream.seq_elem_begin()?;
+            }
+
+            Ok(())
+        });
+    }
+}
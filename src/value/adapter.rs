@@ -0,0 +1,219 @@
+use crate::value::{
+    self,
+    Value,
+};
+
+/**
+A map that's streamed from an iterator of key-value pairs.
+
+The iterator is cloned each time the map is streamed, so it
+should be cheap to clone, like an iterator over a borrowed collection.
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::{
+    test::{self, Token},
+    value::MapAdapter,
+};
+
+let map = MapAdapter::with_len(vec![("a", 1), ("b", 2)].into_iter(), 2);
+
+assert_eq!(
+    vec![
+        Token::MapBegin(Some(2)),
+        Token::Str("a".into()),
+        Token::I32(1),
+        Token::Str("b".into()),
+        Token::I32(2),
+        Token::MapEnd,
+    ],
+    test::tokens(&map),
+);
+# }
+# }
+```
+*/
+#[derive(Clone, Debug)]
+pub struct MapAdapter<I> {
+    iter: I,
+    len: Option<usize>,
+}
+
+impl<I, K, V> MapAdapter<I>
+where
+    I: Iterator<Item = (K, V)> + Clone,
+    K: Value,
+    V: Value,
+{
+    /**
+    Wrap an iterator of key-value pairs without a length hint.
+    */
+    pub fn new(iter: I) -> Self {
+        MapAdapter { iter, len: None }
+    }
+
+    /**
+    Wrap an iterator of key-value pairs with the given length hint.
+    */
+    pub fn with_len(iter: I, len: usize) -> Self {
+        MapAdapter {
+            iter,
+            len: Some(len),
+        }
+    }
+}
+
+impl<I, K, V> Value for MapAdapter<I>
+where
+    I: Iterator<Item = (K, V)> + Clone,
+    K: Value,
+    V: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.map_begin(self.len)?;
+
+        for (k, v) in self.iter.clone() {
+            stream.owned().map_key(&k)?;
+            stream.owned().map_value(&v)?;
+        }
+
+        stream.map_end()
+    }
+}
+
+/**
+A sequence that's streamed from an iterator of elements.
+
+The iterator is cloned each time the sequence is streamed, so it
+should be cheap to clone, like an iterator over a borrowed collection.
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::{
+    test::{self, Token},
+    value::SeqAdapter,
+};
+
+let seq = SeqAdapter::new((1..3).map(|i| i * 2));
+
+assert_eq!(
+    vec![
+        Token::SeqBegin(None),
+        Token::I32(2),
+        Token::I32(4),
+        Token::SeqEnd,
+    ],
+    test::tokens(&seq),
+);
+# }
+# }
+```
+*/
+#[derive(Clone, Debug)]
+pub struct SeqAdapter<I> {
+    iter: I,
+    len: Option<usize>,
+}
+
+impl<I> SeqAdapter<I>
+where
+    I: Iterator + Clone,
+    I::Item: Value,
+{
+    /**
+    Wrap an iterator of elements without a length hint.
+    */
+    pub fn new(iter: I) -> Self {
+        SeqAdapter { iter, len: None }
+    }
+
+    /**
+    Wrap an iterator of elements with the given length hint.
+    */
+    pub fn with_len(iter: I, len: usize) -> Self {
+        SeqAdapter {
+            iter,
+            len: Some(len),
+        }
+    }
+}
+
+impl<I> Value for SeqAdapter<I>
+where
+    I: Iterator + Clone,
+    I::Item: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.seq_begin(self.len)?;
+
+        for v in self.iter.clone() {
+            stream.owned().seq_elem(&v)?;
+        }
+
+        stream.seq_end()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn map_adapter_is_consistent_with_map() {
+        let mut map = BTreeMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+
+        assert_eq!(
+            test::tokens(&map),
+            test::tokens(&MapAdapter::with_len(map.iter(), map.len()))
+        );
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(None),
+                Token::Str("a".into()),
+                Token::I32(1),
+                Token::Str("b".into()),
+                Token::I32(2),
+                Token::MapEnd,
+            ],
+            test::tokens(&MapAdapter::new(map.iter()))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn seq_adapter_is_consistent_with_seq() {
+        let seq = vec![1, 2, 3];
+
+        assert_eq!(
+            test::tokens(&seq),
+            test::tokens(&SeqAdapter::with_len(seq.iter(), seq.len()))
+        );
+
+        assert_eq!(
+            vec![Token::SeqBegin(None), Token::SeqEnd],
+            test::tokens(&SeqAdapter::new(seq.iter().filter(|_| false)))
+        );
+    }
+}
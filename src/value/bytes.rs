@@ -0,0 +1,138 @@
+use crate::value::{
+    self,
+    Value,
+};
+
+/**
+A slice of bytes.
+
+Byte slices already implement [`Value`] as a sequence of integers.
+Wrapping them in `Bytes` will stream them using [`Stream::bytes`] instead.
+
+```
+# use sval::value::{self, Value};
+fn with_value(value: impl Value) {
+    // Do something with the value
+}
+
+with_value(value::Bytes::new([0u8; 32]));
+```
+
+Types that own their bytes can also call [`Stream::bytes`] directly.
+
+[`Value`]: trait.Value.html
+[`Stream::bytes`]: struct.Stream.html#method.bytes
+*/
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Bytes<B>(B);
+
+impl<B> Bytes<B>
+where
+    B: AsRef<[u8]>,
+{
+    /**
+    Wrap a slice of bytes.
+    */
+    pub fn new(bytes: B) -> Self {
+        Bytes(bytes)
+    }
+
+    /**
+    Get the inner bytes.
+    */
+    pub fn into_inner(self) -> B {
+        self.0
+    }
+}
+
+impl<B> Value for Bytes<B>
+where
+    B: AsRef<[u8]>,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.bytes(self.0.as_ref())
+    }
+}
+
+#[cfg(feature = "bytes")]
+mod bytes_support {
+    use super::*;
+
+    impl Value for bytes_lib::Bytes {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.bytes(self.as_ref())
+        }
+    }
+
+    impl Value for bytes_lib::BytesMut {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.bytes(self.as_ref())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    mod alloc_support {
+        use crate::{
+            std::vec::Vec,
+            test::{
+                self,
+                Token,
+            },
+            value::Bytes,
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_bytes() {
+            assert_eq!(
+                vec![Token::Bytes(Vec::new())],
+                test::tokens(&Bytes::new(&[] as &[u8]))
+            );
+
+            assert_eq!(
+                vec![Token::Bytes(vec![1, 2, 3])],
+                test::tokens(&Bytes::new(&[1u8, 2, 3][..]))
+            );
+
+            assert_eq!(
+                vec![Token::Bytes(vec![1, 2, 3])],
+                test::tokens(&Bytes::new(vec![1u8, 2, 3]))
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_byte_slice_as_seq() {
+            assert_eq!(
+                vec![
+                    Token::SeqBegin(Some(2)),
+                    Token::U8(1),
+                    Token::U8(2),
+                    Token::SeqEnd,
+                ],
+                test::tokens(&[1u8, 2][..])
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "bytes")]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_bytes_crate() {
+            assert_eq!(
+                vec![Token::Bytes(vec![1, 2, 3])],
+                test::tokens(&bytes_lib::Bytes::from_static(&[1, 2, 3]))
+            );
+
+            assert_eq!(
+                vec![Token::Bytes(vec![1, 2, 3])],
+                test::tokens(&bytes_lib::BytesMut::from(&[1u8, 2, 3][..]))
+            );
+        }
+    }
+}
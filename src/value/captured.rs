@@ -0,0 +1,139 @@
+use crate::value::{
+    self,
+    Value,
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::boxed::Box;
+
+/**
+A borrowed value with its concrete type erased.
+
+The captured value is streamed in its place. This makes it possible
+to pass values of different types through an API that expects a
+single concrete type.
+
+```
+# use sval::value::{self, Value};
+fn with_values(values: &[value::Captured]) {
+    // Do something with the values
+}
+
+with_values(&[value::Captured::new(&1), value::Captured::new(&"a string")]);
+```
+*/
+#[derive(Clone, Copy)]
+pub struct Captured<'a>(&'a dyn Value);
+
+impl<'a> Captured<'a> {
+    /**
+    Capture a borrowed value.
+    */
+    pub fn new(value: &'a impl Value) -> Self {
+        Captured(value)
+    }
+
+    /**
+    Capture a borrowed value that's already a trait object.
+    */
+    pub fn from_dyn(value: &'a dyn Value) -> Self {
+        Captured(value)
+    }
+}
+
+impl<'a> Value for Captured<'a> {
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        self.0.stream(stream)
+    }
+
+    fn stream_owned(&self, stream: value::Stream) -> value::Result {
+        self.0.stream_owned(stream)
+    }
+}
+
+/**
+An owned value with its concrete type erased.
+
+The captured value is boxed and streamed in its place.
+*/
+#[cfg(feature = "alloc")]
+pub struct OwnedCaptured(Box<dyn Value>);
+
+#[cfg(feature = "alloc")]
+impl OwnedCaptured {
+    /**
+    Capture an owned value.
+    */
+    pub fn new(value: impl Value + 'static) -> Self {
+        OwnedCaptured(Box::new(value))
+    }
+
+    /**
+    Capture a boxed value that's already a trait object.
+    */
+    pub fn from_box(value: Box<dyn Value>) -> Self {
+        OwnedCaptured(value)
+    }
+
+    /**
+    Borrow the captured value.
+    */
+    pub fn by_ref(&self) -> Captured<'_> {
+        Captured(&*self.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Value for OwnedCaptured {
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        self.0.stream(stream)
+    }
+
+    fn stream_owned(&self, stream: value::Stream) -> value::Result {
+        self.0.stream_owned(stream)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            string::String,
+            vec,
+        },
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn captured_streams_inner() {
+        let values = [Captured::new(&1), Captured::new(&"a string")];
+
+        assert_eq!(
+            vec![
+                Token::SeqBegin(Some(2)),
+                Token::I32(1),
+                Token::Str("a string".into()),
+                Token::SeqEnd,
+            ],
+            test::tokens(&values[..])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_captured_streams_inner() {
+        let value = OwnedCaptured::new(vec![String::from("a string")]);
+
+        assert_eq!(test::tokens(&vec!["a string"]), test::tokens(&value));
+        assert_eq!(test::tokens(&value), test::tokens(&value.by_ref()));
+    }
+}
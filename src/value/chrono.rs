@@ -0,0 +1,123 @@
+use crate::{
+    std::{
+        fmt,
+        string::ToString,
+    },
+    value::{
+        self,
+        Value,
+    },
+};
+
+use chrono_lib::{
+    DateTime,
+    NaiveDate,
+    TimeZone,
+};
+
+/**
+Date-times are streamed as RFC 3339 strings, tagged with `"datetime"`.
+*/
+impl<Tz> Value for DateTime<Tz>
+where
+    Tz: TimeZone,
+    Tz::Offset: fmt::Display,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.tagged_begin("datetime")?;
+        stream.str_owned(self.to_rfc3339())?;
+        stream.tagged_end("datetime")
+    }
+}
+
+/**
+Dates are streamed as ISO 8601 strings, tagged with `"date"`.
+
+Dates aren't tagged with `"datetime"` because they don't have a time,
+so they aren't valid RFC 3339 date-times.
+*/
+impl Value for NaiveDate {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.tagged_begin("date")?;
+        stream.str_owned(self.to_string())?;
+        stream.tagged_end("date")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            string::String,
+            vec,
+        },
+        stream::{
+            RecordedCall,
+            RecordingStream,
+        },
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    use chrono_lib::{
+        FixedOffset,
+        Utc,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2013, 3, 21).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn stream_datetime() {
+        let utc = Utc.from_utc_datetime(&date().and_hms_opt(20, 4, 0).unwrap());
+        let offset = utc.with_timezone(&FixedOffset::east_opt(10 * 3600).unwrap());
+
+        assert_eq!(
+            vec![Token::Str("2013-03-21T20:04:00+00:00".into())],
+            test::tokens(&utc)
+        );
+        assert_eq!(
+            vec![Token::Str("2013-03-22T06:04:00+10:00".into())],
+            test::tokens(&offset)
+        );
+
+        let mut recording = RecordingStream::new();
+        crate::stream(&mut recording, &utc).unwrap();
+
+        assert_eq!(
+            &[
+                RecordedCall::TaggedBegin("datetime".into()),
+                RecordedCall::Str("2013-03-21T20:04:00+00:00".into()),
+                RecordedCall::TaggedEnd("datetime".into()),
+            ][..],
+            recording.calls()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn stream_date() {
+        assert_eq!(vec![Token::Str("2013-03-21".into())], test::tokens(&date()));
+
+        let mut recording = RecordingStream::new();
+        crate::stream(&mut recording, &date()).unwrap();
+
+        assert_eq!(
+            &[
+                RecordedCall::TaggedBegin("date".into()),
+                RecordedCall::Str(String::from("2013-03-21")),
+                RecordedCall::TaggedEnd("date".into()),
+            ][..],
+            recording.calls()
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::value::{
+    self,
+    Value,
+};
+
+/**
+A variant of an enum.
+
+Variants with values are streamed as a map with a single entry,
+where the key is the name of the variant. Unit variants are
+streamed as just the name of the variant. This is the same as
+`serde`'s externally tagged representation.
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::{
+    test::{self, Token},
+    value::{self, Enum, Value},
+};
+
+enum Shape {
+    Circle(f64),
+    Empty,
+}
+
+impl Value for Shape {
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        match self {
+            Shape::Circle(radius) => Enum::variant("Circle", 0, radius).stream_owned(stream),
+            Shape::Empty => Enum::unit_variant("Empty").stream_owned(stream),
+        }
+    }
+}
+
+assert_eq!(
+    vec![
+        Token::MapBegin(Some(1)),
+        Token::Str("Circle".into()),
+        Token::Float(1.0),
+        Token::MapEnd,
+    ],
+    test::tokens(&Shape::Circle(1.0)),
+);
+
+assert_eq!(
+    vec![Token::Str("Empty".into())],
+    test::tokens(&Shape::Empty),
+);
+# }
+# }
+```
+*/
+#[derive(Clone, Copy)]
+pub struct Enum<'a> {
+    name: &'a str,
+    index: Option<u32>,
+    value: Option<&'a dyn Value>,
+}
+
+impl<'a> Enum<'a> {
+    /**
+    A variant with a value.
+    */
+    pub fn variant(name: &'a str, index: u32, value: &'a impl Value) -> Self {
+        Enum {
+            name,
+            index: Some(index),
+            value: Some(value),
+        }
+    }
+
+    /**
+    A variant without a value.
+    */
+    pub fn unit_variant(name: &'a str) -> Self {
+        Enum {
+            name,
+            index: None,
+            value: None,
+        }
+    }
+
+    /**
+    Get the name of the variant.
+    */
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /**
+    Get the index of the variant, if it has one.
+
+    The index isn't streamed.
+    */
+    pub fn index(&self) -> Option<u32> {
+        self.index
+    }
+
+    /**
+    Stream the variant, consuming it.
+
+    This is useful when an `Enum` is built inside an implementation
+    of [`Value`](trait.Value.html), so its value can't outlive it.
+    */
+    pub fn stream_owned(self, mut stream: value::Stream) -> value::Result {
+        stream.owned().any(&self)
+    }
+}
+
+impl<'a> Value for Enum<'a> {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        match self.value {
+            Some(ref value) => {
+                stream.map_begin(Some(1))?;
+                stream.map_key(&self.name)?;
+                stream.map_value(value)?;
+                stream.map_end()
+            }
+            None => stream.str(self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    mod alloc_support {
+        use crate::{
+            std::vec,
+            test::{
+                self,
+                Token,
+            },
+            value::Enum,
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_variant() {
+            let value = vec![1, 2];
+            let variant = Enum::variant("Seq", 1, &value);
+
+            assert_eq!("Seq", variant.name());
+            assert_eq!(Some(1), variant.index());
+
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(1)),
+                    Token::Str("Seq".into()),
+                    Token::SeqBegin(Some(2)),
+                    Token::I32(1),
+                    Token::I32(2),
+                    Token::SeqEnd,
+                    Token::MapEnd,
+                ],
+                test::tokens(&variant)
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_unit_variant() {
+            let variant = Enum::unit_variant("Empty");
+
+            assert_eq!(None, variant.index());
+
+            assert_eq!(vec![Token::Str("Empty".into())], test::tokens(&variant));
+        }
+    }
+}
@@ -0,0 +1,591 @@
+use crate::{
+    stream::{
+        self,
+        Stream,
+    },
+    value::{
+        self,
+        Value,
+    },
+};
+
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
+/**
+A sequence with its nested sequences inlined into it.
+
+Any element of the sequence that's itself a sequence has its
+elements streamed in its place, like `Iterator::flatten`.
+Elements that aren't sequences are streamed as they are.
+Only one level of nesting is flattened.
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::{
+    test::{self, Token},
+    value::Flatten,
+};
+
+assert_eq!(
+    vec![
+        Token::SeqBegin(None),
+        Token::I32(1),
+        Token::I32(2),
+        Token::I32(3),
+        Token::I32(4),
+        Token::SeqEnd,
+    ],
+    test::tokens(&Flatten::new((1, (2, 3), 4))),
+);
+# }
+# }
+```
+
+The flattened sequence doesn't have a known length. A value
+that isn't a sequence is streamed unchanged.
+*/
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Flatten<V>(V);
+
+impl<V> Flatten<V>
+where
+    V: Value,
+{
+    /**
+    Wrap a sequence to flatten.
+    */
+    pub fn new(value: V) -> Self {
+        Flatten(value)
+    }
+
+    /**
+    Get the inner value.
+    */
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+impl<V> Value for Flatten<V>
+where
+    V: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        self.0.stream(value::Stream::new(&mut FlattenStream::new(
+            stream.borrowed(),
+        )))
+    }
+}
+
+struct FlattenStream<S> {
+    inner: S,
+    // The number of maps and sequences that have begun
+    depth: usize,
+    // Whether the flattened value is a sequence
+    is_seq: bool,
+    // An element of the flattened sequence has begun but hasn't been forwarded yet
+    pending_elem: bool,
+    // An element of the flattened sequence is being inlined
+    is_inlining: bool,
+    // The last element of the flattened sequence was inlined
+    is_inlined: bool,
+}
+
+enum Begin {
+    Root,
+    Inline,
+    Forward,
+}
+
+impl<S> FlattenStream<S> {
+    fn new(inner: S) -> Self {
+        FlattenStream {
+            inner,
+            depth: 0,
+            is_seq: false,
+            pending_elem: false,
+            is_inlining: false,
+            is_inlined: false,
+        }
+    }
+}
+
+impl<'v, S> FlattenStream<S>
+where
+    S: Stream<'v>,
+{
+    /**
+    Forward a pending sequence element before the item that follows it.
+    */
+    fn item(&mut self) -> stream::Result {
+        if self.pending_elem {
+            self.pending_elem = false;
+            self.inner.seq_elem()?;
+        }
+
+        Ok(())
+    }
+
+    fn begin(&mut self, is_seq: bool) -> Result<Begin, crate::Error> {
+        let begin = match self.depth {
+            0 => {
+                self.is_seq = is_seq;
+                Begin::Root
+            }
+            1 if self.is_seq && self.pending_elem && is_seq => {
+                self.pending_elem = false;
+                self.is_inlining = true;
+                Begin::Inline
+            }
+            _ => {
+                self.item()?;
+                Begin::Forward
+            }
+        };
+
+        self.depth += 1;
+
+        Ok(begin)
+    }
+
+    fn end(&mut self) -> bool {
+        self.depth = self.depth.saturating_sub(1);
+
+        if self.depth == 1 && self.is_inlining {
+            self.is_inlining = false;
+            self.is_inlined = true;
+            false
+        } else {
+            true
+        }
+    }
+
+    fn seq_elem_begin(&mut self) -> bool {
+        if self.depth == 1 && self.is_seq {
+            self.pending_elem = true;
+            self.is_inlined = false;
+            false
+        } else {
+            true
+        }
+    }
+
+    fn seq_elem_end(&mut self) -> bool {
+        if self.depth == 1 && self.is_seq && (self.pending_elem || self.is_inlined) {
+            // The element was inlined, or never forwarded
+            self.pending_elem = false;
+            self.is_inlined = false;
+            false
+        } else {
+            true
+        }
+    }
+}
+
+impl<'v, S> Stream<'v> for FlattenStream<S>
+where
+    S: Stream<'v>,
+{
+    fn hint_skip(&self) -> bool {
+        // Values are always needed to track the depth of the stream
+        false
+    }
+
+    fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
+        self.item()?;
+        self.inner.fmt(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: stream::Arguments<'v>) -> stream::Result {
+        self.item()?;
+        self.inner.fmt_borrowed(v)
+    }
+
+    fn error(&mut self, v: stream::Source) -> stream::Result {
+        self.item()?;
+        self.inner.error(v)
+    }
+
+    fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
+        self.item()?;
+        self.inner.error_borrowed(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.item()?;
+        self.inner.error_str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> stream::Result {
+        self.item()?;
+        self.inner.i64(v)
+    }
+
+    fn u64(&mut self, v: u64) -> stream::Result {
+        self.item()?;
+        self.inner.u64(v)
+    }
+
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.item()?;
+        self.inner.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.item()?;
+        self.inner.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.item()?;
+        self.inner.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.item()?;
+        self.inner.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.item()?;
+        self.inner.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.item()?;
+        self.inner.i32(v)
+    }
+
+    fn i128(&mut self, v: i128) -> stream::Result {
+        self.item()?;
+        self.inner.i128(v)
+    }
+
+    fn u128(&mut self, v: u128) -> stream::Result {
+        self.item()?;
+        self.inner.u128(v)
+    }
+
+    fn f64(&mut self, v: f64) -> stream::Result {
+        self.item()?;
+        self.inner.f64(v)
+    }
+
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.item()?;
+        self.inner.f32(v)
+    }
+
+    fn bool(&mut self, v: bool) -> stream::Result {
+        self.item()?;
+        self.inner.bool(v)
+    }
+
+    fn char(&mut self, v: char) -> stream::Result {
+        self.item()?;
+        self.inner.char(v)
+    }
+
+    fn str(&mut self, v: &str) -> stream::Result {
+        self.item()?;
+        self.inner.str(v)
+    }
+
+    fn typed_str(&mut self, type_name: &str, v: &str) -> stream::Result {
+        self.item()?;
+        self.inner.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.item()?;
+        self.inner.str_borrowed(v)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.item()?;
+        self.inner.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.item()?;
+        self.inner.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.item()?;
+        self.inner.bytes_borrowed(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        self.item()?;
+        self.inner.none()
+    }
+
+    fn map_begin(&mut self, len: Option<usize>) -> stream::Result {
+        match self.begin(false)? {
+            Begin::Inline => Ok(()),
+            Begin::Root | Begin::Forward => self.inner.map_begin(len),
+        }
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        match self.begin(false)? {
+            Begin::Inline => Ok(()),
+            Begin::Root | Begin::Forward => self.inner.map_begin_unknown(),
+        }
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        self.inner.map_key()
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.map_key()?;
+        k.stream_owned(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        self.inner.map_value()
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.map_value()?;
+        v.stream_owned(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_key_collect_borrowed(k)?;
+        self.map_value_collect_borrowed(v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        if self.end() {
+            self.inner.map_end()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_begin(&mut self, len: Option<usize>) -> stream::Result {
+        match self.begin(true)? {
+            Begin::Root => self.inner.seq_begin_unknown(),
+            Begin::Inline => Ok(()),
+            Begin::Forward => self.inner.seq_begin(len),
+        }
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        match self.begin(true)? {
+            Begin::Inline => Ok(()),
+            Begin::Root | Begin::Forward => self.inner.seq_begin_unknown(),
+        }
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        if self.seq_elem_begin() {
+            self.inner.seq_elem()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        if FlattenStream::seq_elem_end(self) {
+            self.inner.seq_elem_end()
+        } else {
+            Ok(())
+        }
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.seq_elem()?;
+        v.stream_owned(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        if self.end() {
+            self.inner.seq_end()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            collections::BTreeMap,
+            vec,
+            vec::Vec,
+        },
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_nested_seq() {
+        assert_eq!(
+            vec![
+                Token::SeqBegin(None),
+                Token::I32(1),
+                Token::I32(2),
+                Token::I32(3),
+                Token::I32(4),
+                Token::SeqEnd,
+            ],
+            test::tokens(&Flatten::new((1, vec![2, 3], 4)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_empty_seq() {
+        assert_eq!(
+            vec![
+                Token::SeqBegin(None),
+                Token::I32(1),
+                Token::I32(4),
+                Token::SeqEnd,
+            ],
+            test::tokens(&Flatten::new((1, Vec::<i32>::new(), 4)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_only_one_level() {
+        assert_eq!(
+            vec![
+                Token::SeqBegin(None),
+                Token::SeqBegin(Some(1)),
+                Token::I32(1),
+                Token::SeqEnd,
+                Token::SeqBegin(Some(2)),
+                Token::I32(2),
+                Token::I32(3),
+                Token::SeqEnd,
+                Token::SeqEnd,
+            ],
+            test::tokens(&Flatten::new(vec![vec![vec![1]], vec![vec![2, 3]]]))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_keeps_maps() {
+        let mut map = BTreeMap::new();
+        map.insert("a", vec![1]);
+
+        assert_eq!(
+            vec![
+                Token::SeqBegin(None),
+                Token::MapBegin(Some(1)),
+                Token::Str("a".into()),
+                Token::SeqBegin(Some(1)),
+                Token::I32(1),
+                Token::SeqEnd,
+                Token::MapEnd,
+                Token::I32(2),
+                Token::SeqEnd,
+            ],
+            test::tokens(&Flatten::new((map, 2)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_non_seq() {
+        assert_eq!(test::tokens(&1), test::tokens(&Flatten::new(1)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn flatten_seq_elem_end() {
+        struct Bracketed;
+
+        impl Value for Bracketed {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(Some(2))?;
+
+                stream.seq_elem_begin()?.seq_begin(Some(1))?;
+                stream.seq_elem_begin()?.i32(1)?;
+                stream.seq_elem_end()?;
+                stream.seq_end()?;
+                stream.seq_elem_end()?;
+
+                stream.seq_elem_begin()?.i32(2)?;
+                stream.seq_elem_end()?;
+
+                stream.seq_end()
+            }
+        }
+
+        // Ending the inlined element doesn't end one of its elements a second time
+        let mut validate = stream::validate(crate::buffer::Buffer::new());
+        crate::stream(&mut validate, &Flatten::new(Bracketed)).unwrap();
+        validate.end().unwrap();
+
+        assert_eq!(
+            vec![
+                Token::SeqBegin(None),
+                Token::I32(1),
+                Token::I32(2),
+                Token::SeqEnd,
+            ],
+            test::tokens(&validate.into_inner())
+        );
+    }
+}
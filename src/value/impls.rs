@@ -1,5 +1,32 @@
 use crate::{
-    std::fmt,
+    std::{
+        cell::{
+            Cell,
+            RefCell,
+        },
+        fmt,
+        marker::PhantomData,
+        num::{
+            NonZeroI128,
+            NonZeroI16,
+            NonZeroI32,
+            NonZeroI64,
+            NonZeroI8,
+            NonZeroIsize,
+            NonZeroU128,
+            NonZeroU16,
+            NonZeroU32,
+            NonZeroU64,
+            NonZeroU8,
+            NonZeroUsize,
+            Saturating,
+            Wrapping,
+        },
+        ops::{
+            Range,
+            RangeInclusive,
+        },
+    },
     stream,
     value::{
         self,
@@ -13,6 +40,12 @@ impl Value for () {
     }
 }
 
+impl<T: ?Sized> Value for PhantomData<T> {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.none()
+    }
+}
+
 impl<T> Value for Option<T>
 where
     T: Value,
@@ -40,36 +73,57 @@ where
     }
 }
 
-impl<T, U> Value for (T, U)
-where
-    T: Value,
-    U: Value,
-{
-    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.seq_begin(Some(2))?;
+macro_rules! impl_tuple {
+    ($(($len:expr, $($i:tt: $T:ident),+)),* $(,)?) => {
+        $(
+            impl<$($T),+> Value for ($($T,)+)
+            where
+                $($T: Value,)+
+            {
+                fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                    stream.seq_begin(Some($len))?;
 
-        stream.seq_elem(&self.0)?;
-        stream.seq_elem(&self.1)?;
+                    $(
+                        stream.seq_elem(&self.$i)?;
+                    )+
 
-        stream.seq_end()
-    }
+                    stream.seq_end()
+                }
+            }
+        )*
+    };
 }
 
+impl_tuple![
+    (1, 0: T0),
+    (2, 0: T0, 1: T1),
+    (3, 0: T0, 1: T1, 2: T2),
+    (4, 0: T0, 1: T1, 2: T2, 3: T3),
+    (5, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4),
+    (6, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4, 5: T5),
+    (7, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4, 5: T5, 6: T6),
+    (8, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4, 5: T5, 6: T6, 7: T7),
+    (9, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4, 5: T5, 6: T6, 7: T7, 8: T8),
+    (10, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4, 5: T5, 6: T6, 7: T7, 8: T8, 9: T9),
+    (11, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4, 5: T5, 6: T6, 7: T7, 8: T8, 9: T9, 10: T10),
+    (12, 0: T0, 1: T1, 2: T2, 3: T3, 4: T4, 5: T5, 6: T6, 7: T7, 8: T8, 9: T9, 10: T10, 11: T11),
+];
+
 impl Value for u8 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.u64(u64::from(*self))
+        stream.u8(*self)
     }
 }
 
 impl Value for u16 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.u64(u64::from(*self))
+        stream.u16(*self)
     }
 }
 
 impl Value for u32 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.u64(u64::from(*self))
+        stream.u32(*self)
     }
 }
 
@@ -79,21 +133,40 @@ impl Value for u64 {
     }
 }
 
+/**
+A `usize` is streamed as a 64-bit integer.
+
+On 64-bit targets it's the same as a `u64`. On smaller targets it's widened to one.
+*/
+impl Value for usize {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        #[cfg(target_pointer_width = "64")]
+        {
+            stream.u64(*self as u64)
+        }
+
+        #[cfg(not(target_pointer_width = "64"))]
+        {
+            stream.u64(u64::from(*self as u32))
+        }
+    }
+}
+
 impl Value for i8 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.i64(i64::from(*self))
+        stream.i8(*self)
     }
 }
 
 impl Value for i16 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.i64(i64::from(*self))
+        stream.i16(*self)
     }
 }
 
 impl Value for i32 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.i64(i64::from(*self))
+        stream.i32(*self)
     }
 }
 
@@ -103,6 +176,25 @@ impl Value for i64 {
     }
 }
 
+/**
+An `isize` is streamed as a 64-bit integer.
+
+On 64-bit targets it's the same as an `i64`. On smaller targets it's widened to one.
+*/
+impl Value for isize {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        #[cfg(target_pointer_width = "64")]
+        {
+            stream.i64(*self as i64)
+        }
+
+        #[cfg(not(target_pointer_width = "64"))]
+        {
+            stream.i64(i64::from(*self as i32))
+        }
+    }
+}
+
 impl Value for u128 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
         stream.u128(*self)
@@ -115,9 +207,81 @@ impl Value for i128 {
     }
 }
 
+impl Value for NonZeroU8 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.u8(self.get())
+    }
+}
+
+impl Value for NonZeroU16 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.u16(self.get())
+    }
+}
+
+impl Value for NonZeroU32 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.u32(self.get())
+    }
+}
+
+impl Value for NonZeroU64 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.u64(self.get())
+    }
+}
+
+impl Value for NonZeroUsize {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.owned().any(&self.get())
+    }
+}
+
+impl Value for NonZeroI8 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.i8(self.get())
+    }
+}
+
+impl Value for NonZeroI16 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.i16(self.get())
+    }
+}
+
+impl Value for NonZeroI32 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.i32(self.get())
+    }
+}
+
+impl Value for NonZeroI64 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.i64(self.get())
+    }
+}
+
+impl Value for NonZeroIsize {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.owned().any(&self.get())
+    }
+}
+
+impl Value for NonZeroU128 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.u128(self.get())
+    }
+}
+
+impl Value for NonZeroI128 {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.i128(self.get())
+    }
+}
+
 impl Value for f32 {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
-        stream.f64(f64::from(*self))
+        stream.f32(*self)
     }
 }
 
@@ -157,6 +321,82 @@ impl<'a> Value for stream::Arguments<'a> {
     }
 }
 
+impl<T> Value for Range<T>
+where
+    T: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.map_begin(Some(2))?;
+
+        stream.map_key(&"start")?;
+        stream.map_value(&self.start)?;
+
+        stream.map_key(&"end")?;
+        stream.map_value(&self.end)?;
+
+        stream.map_end()
+    }
+}
+
+impl<T> Value for RangeInclusive<T>
+where
+    T: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.map_begin(Some(2))?;
+
+        stream.map_key(&"start")?;
+        stream.map_value(self.start())?;
+
+        stream.map_key(&"end_inclusive")?;
+        stream.map_value(self.end())?;
+
+        stream.map_end()
+    }
+}
+
+impl<T> Value for Cell<T>
+where
+    T: Copy + Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.owned().any(&self.get())
+    }
+}
+
+/**
+A `RefCell` that's already mutably borrowed is streamed as empty.
+*/
+impl<T: ?Sized> Value for RefCell<T>
+where
+    T: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        match self.try_borrow() {
+            Ok(v) => stream.owned().any(&*v),
+            Err(_) => stream.none(),
+        }
+    }
+}
+
+impl<T> Value for Wrapping<T>
+where
+    T: Value,
+{
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        self.0.stream(stream)
+    }
+}
+
+impl<T> Value for Saturating<T>
+where
+    T: Value,
+{
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        self.0.stream(stream)
+    }
+}
+
 impl<'a> Value for stream::Source<'a> {
     fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
         #[cfg(feature = "std")]
@@ -176,8 +416,15 @@ mod alloc_support {
     use super::*;
 
     use crate::std::{
+        borrow::Cow,
         boxed::Box,
-        collections::BTreeMap,
+        collections::{
+            BTreeMap,
+            BTreeSet,
+            BinaryHeap,
+            LinkedList,
+            VecDeque,
+        },
         rc::Rc,
         string::String,
         vec::Vec,
@@ -192,6 +439,31 @@ mod alloc_support {
         }
     }
 
+    impl<'a, T> Value for Cow<'a, T>
+    where
+        T: Clone + Value,
+    {
+        fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+            (**self).stream(stream)
+        }
+    }
+
+    impl<'a> Value for Cow<'a, str> {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.str(self)
+        }
+    }
+
+    /**
+    Borrowed or owned bytes are streamed as bytes rather than
+    as a sequence of integers.
+    */
+    impl<'a> Value for Cow<'a, [u8]> {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.bytes(self)
+        }
+    }
+
     impl<T: ?Sized> Value for Rc<T>
     where
         T: Value,
@@ -216,6 +488,36 @@ mod alloc_support {
         }
     }
 
+    impl<T> Value for VecDeque<T>
+    where
+        T: Value,
+    {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.seq_begin(Some(self.len()))?;
+
+            for v in self {
+                stream.seq_elem(v)?;
+            }
+
+            stream.seq_end()
+        }
+    }
+
+    impl<T> Value for LinkedList<T>
+    where
+        T: Value,
+    {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.seq_begin(Some(self.len()))?;
+
+            for v in self {
+                stream.seq_elem(v)?;
+            }
+
+            stream.seq_end()
+        }
+    }
+
     impl<K, V> Value for BTreeMap<K, V>
     where
         K: Eq + Value,
@@ -225,13 +527,48 @@ mod alloc_support {
             stream.map_begin(Some(self.len()))?;
 
             for (k, v) in self {
-                stream.map_key(k)?;
-                stream.map_value(v)?;
+                stream.map_flat_entry(k, v)?;
             }
 
             stream.map_end()
         }
     }
+
+    impl<T> Value for BTreeSet<T>
+    where
+        T: Eq + Value,
+    {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.seq_begin(Some(self.len()))?;
+
+            for v in self {
+                stream.seq_elem(v)?;
+            }
+
+            stream.seq_end()
+        }
+    }
+
+    /**
+    Binary heaps are streamed in their internal order, which is
+    arbitrary. The elements of a heap aren't guaranteed to be streamed
+    in the same order they'd be popped in, or even the same order
+    for heaps with the same elements.
+    */
+    impl<T> Value for BinaryHeap<T>
+    where
+        T: Ord + Value,
+    {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.seq_begin(Some(self.len()))?;
+
+            for v in self.iter() {
+                stream.seq_elem(v)?;
+            }
+
+            stream.seq_end()
+        }
+    }
 }
 
 #[cfg(feature = "std")]
@@ -239,13 +576,40 @@ mod std_support {
     use super::*;
 
     use crate::std::{
-        collections::HashMap,
+        borrow::Cow,
+        collections::{
+            HashMap,
+            HashSet,
+        },
+        convert::TryFrom,
         error,
         hash::{
             BuildHasher,
             Hash,
         },
-        sync::Arc,
+        io,
+        net::{
+            IpAddr,
+            Ipv4Addr,
+            Ipv6Addr,
+            SocketAddr,
+            SocketAddrV4,
+            SocketAddrV6,
+        },
+        path::{
+            Path,
+            PathBuf,
+        },
+        sync::{
+            Arc,
+            Mutex,
+            RwLock,
+        },
+        time::{
+            Duration,
+            SystemTime,
+            UNIX_EPOCH,
+        },
     };
 
     impl Value for dyn error::Error + 'static {
@@ -254,6 +618,34 @@ mod std_support {
         }
     }
 
+    /**
+    IO errors are streamed as structured maps rather than opaque errors.
+    The map has a `kind`, like `"NotFound"`, an `os_code` that's empty
+    if the error didn't come from the OS, and a `message` with the
+    error's description, including any custom error it wraps.
+    To stream an IO error as an error instead, stream it as a
+    `dyn Error` or use `stream::Source`.
+    */
+    impl Value for io::Error {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.map_begin(Some(3))?;
+
+            stream.map_key_str("kind")?;
+            stream.map_value_begin()?.owned().debug(&self.kind())?;
+
+            stream.map_key_str("os_code")?;
+            match self.raw_os_error() {
+                Some(code) => stream.map_value_begin()?.i32(code)?,
+                None => stream.map_value_begin()?.none()?,
+            }
+
+            stream.map_key_str("message")?;
+            stream.map_value_begin()?.display(self)?;
+
+            stream.map_end()
+        }
+    }
+
     impl<T: ?Sized> Value for Arc<T>
     where
         T: Value,
@@ -263,6 +655,46 @@ mod std_support {
         }
     }
 
+    /**
+    Mutexes are locked while their inner value is streamed.
+    A poisoned mutex produces an error. Streaming a mutex
+    that's already locked by the current thread will deadlock.
+    */
+    impl<T: ?Sized> Value for Mutex<T>
+    where
+        T: Value,
+    {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            let guard = self
+                .lock()
+                .map_err(|_| crate::Error::msg("attempt to stream a poisoned mutex"))?;
+
+            stream.owned().any(&*guard)
+        }
+    }
+
+    /**
+    Locks are read while their inner value is streamed.
+    A poisoned lock produces an error. Streaming a lock
+    that's already write-locked by the current thread will deadlock.
+    */
+    impl<T: ?Sized> Value for RwLock<T>
+    where
+        T: Value,
+    {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            let guard = self
+                .read()
+                .map_err(|_| crate::Error::msg("attempt to stream a poisoned lock"))?;
+
+            stream.owned().any(&*guard)
+        }
+    }
+
+    /**
+    Hash maps stream their entries in iteration order, which isn't
+    consistent between maps. Use `Sorted` to stream them in key order.
+    */
     impl<K, V, H> Value for HashMap<K, V, H>
     where
         K: Hash + Eq + Value,
@@ -273,68 +705,372 @@ mod std_support {
             stream.map_begin(Some(self.len()))?;
 
             for (k, v) in self {
-                stream.map_key(k)?;
-                stream.map_value(v)?;
+                stream.map_flat_entry(k, v)?;
             }
 
             stream.map_end()
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    #[cfg(feature = "alloc")]
-    mod alloc_support {
-        use crate::{
-            std::{
-                boxed::Box,
-                collections::BTreeMap,
-                rc::Rc,
-                string::String,
-                vec::Vec,
-            },
-            test::{
-                self,
-                Token,
-            },
-        };
-
-        #[cfg(target_arch = "wasm32")]
-        use wasm_bindgen_test::*;
 
-        #[test]
-        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
-        fn stream_unsigned() {
-            assert_eq!(vec![Token::Unsigned(1)], test::tokens(&1u8));
-
-            assert_eq!(vec![Token::Unsigned(1)], test::tokens(&1u16));
+    impl<T, H> Value for HashSet<T, H>
+    where
+        T: Hash + Eq + Value,
+        H: BuildHasher,
+    {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.seq_begin(Some(self.len()))?;
 
-            assert_eq!(vec![Token::Unsigned(1)], test::tokens(&1u32));
+            for v in self {
+                stream.seq_elem(v)?;
+            }
 
-            assert_eq!(vec![Token::Unsigned(1)], test::tokens(&1u64));
+            stream.seq_end()
+        }
+    }
 
-            assert_eq!(vec![Token::BigUnsigned(1)], test::tokens(&1u128));
+    /**
+    Network addresses are streamed using their textual representation
+    rather than as sequences of octets. That's the format consumers are
+    most likely to expect, and it's unambiguous between IPv4 and IPv6.
+    */
+    impl Value for IpAddr {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.display(self)
         }
+    }
 
-        #[test]
+    impl Value for Ipv4Addr {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.display(self)
+        }
+    }
+
+    impl Value for Ipv6Addr {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.display(self)
+        }
+    }
+
+    impl Value for SocketAddr {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.display(self)
+        }
+    }
+
+    impl Value for SocketAddrV4 {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.display(self)
+        }
+    }
+
+    impl Value for SocketAddrV6 {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.display(self)
+        }
+    }
+
+    /**
+    Paths are streamed as strings. Paths that aren't valid UTF-8
+    will have any invalid sequences replaced with U+FFFD.
+    */
+    impl Value for Path {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            match self.to_str() {
+                Some(path) => stream.str(path),
+                None => stream.str_owned(self.to_string_lossy().into_owned()),
+            }
+        }
+    }
+
+    impl<'a> Value for Cow<'a, Path> {
+        fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+            (**self).stream(stream)
+        }
+    }
+
+    impl Value for PathBuf {
+        fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+            self.as_path().stream(stream)
+        }
+    }
+
+    impl Value for Duration {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.map_begin(Some(2))?;
+
+            stream.map_key(&"secs")?;
+            stream.map_value_begin()?.u64(self.as_secs())?;
+
+            stream.map_key(&"nanos")?;
+            stream
+                .map_value_begin()?
+                .u64(u64::from(self.subsec_nanos()))?;
+
+            stream.map_end()
+        }
+    }
+
+    /**
+    System times are streamed as the number of seconds and nanoseconds
+    since the Unix epoch. Times before the epoch have negative seconds,
+    with nanoseconds counting forwards from them, so a time half a second
+    before the epoch has `elapsed_secs` of `-1` and `nanos` of `500000000`.
+    Seconds that don't fit in an `i64` are streamed as an `i128`.
+    */
+    impl Value for SystemTime {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            let (secs, nanos) = match self.duration_since(UNIX_EPOCH) {
+                Ok(elapsed) => (i128::from(elapsed.as_secs()), elapsed.subsec_nanos()),
+                Err(err) => {
+                    let before = err.duration();
+                    let secs = -i128::from(before.as_secs());
+
+                    match before.subsec_nanos() {
+                        0 => (secs, 0),
+                        nanos => (secs - 1, 1_000_000_000 - nanos),
+                    }
+                }
+            };
+
+            stream.map_begin(Some(2))?;
+
+            stream.map_key(&"elapsed_secs")?;
+            match i64::try_from(secs) {
+                Ok(secs) => stream.map_value_begin()?.i64(secs)?,
+                Err(_) => stream.map_value_begin()?.i128(secs)?,
+            }
+
+            stream.map_key(&"nanos")?;
+            stream.map_value_begin()?.u64(u64::from(nanos))?;
+
+            stream.map_end()
+        }
+    }
+}
+
+#[cfg(feature = "serde_json_lib")]
+mod serde_json_support {
+    use super::*;
+
+    use serde_json_lib::{
+        Number,
+        Value as JsonValue,
+    };
+
+    impl Value for JsonValue {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            match self {
+                JsonValue::Null => stream.none(),
+                JsonValue::Bool(v) => stream.bool(*v),
+                JsonValue::Number(v) => v.stream(stream),
+                JsonValue::String(v) => stream.str(v),
+                JsonValue::Array(v) => v.stream(stream),
+                JsonValue::Object(v) => {
+                    stream.map_begin(Some(v.len()))?;
+
+                    for (k, v) in v {
+                        stream.map_key(k)?;
+                        stream.map_value(v)?;
+                    }
+
+                    stream.map_end()
+                }
+            }
+        }
+    }
+
+    impl Value for Number {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            // Numbers are streamed using the narrowest type that can represent them
+            if let Some(v) = self.as_u64() {
+                stream.u64(v)
+            } else if let Some(v) = self.as_i64() {
+                stream.i64(v)
+            } else if let Some(v) = self.as_f64() {
+                stream.f64(v)
+            } else {
+                stream.display(self)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    mod alloc_support {
+        use crate::{
+            std::{
+                borrow::Cow,
+                boxed::Box,
+                cell::{
+                    Cell,
+                    RefCell,
+                },
+                collections::{
+                    BTreeMap,
+                    BTreeSet,
+                    BinaryHeap,
+                    LinkedList,
+                    VecDeque,
+                },
+                fmt,
+                marker::PhantomData,
+                num::{
+                    NonZeroI128,
+                    NonZeroI16,
+                    NonZeroI32,
+                    NonZeroI64,
+                    NonZeroI8,
+                    NonZeroIsize,
+                    NonZeroU128,
+                    NonZeroU16,
+                    NonZeroU32,
+                    NonZeroU64,
+                    NonZeroU8,
+                    NonZeroUsize,
+                    Saturating,
+                    Wrapping,
+                },
+                rc::Rc,
+                string::String,
+                vec::Vec,
+            },
+            test::{
+                self,
+                Token,
+            },
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_unsigned() {
+            assert_eq!(vec![Token::U8(1)], test::tokens(&1u8));
+
+            assert_eq!(vec![Token::U16(1)], test::tokens(&1u16));
+
+            assert_eq!(vec![Token::U32(1)], test::tokens(&1u32));
+
+            assert_eq!(vec![Token::Unsigned(1)], test::tokens(&1u64));
+
+            assert_eq!(vec![Token::BigUnsigned(1)], test::tokens(&1u128));
+
+            assert_eq!(vec![Token::Unsigned(1)], test::tokens(&1usize));
+        }
+
+        #[test]
         #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
         fn stream_signed() {
-            assert_eq!(vec![Token::Signed(1)], test::tokens(&1i8));
+            assert_eq!(vec![Token::I8(1)], test::tokens(&1i8));
 
-            assert_eq!(vec![Token::Signed(1)], test::tokens(&1i16));
+            assert_eq!(vec![Token::I16(1)], test::tokens(&1i16));
 
-            assert_eq!(vec![Token::Signed(1)], test::tokens(&1i32));
+            assert_eq!(vec![Token::I32(1)], test::tokens(&1i32));
 
             assert_eq!(vec![Token::Signed(1)], test::tokens(&1i64));
 
             assert_eq!(vec![Token::BigSigned(1)], test::tokens(&1i128));
+
+            assert_eq!(vec![Token::Signed(1)], test::tokens(&1isize));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_pointer_width() {
+            #[cfg(target_pointer_width = "32")]
+            {
+                assert_eq!(
+                    vec![Token::Unsigned(u32::MAX as u64)],
+                    test::tokens(&usize::MAX)
+                );
+                assert_eq!(
+                    vec![Token::Signed(i32::MIN as i64)],
+                    test::tokens(&isize::MIN)
+                );
+            }
+
+            #[cfg(target_pointer_width = "64")]
+            {
+                assert_eq!(vec![Token::Unsigned(u64::MAX)], test::tokens(&usize::MAX));
+                assert_eq!(vec![Token::Signed(i64::MIN)], test::tokens(&isize::MIN));
+            }
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_non_zero_unsigned() {
+            assert_eq!(
+                vec![Token::U8(1)],
+                test::tokens(&NonZeroU8::new(1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::U16(1)],
+                test::tokens(&NonZeroU16::new(1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::U32(1)],
+                test::tokens(&NonZeroU32::new(1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::Unsigned(1)],
+                test::tokens(&NonZeroU64::new(1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::BigUnsigned(1)],
+                test::tokens(&NonZeroU128::new(1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::Unsigned(1)],
+                test::tokens(&NonZeroUsize::new(1).unwrap())
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_non_zero_signed() {
+            assert_eq!(
+                vec![Token::I8(-1)],
+                test::tokens(&NonZeroI8::new(-1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::I16(-1)],
+                test::tokens(&NonZeroI16::new(-1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::I32(-1)],
+                test::tokens(&NonZeroI32::new(-1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::Signed(-1)],
+                test::tokens(&NonZeroI64::new(-1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::BigSigned(-1)],
+                test::tokens(&NonZeroI128::new(-1).unwrap())
+            );
+
+            assert_eq!(
+                vec![Token::Signed(-1)],
+                test::tokens(&NonZeroIsize::new(-1).unwrap())
+            );
         }
 
         #[test]
         #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
         fn stream_float() {
-            assert_eq!(vec![Token::Float(1.0)], test::tokens(&1f32));
+            assert_eq!(vec![Token::Float32(1.0)], test::tokens(&1f32));
 
             assert_eq!(vec![Token::Float(1.0)], test::tokens(&1f64));
         }
@@ -371,7 +1107,7 @@ mod tests {
         fn stream_option() {
             assert_eq!(vec![Token::None], test::tokens(&Option::None::<i32>));
 
-            assert_eq!(vec![Token::Signed(1)], test::tokens(&Some(1)));
+            assert_eq!(vec![Token::I32(1)], test::tokens(&Some(1)));
         }
 
         #[test]
@@ -384,9 +1120,9 @@ mod tests {
             assert_eq!(
                 vec![
                     Token::SeqBegin(Some(3)),
-                    Token::Signed(1),
-                    Token::Signed(2),
-                    Token::Signed(3),
+                    Token::I32(1),
+                    Token::I32(2),
+                    Token::I32(3),
                     Token::SeqEnd,
                 ],
                 v
@@ -399,9 +1135,193 @@ mod tests {
             assert_eq!(
                 vec![
                     Token::SeqBegin(Some(3)),
-                    Token::Signed(1),
-                    Token::Signed(2),
-                    Token::Signed(3),
+                    Token::I32(1),
+                    Token::I32(2),
+                    Token::I32(3),
+                    Token::SeqEnd,
+                ],
+                v
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_range() {
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(2)),
+                    Token::Str("start".into()),
+                    Token::I32(0),
+                    Token::Str("end".into()),
+                    Token::I32(5),
+                    Token::MapEnd,
+                ],
+                test::tokens(&(0..5))
+            );
+
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(2)),
+                    Token::Str("start".into()),
+                    Token::I32(0),
+                    Token::Str("end_inclusive".into()),
+                    Token::I32(5),
+                    Token::MapEnd,
+                ],
+                test::tokens(&(0..=5))
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_cow() {
+            let borrowed: Cow<str> = Cow::Borrowed("a string");
+            let owned: Cow<str> = Cow::Owned(String::from("a string"));
+
+            assert_eq!(test::tokens("a string"), test::tokens(&borrowed));
+            assert_eq!(test::tokens(&borrowed), test::tokens(&owned));
+
+            let borrowed: Cow<[u8]> = Cow::Borrowed(&[1, 2]);
+            let owned: Cow<[u8]> = Cow::Owned(vec![1, 2]);
+
+            assert_eq!(vec![Token::Bytes(vec![1, 2])], test::tokens(&borrowed));
+            assert_eq!(test::tokens(&borrowed), test::tokens(&owned));
+
+            let borrowed: Cow<i32> = Cow::Borrowed(&1);
+            let owned: Cow<i32> = Cow::Owned(1);
+
+            assert_eq!(test::tokens(&1), test::tokens(&borrowed));
+            assert_eq!(test::tokens(&borrowed), test::tokens(&owned));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_phantom_data() {
+            assert_eq!(
+                vec![Token::None],
+                test::tokens(&PhantomData::<dyn fmt::Debug>)
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_num_wrappers() {
+            assert_eq!(vec![Token::U8(42)], test::tokens(&Wrapping(42u8)));
+            assert_eq!(vec![Token::Signed(-1)], test::tokens(&Wrapping(-1i64)));
+            assert_eq!(vec![Token::U8(42)], test::tokens(&Saturating(42u8)));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_cell() {
+            assert_eq!(vec![Token::I32(1)], test::tokens(&Cell::new(1)));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_ref_cell() {
+            let v = RefCell::new(vec![1]);
+
+            assert_eq!(
+                vec![Token::SeqBegin(Some(1)), Token::I32(1), Token::SeqEnd],
+                test::tokens(&v)
+            );
+
+            let _borrowed = v.borrow_mut();
+
+            assert_eq!(vec![Token::None], test::tokens(&v));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_tuple() {
+            assert_eq!(vec![Token::None], test::tokens(&()));
+
+            let v = test::tokens(&(1,));
+            assert_eq!(
+                vec![Token::SeqBegin(Some(1)), Token::I32(1), Token::SeqEnd],
+                v
+            );
+
+            let v = test::tokens(&(1, "a"));
+            assert_eq!(
+                vec![
+                    Token::SeqBegin(Some(2)),
+                    Token::I32(1),
+                    Token::Str("a".into()),
+                    Token::SeqEnd,
+                ],
+                v
+            );
+
+            let v = test::tokens(&(1, "a", true));
+            assert_eq!(
+                vec![
+                    Token::SeqBegin(Some(3)),
+                    Token::I32(1),
+                    Token::Str("a".into()),
+                    Token::Bool(true),
+                    Token::SeqEnd,
+                ],
+                v
+            );
+
+            let v = test::tokens(&(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12));
+            assert_eq!(
+                {
+                    let mut expected = vec![Token::SeqBegin(Some(12))];
+                    expected.extend((1..=12).map(Token::I32));
+                    expected.push(Token::SeqEnd);
+                    expected
+                },
+                v
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_vec_deque() {
+            let v = test::tokens(&VecDeque::<i32>::new());
+            assert_eq!(vec![Token::SeqBegin(Some(0)), Token::SeqEnd], v);
+
+            let v = test::tokens(&{
+                let mut deque = VecDeque::new();
+                deque.push_back(2);
+                deque.push_back(3);
+                deque.push_front(1);
+                deque
+            });
+            assert_eq!(
+                vec![
+                    Token::SeqBegin(Some(3)),
+                    Token::I32(1),
+                    Token::I32(2),
+                    Token::I32(3),
+                    Token::SeqEnd,
+                ],
+                v
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_linked_list() {
+            let v = test::tokens(&LinkedList::<i32>::new());
+            assert_eq!(vec![Token::SeqBegin(Some(0)), Token::SeqEnd], v);
+
+            let v = test::tokens(&{
+                let mut list = LinkedList::new();
+                list.push_back(2);
+                list.push_back(3);
+                list.push_front(1);
+                list
+            });
+            assert_eq!(
+                vec![
+                    Token::SeqBegin(Some(3)),
+                    Token::I32(1),
+                    Token::I32(2),
+                    Token::I32(3),
                     Token::SeqEnd,
                 ],
                 v
@@ -423,26 +1343,77 @@ mod tests {
             assert_eq!(
                 vec![
                     Token::MapBegin(Some(2)),
-                    Token::Signed(1),
-                    Token::Signed(11),
-                    Token::Signed(2),
-                    Token::Signed(22),
+                    Token::I32(1),
+                    Token::I32(11),
+                    Token::I32(2),
+                    Token::I32(22),
                     Token::MapEnd,
                 ],
                 v
             );
         }
 
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_set() {
+            let v = test::tokens(&BTreeSet::<i32>::new());
+            assert_eq!(vec![Token::SeqBegin(Some(0)), Token::SeqEnd], v);
+
+            let v = test::tokens(&{
+                let mut set = BTreeSet::new();
+                set.insert(2);
+                set.insert(1);
+                set
+            });
+            assert_eq!(
+                vec![
+                    Token::SeqBegin(Some(2)),
+                    Token::I32(1),
+                    Token::I32(2),
+                    Token::SeqEnd,
+                ],
+                v
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_binary_heap() {
+            let v = test::tokens(&BinaryHeap::<i32>::new());
+            assert_eq!(vec![Token::SeqBegin(Some(0)), Token::SeqEnd], v);
+
+            let v = test::tokens(&{
+                let mut heap = BinaryHeap::new();
+                heap.push(1);
+                heap
+            });
+            assert_eq!(
+                vec![Token::SeqBegin(Some(1)), Token::I32(1), Token::SeqEnd],
+                v
+            );
+
+            // The order of elements isn't specified, only that they're all streamed
+            let mut v = test::tokens(&BinaryHeap::from(vec![2, 3, 1]));
+            assert_eq!(Some(Token::SeqEnd), v.pop());
+            assert_eq!(Token::SeqBegin(Some(3)), v.remove(0));
+
+            v.sort_by_key(|token| match token {
+                Token::I32(v) => *v,
+                _ => panic!("unexpected {:?}", token),
+            });
+            assert_eq!(vec![Token::I32(1), Token::I32(2), Token::I32(3)], v);
+        }
+
         #[test]
         #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
         fn stream_box() {
-            assert_eq!(vec![Token::Signed(1)], test::tokens(&Box::new(1i8)));
+            assert_eq!(vec![Token::I8(1)], test::tokens(&Box::new(1i8)));
         }
 
         #[test]
         #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
         fn stream_rc() {
-            assert_eq!(vec![Token::Signed(1)], test::tokens(&Rc::new(1i8)));
+            assert_eq!(vec![Token::I8(1)], test::tokens(&Rc::new(1i8)));
         }
     }
 
@@ -450,10 +1421,31 @@ mod tests {
     mod std_support {
         use crate::{
             std::{
-                collections::HashMap,
+                collections::{
+                    HashMap,
+                    HashSet,
+                },
                 error,
                 io,
-                sync::Arc,
+                net::{
+                    IpAddr,
+                    Ipv4Addr,
+                    Ipv6Addr,
+                    SocketAddr,
+                },
+                path::{
+                    Path,
+                    PathBuf,
+                },
+                sync::{
+                    Arc,
+                    Mutex,
+                    RwLock,
+                },
+                time::{
+                    Duration,
+                    UNIX_EPOCH,
+                },
             },
             stream::Source,
             test::{
@@ -498,6 +1490,30 @@ mod tests {
             );
         }
 
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_io_error() {
+            let err = io::Error::new(io::ErrorKind::NotFound, "missing file");
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(3)),
+                    Token::Str("kind".into()),
+                    Token::Str("NotFound".into()),
+                    Token::Str("os_code".into()),
+                    Token::None,
+                    Token::Str("message".into()),
+                    Token::Str("missing file".into()),
+                    Token::MapEnd,
+                ],
+                test::tokens(&err)
+            );
+
+            let err = io::Error::from_raw_os_error(2);
+            let v = test::tokens(&err);
+            assert_eq!(Token::I32(2), v[4]);
+            assert_eq!(Token::Str(format!("{}", err)), v[6]);
+        }
+
         #[test]
         #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
         fn stream_dyn_error() {
@@ -515,10 +1531,235 @@ mod tests {
             assert_eq!(vec![Token::MapBegin(Some(0)), Token::MapEnd], v);
         }
 
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_set() {
+            let v = test::tokens(&HashSet::<i32>::new());
+            assert_eq!(vec![Token::SeqBegin(Some(0)), Token::SeqEnd], v);
+
+            let v = test::tokens(&{
+                let mut set = HashSet::new();
+                set.insert(1);
+                set
+            });
+            assert_eq!(
+                vec![Token::SeqBegin(Some(1)), Token::I32(1), Token::SeqEnd],
+                v
+            );
+        }
+
         #[test]
         #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
         fn stream_rc() {
-            assert_eq!(vec![Token::Signed(1)], test::tokens(&Arc::new(1i8)));
+            assert_eq!(vec![Token::I8(1)], test::tokens(&Arc::new(1i8)));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_ip_addr() {
+            assert_eq!(
+                vec![Token::Str("127.0.0.1".into())],
+                test::tokens(&Ipv4Addr::LOCALHOST)
+            );
+
+            assert_eq!(
+                vec![Token::Str("::1".into())],
+                test::tokens(&Ipv6Addr::LOCALHOST)
+            );
+
+            assert_eq!(
+                vec![Token::Str("127.0.0.1".into())],
+                test::tokens(&IpAddr::from(Ipv4Addr::LOCALHOST))
+            );
+
+            assert_eq!(
+                vec![Token::Str("::1".into())],
+                test::tokens(&IpAddr::from(Ipv6Addr::LOCALHOST))
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_socket_addr() {
+            assert_eq!(
+                vec![Token::Str("127.0.0.1:8080".into())],
+                test::tokens(&SocketAddr::from((Ipv4Addr::LOCALHOST, 8080)))
+            );
+
+            assert_eq!(
+                vec![Token::Str("[::1]:8080".into())],
+                test::tokens(&SocketAddr::from((Ipv6Addr::LOCALHOST, 8080)))
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_mutex() {
+            let v = Mutex::new(vec![1]);
+
+            assert_eq!(
+                vec![Token::SeqBegin(Some(1)), Token::I32(1), Token::SeqEnd],
+                test::tokens(&v)
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_rwlock() {
+            let v = RwLock::new("a");
+
+            assert_eq!(vec![Token::Str("a".into())], test::tokens(&v));
+        }
+
+        #[test]
+        fn stream_poisoned_mutex() {
+            let v = Arc::new(Mutex::new(1));
+
+            let poison = v.clone();
+            let _ = crate::std::thread::spawn(move || {
+                let _guard = poison.lock().unwrap();
+                panic!("poison the mutex");
+            })
+            .join();
+
+            assert!(crate::stream_owned(&mut crate::stream::CountingStream::new(), &*v).is_err());
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_path() {
+            assert_eq!(
+                vec![Token::Str("a/b.txt".into())],
+                test::tokens(Path::new("a/b.txt"))
+            );
+
+            assert_eq!(
+                vec![Token::Str("a/b.txt".into())],
+                test::tokens(&PathBuf::from("a/b.txt"))
+            );
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn stream_path_non_utf8() {
+            use crate::std::{
+                ffi::OsStr,
+                os::unix::ffi::OsStrExt,
+            };
+
+            let path = Path::new(OsStr::from_bytes(b"a/\xFFb"));
+
+            assert_eq!(vec![Token::Str("a/\u{FFFD}b".into())], test::tokens(path));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_duration() {
+            let v = test::tokens(&Duration::new(1, 42));
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(2)),
+                    Token::Str("secs".into()),
+                    Token::Unsigned(1),
+                    Token::Str("nanos".into()),
+                    Token::Unsigned(42),
+                    Token::MapEnd,
+                ],
+                v
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_system_time() {
+            let v = test::tokens(&(UNIX_EPOCH + Duration::new(1, 42)));
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(2)),
+                    Token::Str("elapsed_secs".into()),
+                    Token::Signed(1),
+                    Token::Str("nanos".into()),
+                    Token::Unsigned(42),
+                    Token::MapEnd,
+                ],
+                v
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_system_time_before_epoch() {
+            let v = test::tokens(&(UNIX_EPOCH - Duration::new(1, 500_000_000)));
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(2)),
+                    Token::Str("elapsed_secs".into()),
+                    Token::Signed(-2),
+                    Token::Str("nanos".into()),
+                    Token::Unsigned(500_000_000),
+                    Token::MapEnd,
+                ],
+                v
+            );
+
+            let v = test::tokens(&(UNIX_EPOCH - Duration::from_secs(1)));
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(2)),
+                    Token::Str("elapsed_secs".into()),
+                    Token::Signed(-1),
+                    Token::Str("nanos".into()),
+                    Token::Unsigned(0),
+                    Token::MapEnd,
+                ],
+                v
+            );
+        }
+    }
+
+    #[cfg(feature = "serde_json_lib")]
+    mod serde_json_support {
+        use crate::test::{
+            self,
+            Token,
+        };
+
+        use serde_json_lib::json;
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_json_primitive() {
+            assert_eq!(vec![Token::None], test::tokens(&json!(null)));
+            assert_eq!(vec![Token::Bool(true)], test::tokens(&json!(true)));
+            assert_eq!(vec![Token::Str("a".into())], test::tokens(&json!("a")));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_json_number() {
+            assert_eq!(vec![Token::Unsigned(1)], test::tokens(&json!(1)));
+            assert_eq!(vec![Token::Signed(-1)], test::tokens(&json!(-1)));
+            assert_eq!(vec![Token::Float(1.5)], test::tokens(&json!(1.5)));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_json_structure() {
+            assert_eq!(
+                vec![
+                    Token::MapBegin(Some(1)),
+                    Token::Str("a".into()),
+                    Token::SeqBegin(Some(2)),
+                    Token::Unsigned(1),
+                    Token::None,
+                    Token::SeqEnd,
+                    Token::MapEnd,
+                ],
+                test::tokens(&json!({ "a": [1, null] }))
+            );
         }
     }
 }
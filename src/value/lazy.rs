@@ -0,0 +1,114 @@
+use crate::value::{
+    self,
+    Value,
+};
+
+/**
+A value that's only computed when it's streamed.
+
+The closure is called each time the value is streamed, so expensive
+values are only built if they're actually needed:
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::{test::{self, Token}, value::Lazy};
+
+let value = Lazy::new(|| 40 + 2);
+
+assert_eq!(vec![Token::I32(42)], test::tokens(&value));
+# }
+# }
+```
+
+If the stream hints that it will skip values then the closure isn't
+called at all.
+
+The closure is a `Fn` rather than a `FnOnce` because a value can be
+streamed any number of times.
+*/
+#[derive(Clone, Copy)]
+pub struct Lazy<F>(F);
+
+impl<F, V> Lazy<F>
+where
+    F: Fn() -> V,
+    V: Value,
+{
+    /**
+    Wrap a closure that computes a value.
+    */
+    pub fn new(f: F) -> Self {
+        Lazy(f)
+    }
+}
+
+impl<F, V> Value for Lazy<F>
+where
+    F: Fn() -> V,
+    V: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        if stream.hint_skip() {
+            return Ok(());
+        }
+
+        stream.owned().any(&(self.0)())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            cell::Cell,
+            vec,
+        },
+        stream::CountingStream,
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn lazy_is_called_when_streamed() {
+        let calls = Cell::new(0);
+
+        let value = Lazy::new(|| {
+            calls.set(calls.get() + 1);
+            vec![1, 2]
+        });
+
+        assert_eq!(0, calls.get());
+
+        let mut stream = CountingStream::new();
+        crate::stream(&mut stream, &value).unwrap();
+
+        assert_eq!(1, calls.get());
+        assert_eq!(2, stream.primitive_count());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn lazy_tokens() {
+        let value = Lazy::new(|| vec![1, 2]);
+
+        assert_eq!(
+            vec![
+                Token::SeqBegin(Some(2)),
+                Token::I32(1),
+                Token::I32(2),
+                Token::SeqEnd,
+            ],
+            test::tokens(&value)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    buffer::{
+        Buffer,
+        Token,
+    },
+    std::cell::RefCell,
+    value::{
+        self,
+        owned::TokenBuf,
+        Value,
+    },
+};
+
+/**
+A value with a function applied to each of its tokens.
+
+The value is buffered into [`Token`]s, which are passed through the
+function one at a time before being streamed. Tokens that the function
+returns `None` for are dropped. The function is responsible for
+keeping the resulting structure valid.
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::{
+    buffer::Token as BufferToken,
+    test::{self, Token},
+    value::Mapped,
+};
+
+let mut map = std::collections::BTreeMap::new();
+map.insert("a", 1);
+
+// Rename the map key `a` to `b`
+let mut is_key = false;
+let value = Mapped::new(map, move |token| {
+    let token = match token {
+        BufferToken::Str(ref key) if is_key && key == "a" => BufferToken::Str("b".into()),
+        token => token,
+    };
+
+    is_key = token == BufferToken::MapKey;
+    Some(token)
+});
+
+assert_eq!(
+    vec![
+        Token::MapBegin(Some(1)),
+        Token::Str("b".into()),
+        Token::I32(1),
+        Token::MapEnd,
+    ],
+    test::tokens(&value),
+);
+# }
+# }
+```
+
+[`Token`]: ../buffer/enum.Token.html
+*/
+pub struct Mapped<V, F> {
+    value: V,
+    f: RefCell<F>,
+}
+
+impl<V, F> Mapped<V, F>
+where
+    V: Value,
+    F: FnMut(Token) -> Option<Token>,
+{
+    /**
+    Wrap a value with a function to apply to its tokens.
+    */
+    pub fn new(value: V, f: F) -> Self {
+        Mapped {
+            value,
+            f: RefCell::new(f),
+        }
+    }
+
+    /**
+    Get the inner value.
+    */
+    pub fn into_inner(self) -> V {
+        self.value
+    }
+}
+
+impl<V, F> Value for Mapped<V, F>
+where
+    V: Value,
+    F: FnMut(Token) -> Option<Token>,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        let tokens = TokenBuf::collect(&self.value)?;
+
+        let mut f = self
+            .f
+            .try_borrow_mut()
+            .map_err(|_| crate::Error::msg("the value is already being streamed"))?;
+
+        let mut buffer = Buffer::new();
+        for token in tokens {
+            if let Some(token) = f(Token::from_kind(&token.kind)) {
+                buffer.push_token(token);
+            }
+        }
+
+        stream.owned().any(&buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::vec,
+        test::{
+            self,
+            Token as TestToken,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn mapped_replaces_tokens() {
+        let value = Mapped::new((1, 2), |token| match token {
+            Token::I32(v) => Some(Token::Signed(v as i64 * 10)),
+            token => Some(token),
+        });
+
+        assert_eq!(
+            vec![
+                TestToken::SeqBegin(Some(2)),
+                TestToken::Signed(10),
+                TestToken::Signed(20),
+                TestToken::SeqEnd,
+            ],
+            test::tokens(&value)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn mapped_drops_tokens() {
+        // Unwrap a single element tuple
+        let value = Mapped::new((1,), |token| match token {
+            Token::SeqBegin(_) | Token::SeqElem | Token::SeqEnd => None,
+            token => Some(token),
+        });
+
+        assert_eq!(vec![TestToken::I32(1)], test::tokens(&value));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn mapped_renames_keys() {
+        let mut is_key = false;
+        let value = Mapped::new(vec![("a", 1), ("c", 2)], move |token| {
+            let token = match token {
+                Token::Str(ref key) if is_key && key == "a" => Token::Str("b".into()),
+                token => token,
+            };
+
+            is_key = token == Token::MapKey;
+            Some(token)
+        });
+
+        assert_eq!(
+            vec![
+                TestToken::SeqBegin(Some(2)),
+                TestToken::SeqBegin(Some(2)),
+                TestToken::Str("a".into()),
+                TestToken::I32(1),
+                TestToken::SeqEnd,
+                TestToken::SeqBegin(Some(2)),
+                TestToken::Str("c".into()),
+                TestToken::I32(2),
+                TestToken::SeqEnd,
+                TestToken::SeqEnd,
+            ],
+            test::tokens(&value)
+        );
+
+        let mut is_key = false;
+        let value = Mapped::new(
+            crate::std::collections::BTreeMap::from([("a", 1), ("c", 2)]),
+            move |token| {
+                let token = match token {
+                    Token::Str(ref key) if is_key && key == "a" => Token::Str("b".into()),
+                    token => token,
+                };
+
+                is_key = token == Token::MapKey;
+                Some(token)
+            },
+        );
+
+        assert_eq!(
+            vec![
+                TestToken::MapBegin(Some(2)),
+                TestToken::Str("b".into()),
+                TestToken::I32(1),
+                TestToken::Str("c".into()),
+                TestToken::I32(2),
+                TestToken::MapEnd,
+            ],
+            test::tokens(&value)
+        );
+    }
+}
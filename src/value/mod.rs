@@ -31,16 +31,59 @@ pub struct Data {
 ```
 */
 
+mod adapter;
+mod bytes;
+mod captured;
+mod enum_;
+mod flatten;
 mod impls;
+mod lazy;
 mod stream;
+mod transparent;
 
 #[cfg(feature = "alloc")]
 pub(crate) mod owned;
 
-pub use self::stream::Stream;
+#[cfg(feature = "alloc")]
+mod mapped;
+
+#[cfg(feature = "alloc")]
+mod sorted;
+
+#[cfg(feature = "alloc")]
+mod struct_;
+
+#[cfg(feature = "chrono")]
+mod chrono;
+
+#[cfg(feature = "uuid")]
+mod uuid;
+
+pub use self::{
+    adapter::{
+        MapAdapter,
+        SeqAdapter,
+    },
+    bytes::Bytes,
+    captured::Captured,
+    enum_::Enum,
+    flatten::Flatten,
+    lazy::Lazy,
+    stream::Stream,
+    transparent::Transparent,
+};
+
+#[cfg(feature = "uuid")]
+pub use self::uuid::UuidFormat;
 
 #[cfg(feature = "alloc")]
-pub use self::owned::OwnedValue;
+pub use self::{
+    captured::OwnedCaptured,
+    mapped::Mapped,
+    owned::OwnedValue,
+    sorted::Sorted,
+    struct_::Struct,
+};
 
 /**
 A value with a streamable structure.
@@ -61,17 +61,26 @@ impl OwnedValue {
     [`Value`]: struct.Value.html
     */
     pub fn collect(v: impl Value) -> Self {
+        OwnedValue::try_collect(v)
+            .unwrap_or_else(|err| OwnedValue(ValueInner::Error(err.to_string().into())))
+    }
+
+    /**
+    Get an owned value from an arbitrary [`Value`], returning any
+    error encountered while streaming it.
+
+    [`Value`]: struct.Value.html
+    */
+    pub fn try_collect(v: impl Value) -> Result<Self, crate::Error> {
         // Try get a primitive first
         // If the value is a simple primitive that can
         // be represented in a single token then we can avoid
         // allocating for it.
         if let Some(primitive) = PrimitiveBuf::collect(&v) {
-            return OwnedValue(ValueInner::Primitive(primitive));
+            return Ok(OwnedValue(ValueInner::Primitive(primitive)));
         }
 
-        TokenBuf::collect(v)
-            .map(|tokens| OwnedValue(ValueInner::Stream(tokens.into())))
-            .unwrap_or_else(|err| OwnedValue(ValueInner::Error(err.to_string().into())))
+        TokenBuf::collect(v).map(|tokens| OwnedValue(ValueInner::Stream(tokens.into())))
     }
 
     /**
@@ -136,19 +145,19 @@ impl From<usize> for OwnedValue {
 
 impl From<u8> for OwnedValue {
     fn from(v: u8) -> Self {
-        OwnedValue(ValueInner::Primitive(Primitive::Unsigned(v as u64)))
+        OwnedValue(ValueInner::Primitive(Primitive::U8(v)))
     }
 }
 
 impl From<u16> for OwnedValue {
     fn from(v: u16) -> Self {
-        OwnedValue(ValueInner::Primitive(Primitive::Unsigned(v as u64)))
+        OwnedValue(ValueInner::Primitive(Primitive::U16(v)))
     }
 }
 
 impl From<u32> for OwnedValue {
     fn from(v: u32) -> Self {
-        OwnedValue(ValueInner::Primitive(Primitive::Unsigned(v as u64)))
+        OwnedValue(ValueInner::Primitive(Primitive::U32(v)))
     }
 }
 
@@ -172,19 +181,19 @@ impl From<isize> for OwnedValue {
 
 impl From<i8> for OwnedValue {
     fn from(v: i8) -> Self {
-        OwnedValue(ValueInner::Primitive(Primitive::Signed(v as i64)))
+        OwnedValue(ValueInner::Primitive(Primitive::I8(v)))
     }
 }
 
 impl From<i16> for OwnedValue {
     fn from(v: i16) -> Self {
-        OwnedValue(ValueInner::Primitive(Primitive::Signed(v as i64)))
+        OwnedValue(ValueInner::Primitive(Primitive::I16(v)))
     }
 }
 
 impl From<i32> for OwnedValue {
     fn from(v: i32) -> Self {
-        OwnedValue(ValueInner::Primitive(Primitive::Signed(v as i64)))
+        OwnedValue(ValueInner::Primitive(Primitive::I32(v)))
     }
 }
 
@@ -202,7 +211,7 @@ impl From<i128> for OwnedValue {
 
 impl From<f32> for OwnedValue {
     fn from(v: f32) -> Self {
-        OwnedValue(ValueInner::Primitive(Primitive::Float(v as f64)))
+        OwnedValue(ValueInner::Primitive(Primitive::Float32(v)))
     }
 }
 
@@ -331,6 +340,17 @@ impl OwnedSource {
     }
 }
 
+impl OwnedSource {
+    pub(crate) fn msg(msg: String) -> Self {
+        OwnedSource {
+            debug: msg.clone(),
+            display: msg,
+            #[cfg(feature = "std")]
+            source: None,
+        }
+    }
+}
+
 impl<'a> From<stream::Source<'a>> for OwnedSource {
     fn from(err: stream::Source<'a>) -> OwnedSource {
         #[cfg(feature = "std")]
@@ -392,29 +412,45 @@ pub(crate) enum TokenKind {
     SeqEnd,
     Signed(i64),
     Unsigned(u64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    I8(i8),
+    I16(i16),
+    I32(i32),
     Float(f64),
+    Float32(f32),
     BigSigned(i128),
     BigUnsigned(u128),
     Bool(bool),
     Str(StringContainer<OwnedContainer<str>>),
     Char(char),
+    Bytes(OwnedContainer<[u8]>),
     Error(OwnedContainer<OwnedSource>),
     None,
 }
 
 impl Token {
-    fn stream_owned(&self, mut stream: value::Stream) -> value::Result {
+    pub(crate) fn stream_owned(&self, mut stream: value::Stream) -> value::Result {
         use self::TokenKind::*;
 
         match self.kind {
             Signed(v) => stream.i64(v)?,
             Unsigned(v) => stream.u64(v)?,
+            U8(v) => stream.u8(v)?,
+            U16(v) => stream.u16(v)?,
+            U32(v) => stream.u32(v)?,
+            I8(v) => stream.i8(v)?,
+            I16(v) => stream.i16(v)?,
+            I32(v) => stream.i32(v)?,
             Float(v) => stream.f64(v)?,
+            Float32(v) => stream.f32(v)?,
             BigSigned(v) => stream.i128(v)?,
             BigUnsigned(v) => stream.u128(v)?,
             Bool(v) => stream.bool(v)?,
             Str(ref v) => stream.owned().str(&*v)?,
             Char(v) => stream.char(v)?,
+            Bytes(ref v) => stream.owned().bytes(v)?,
             Error(ref v) => stream::Source::from(&**v).stream(stream.owned())?,
             None => stream.none()?,
             MapBegin(len) => stream.map_begin(len)?,
@@ -449,7 +485,7 @@ impl TokenBuf {
         }
     }
 
-    fn collect(v: impl Value) -> Result<Vec<Token>, crate::Error> {
+    pub(crate) fn collect(v: impl Value) -> Result<Vec<Token>, crate::Error> {
         let mut buf = TokenBuf::new();
         crate::stream_owned(&mut buf, &v)?;
 
@@ -464,7 +500,6 @@ impl TokenBuf {
     }
 }
 
-#[cfg(feature = "serde1")]
 impl TokenBuf {
     pub(crate) fn clear(&mut self) {
         self.tokens.clear();
@@ -475,12 +510,25 @@ impl TokenBuf {
         &self.tokens
     }
 
+    pub(crate) fn error_owned(&mut self, v: OwnedSource) -> stream::Result {
+        self.push(TokenKind::Error(OwnedContainer::from(v)));
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl TokenBuf {
     pub(crate) fn is_streamable(&self) -> bool {
         self.depth == 0
     }
 }
 
 impl<'v> Stream<'v> for TokenBuf {
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
     fn fmt(&mut self, f: stream::Arguments) -> stream::Result {
         self.push(TokenKind::Str(StringContainer::from(f.to_string())));
 
@@ -492,15 +540,17 @@ impl<'v> Stream<'v> for TokenBuf {
     }
 
     fn error(&mut self, v: stream::Source) -> stream::Result {
-        self.push(TokenKind::Error(OwnedContainer::from(OwnedSource::from(v))));
-
-        Ok(())
+        self.error_owned(OwnedSource::from(v))
     }
 
     fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
         self.error(v)
     }
 
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.error_owned(OwnedSource::msg(v.into()))
+    }
+
     fn i64(&mut self, v: i64) -> stream::Result {
         self.push(TokenKind::Signed(v));
 
@@ -513,6 +563,42 @@ impl<'v> Stream<'v> for TokenBuf {
         Ok(())
     }
 
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.push(TokenKind::U8(v));
+
+        Ok(())
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.push(TokenKind::U16(v));
+
+        Ok(())
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.push(TokenKind::U32(v));
+
+        Ok(())
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.push(TokenKind::I8(v));
+
+        Ok(())
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.push(TokenKind::I16(v));
+
+        Ok(())
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.push(TokenKind::I32(v));
+
+        Ok(())
+    }
+
     fn i128(&mut self, v: i128) -> stream::Result {
         self.push(TokenKind::BigSigned(v));
 
@@ -531,6 +617,12 @@ impl<'v> Stream<'v> for TokenBuf {
         Ok(())
     }
 
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.push(TokenKind::Float32(v));
+
+        Ok(())
+    }
+
     fn bool(&mut self, v: bool) -> stream::Result {
         self.push(TokenKind::Bool(v));
 
@@ -549,10 +641,46 @@ impl<'v> Stream<'v> for TokenBuf {
         Ok(())
     }
 
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
     fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
         self.str(v)
     }
 
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.push(TokenKind::Str(StringContainer::from(v)));
+
+        Ok(())
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.push(TokenKind::Bytes(OwnedContainer::from(v)));
+
+        Ok(())
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
     fn none(&mut self) -> stream::Result {
         self.push(TokenKind::None);
 
@@ -567,6 +695,10 @@ impl<'v> Stream<'v> for TokenBuf {
         Ok(())
     }
 
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
     fn map_key(&mut self) -> stream::Result {
         self.push(TokenKind::MapKey);
 
@@ -597,6 +729,19 @@ impl<'v> Stream<'v> for TokenBuf {
         self.map_value_collect(v)
     }
 
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
     fn map_end(&mut self) -> stream::Result {
         self.push(TokenKind::MapEnd);
 
@@ -613,12 +758,21 @@ impl<'v> Stream<'v> for TokenBuf {
         Ok(())
     }
 
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
     fn seq_elem(&mut self) -> stream::Result {
         self.push(TokenKind::SeqElem);
 
         Ok(())
     }
 
+    fn seq_elem_end(&mut self) -> stream::Result {
+        // Elements are implicitly ended, so there's nothing to buffer
+        Ok(())
+    }
+
     fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
         self.seq_elem()?;
         v.stream(self)
@@ -642,12 +796,20 @@ impl<'v> Stream<'v> for TokenBuf {
 pub(crate) enum Primitive {
     Signed(i64),
     Unsigned(u64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    I8(i8),
+    I16(i16),
+    I32(i32),
     Float(f64),
+    Float32(f32),
     BigSigned(i128),
     BigUnsigned(u128),
     Bool(bool),
     Str(StringContainer<SharedContainer<str>>),
     Char(char),
+    Bytes(SharedContainer<[u8]>),
     Error(SharedContainer<OwnedSource>),
     None,
 }
@@ -660,12 +822,20 @@ impl Primitive {
             kind: match *self {
                 Primitive::Signed(v) => TokenKind::Signed(v),
                 Primitive::Unsigned(v) => TokenKind::Unsigned(v),
+                Primitive::U8(v) => TokenKind::U8(v),
+                Primitive::U16(v) => TokenKind::U16(v),
+                Primitive::U32(v) => TokenKind::U32(v),
+                Primitive::I8(v) => TokenKind::I8(v),
+                Primitive::I16(v) => TokenKind::I16(v),
+                Primitive::I32(v) => TokenKind::I32(v),
                 Primitive::Float(v) => TokenKind::Float(v),
+                Primitive::Float32(v) => TokenKind::Float32(v),
                 Primitive::BigSigned(v) => TokenKind::BigSigned(v),
                 Primitive::BigUnsigned(v) => TokenKind::BigUnsigned(v),
                 Primitive::Bool(v) => TokenKind::Bool(v),
                 Primitive::Str(ref v) => TokenKind::Str((&**v).into()),
                 Primitive::Char(v) => TokenKind::Char(v),
+                Primitive::Bytes(ref v) => TokenKind::Bytes((&**v).into()),
                 Primitive::Error(ref v) => TokenKind::Error((&**v).clone().into()),
                 Primitive::None => TokenKind::None,
             },
@@ -680,12 +850,20 @@ impl Value for Primitive {
         match *self {
             Signed(v) => stream.i64(v)?,
             Unsigned(v) => stream.u64(v)?,
+            U8(v) => stream.u8(v)?,
+            U16(v) => stream.u16(v)?,
+            U32(v) => stream.u32(v)?,
+            I8(v) => stream.i8(v)?,
+            I16(v) => stream.i16(v)?,
+            I32(v) => stream.i32(v)?,
             Float(v) => stream.f64(v)?,
+            Float32(v) => stream.f32(v)?,
             BigSigned(v) => stream.i128(v)?,
             BigUnsigned(v) => stream.u128(v)?,
             Bool(v) => stream.bool(v)?,
             Str(ref v) => stream.owned().str(&*v)?,
             Char(v) => stream.char(v)?,
+            Bytes(ref v) => stream.owned().bytes(v)?,
             Error(ref v) => stream::Source::from(&**v).stream(stream.owned())?,
             None => stream.none()?,
         }
@@ -716,6 +894,10 @@ impl PrimitiveBuf {
 }
 
 impl<'v> Stream<'v> for PrimitiveBuf {
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
     fn fmt(&mut self, f: stream::Arguments) -> stream::Result {
         self.set(Primitive::Str(StringContainer::from(f.to_string())));
 
@@ -738,6 +920,14 @@ impl<'v> Stream<'v> for PrimitiveBuf {
         self.error(v)
     }
 
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.set(Primitive::Error(SharedContainer::from(OwnedSource::msg(
+            v.into(),
+        ))));
+
+        Ok(())
+    }
+
     fn i64(&mut self, v: i64) -> stream::Result {
         self.set(Primitive::Signed(v));
 
@@ -750,6 +940,42 @@ impl<'v> Stream<'v> for PrimitiveBuf {
         Ok(())
     }
 
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.set(Primitive::U8(v));
+
+        Ok(())
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.set(Primitive::U16(v));
+
+        Ok(())
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.set(Primitive::U32(v));
+
+        Ok(())
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.set(Primitive::I8(v));
+
+        Ok(())
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.set(Primitive::I16(v));
+
+        Ok(())
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.set(Primitive::I32(v));
+
+        Ok(())
+    }
+
     fn i128(&mut self, v: i128) -> stream::Result {
         self.set(Primitive::BigSigned(v));
 
@@ -768,6 +994,12 @@ impl<'v> Stream<'v> for PrimitiveBuf {
         Ok(())
     }
 
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.set(Primitive::Float32(v));
+
+        Ok(())
+    }
+
     fn bool(&mut self, v: bool) -> stream::Result {
         self.set(Primitive::Bool(v));
 
@@ -786,10 +1018,46 @@ impl<'v> Stream<'v> for PrimitiveBuf {
         Ok(())
     }
 
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
     fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
         self.str(v)
     }
 
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.set(Primitive::Str(StringContainer::from(v)));
+
+        Ok(())
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.set(Primitive::Bytes(SharedContainer::from(v)));
+
+        Ok(())
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
     fn none(&mut self) -> stream::Result {
         self.set(Primitive::None);
 
@@ -800,6 +1068,10 @@ impl<'v> Stream<'v> for PrimitiveBuf {
         Err(crate::Error::unsupported("unsupported primitive"))
     }
 
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
     fn map_key(&mut self) -> stream::Result {
         Err(crate::Error::unsupported("unsupported primitive"))
     }
@@ -824,6 +1096,19 @@ impl<'v> Stream<'v> for PrimitiveBuf {
         self.map_value_collect(v)
     }
 
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
     fn map_end(&mut self) -> stream::Result {
         Err(crate::Error::unsupported("unsupported primitive"))
     }
@@ -832,10 +1117,18 @@ impl<'v> Stream<'v> for PrimitiveBuf {
         Err(crate::Error::unsupported("unsupported primitive"))
     }
 
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
     fn seq_elem(&mut self) -> stream::Result {
         Err(crate::Error::unsupported("unsupported primitive"))
     }
 
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Err(crate::Error::unsupported("unsupported primitive"))
+    }
+
     fn seq_elem_collect(&mut self, _: stream::Value) -> stream::Result {
         Err(crate::Error::unsupported("unsupported primitive"))
     }
@@ -996,6 +1289,7 @@ mod tests {
         assert_eq!(vec![Token::BigSigned(42i128)], test::tokens(&42i128));
 
         assert_eq!(vec![Token::Float(42f64)], test::tokens(&42f64));
+        assert_eq!(vec![Token::Float32(42f32)], test::tokens(&42f32));
 
         assert_eq!(vec![Token::Bool(true)], test::tokens(&true));
 
@@ -1012,16 +1306,201 @@ mod tests {
         assert_eq!(
             vec![
                 Token::MapBegin(Some(2)),
-                Token::Signed(1),
-                Token::Signed(11),
-                Token::Signed(2),
-                Token::Signed(22),
+                Token::I32(1),
+                Token::I32(11),
+                Token::I32(2),
+                Token::I32(22),
                 Token::MapEnd,
             ],
             v
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_unknown_len() {
+        struct UnknownLen;
+
+        impl Value for UnknownLen {
+            fn stream<'s, 'v>(&self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin_unknown()?;
+                stream.map_key_begin()?.i32(1)?;
+                stream.map_value_begin()?.seq_begin_unknown()?;
+                stream.seq_end()?;
+                stream.map_end()
+            }
+        }
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(None),
+                Token::I32(1),
+                Token::SeqBegin(None),
+                Token::SeqEnd,
+                Token::MapEnd,
+            ],
+            test::tokens(&UnknownLen)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_error_str() {
+        struct ErrorStr;
+
+        impl Value for ErrorStr {
+            fn stream<'s, 'v>(&self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.error_str("something went wrong")
+            }
+        }
+
+        struct ErrorStrSeq;
+
+        impl Value for ErrorStrSeq {
+            fn stream<'s, 'v>(&self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(Some(1))?;
+                stream.seq_elem_begin()?.error_str("something went wrong")?;
+                stream.seq_end()
+            }
+        }
+
+        match &test::tokens(&ErrorStr)[..] {
+            [Token::Error(err)] => assert_eq!("something went wrong", err.to_string()),
+            tokens => panic!("unexpected tokens {:?}", tokens),
+        }
+
+        match &test::tokens(&ErrorStrSeq)[..] {
+            [Token::SeqBegin(Some(1)), Token::Error(err), Token::SeqEnd] => {
+                assert_eq!("something went wrong", err.to_string())
+            }
+            tokens => panic!("unexpected tokens {:?}", tokens),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_none_or() {
+        struct First(Vec<i32>);
+
+        impl Value for First {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.none_or(self.0.first())
+            }
+        }
+
+        assert_eq!(vec![Token::I32(1)], test::tokens(&First(vec![1, 2])));
+        assert_eq!(vec![Token::None], test::tokens(&First(vec![])));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_typed_str() {
+        struct DateTime;
+
+        impl Value for DateTime {
+            fn stream<'s, 'v>(&self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.typed_str("date-time", "2020-01-01T00:00:00Z")
+            }
+        }
+
+        assert_eq!(
+            vec![Token::Str("2020-01-01T00:00:00Z".into())],
+            test::tokens(&DateTime)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_map_flat_entry() {
+        struct Map;
+
+        impl Value for Map {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin(Some(1))?;
+                stream.map_flat_entry(&"a", &1i64)?;
+                stream.map_end()
+            }
+        }
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(1)),
+                Token::Str("a".into()),
+                Token::Signed(1),
+                Token::MapEnd,
+            ],
+            test::tokens(&Map)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_str_owned() {
+        struct OwnedStr;
+
+        impl Value for OwnedStr {
+            fn stream<'s, 'v>(&self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.str_owned(String::from("a"))
+            }
+        }
+
+        struct OwnedStrSeq;
+
+        impl Value for OwnedStrSeq {
+            fn stream<'s, 'v>(&self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(Some(1))?;
+                stream.seq_elem_begin()?.str_owned(String::from("a"))?;
+                stream.seq_end()
+            }
+        }
+
+        assert_eq!(vec![Token::Str("a".into())], test::tokens(&OwnedStr));
+
+        assert_eq!(
+            vec![
+                Token::SeqBegin(Some(1)),
+                Token::Str("a".into()),
+                Token::SeqEnd,
+            ],
+            test::tokens(&OwnedStrSeq)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn owned_seq_elem_begin_end() {
+        struct Bracketed;
+
+        impl Value for Bracketed {
+            fn stream<'s, 'v>(&self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.seq_begin(Some(2))?;
+
+                stream.seq_elem_begin()?;
+                stream.seq_begin(Some(1))?;
+                stream.seq_elem(&1)?;
+                stream.seq_end()?;
+                stream.seq_elem_end()?;
+
+                stream.seq_elem_begin()?.i64(2)?;
+                stream.seq_elem_end()?;
+
+                stream.seq_end()
+            }
+        }
+
+        assert_eq!(
+            vec![
+                Token::SeqBegin(Some(2)),
+                Token::SeqBegin(Some(1)),
+                Token::I32(1),
+                Token::SeqEnd,
+                Token::Signed(2),
+                Token::SeqEnd,
+            ],
+            test::tokens(&Bracketed)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn owned_seq() {
@@ -1030,8 +1509,8 @@ mod tests {
         assert_eq!(
             vec![
                 Token::SeqBegin(Some(2)),
-                Token::Signed(1),
-                Token::Signed(2),
+                Token::I32(1),
+                Token::I32(2),
                 Token::SeqEnd,
             ],
             v
@@ -1071,7 +1550,7 @@ mod tests {
         #[test]
         #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
         fn owned_error() {
-            let v = test::tokens(stream::Source::empty());
+            let v = test::tokens(&stream::Source::empty());
 
             assert_eq!(vec![Token::None,], v);
         }
@@ -1113,7 +1592,7 @@ mod tests {
                 source: io::Error::from(io::ErrorKind::Other),
             };
 
-            let v = test::tokens(stream::Source::new(&err));
+            let v = test::tokens(&stream::Source::new(&err));
 
             assert_eq!(vec![Token::Error(test::Source::new(&err)),], v);
         }
@@ -0,0 +1,143 @@
+use crate::{
+    std::vec::Vec,
+    value::{
+        self,
+        Value,
+    },
+};
+
+/**
+A map that streams its entries in key order.
+
+Some maps, like `HashMap`, don't iterate their entries in a
+consistent order. Wrapping them in `Sorted` will stream their
+entries ordered by key, so the same map always produces the same tokens:
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use std::collections::HashMap;
+
+use sval::{test::{self, Token}, value::Sorted};
+
+let mut map = HashMap::new();
+map.insert("b", 2);
+map.insert("a", 1);
+
+assert_eq!(
+    vec![
+        Token::MapBegin(Some(2)),
+        Token::Str("a".into()),
+        Token::I32(1),
+        Token::Str("b".into()),
+        Token::I32(2),
+        Token::MapEnd,
+    ],
+    test::tokens(&Sorted::new(&map)),
+);
+# }
+# }
+```
+
+Add the `alloc` feature to your `Cargo.toml` to enable this type.
+*/
+#[derive(Debug)]
+pub struct Sorted<'a, M: ?Sized>(&'a M);
+
+impl<'a, M: ?Sized> Clone for Sorted<'a, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, M: ?Sized> Copy for Sorted<'a, M> {}
+
+impl<'a, M: ?Sized> Sorted<'a, M> {
+    /**
+    Wrap a map to sort.
+    */
+    pub fn new(map: &'a M) -> Self {
+        Sorted(map)
+    }
+}
+
+impl<'a, M: ?Sized, K, V> Value for Sorted<'a, M>
+where
+    &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    K: Ord + Value + 'a,
+    V: Value + 'a,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        let mut entries: Vec<_> = self.0.into_iter().collect();
+        entries.sort_by_key(|&(k, _)| k);
+
+        stream.map_begin(Some(entries.len()))?;
+
+        for (k, v) in entries {
+            stream.map_key(k)?;
+            stream.map_value(v)?;
+        }
+
+        stream.map_end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::collections::BTreeMap,
+        test,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn sorted_btree_map() {
+        let mut map = BTreeMap::new();
+        map.insert(2, "b");
+        map.insert(1, "a");
+
+        assert_eq!(test::tokens(&map), test::tokens(&Sorted::new(&map)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn sorted_hash_map() {
+        use crate::{
+            std::{
+                collections::HashMap,
+                vec,
+            },
+            test::Token,
+        };
+
+        let mut a = HashMap::new();
+        let mut b = HashMap::new();
+
+        for i in 0..32 {
+            a.insert(i, i);
+            b.insert(31 - i, 31 - i);
+        }
+
+        let tokens = test::tokens(&Sorted::new(&a));
+
+        assert_eq!(tokens, test::tokens(&Sorted::new(&b)));
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(32)),
+                Token::I32(0),
+                Token::I32(0),
+                Token::I32(1),
+                Token::I32(1),
+            ],
+            &tokens[..5]
+        );
+    }
+}
@@ -7,6 +7,9 @@ use crate::{
     value::Value,
 };
 
+#[cfg(feature = "alloc")]
+use crate::std::string::String;
+
 #[cfg(feature = "std")]
 use crate::std::error;
 
@@ -48,6 +51,17 @@ impl<'s, 'v> Stream<'s, 'v> {
         (self.0).0
     }
 
+    /**
+    Whether or not the underlying stream will ignore any values given to it.
+
+    Implementations of [`Value`] can use this to avoid doing expensive work.
+
+    [`Value`]: ./trait.Value.html
+    */
+    pub fn hint_skip(&self) -> bool {
+        (self.0).0.hint_skip()
+    }
+
     /**
     Stream an implementation of [`Value`].
 
@@ -81,6 +95,13 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().error_borrowed(stream::Source::new(v))
     }
 
+    /**
+    Stream an error that's described by a message.
+    */
+    pub fn error_str(&mut self, v: &str) -> stream::Result {
+        self.inner().error_str(v)
+    }
+
     /**
     Stream a signed integer.
     */
@@ -95,6 +116,48 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().u64(v)
     }
 
+    /**
+    Stream a 8-bit unsigned integer.
+    */
+    pub fn u8(&mut self, v: u8) -> stream::Result {
+        self.inner().u8(v)
+    }
+
+    /**
+    Stream a 16-bit unsigned integer.
+    */
+    pub fn u16(&mut self, v: u16) -> stream::Result {
+        self.inner().u16(v)
+    }
+
+    /**
+    Stream a 32-bit unsigned integer.
+    */
+    pub fn u32(&mut self, v: u32) -> stream::Result {
+        self.inner().u32(v)
+    }
+
+    /**
+    Stream a 8-bit signed integer.
+    */
+    pub fn i8(&mut self, v: i8) -> stream::Result {
+        self.inner().i8(v)
+    }
+
+    /**
+    Stream a 16-bit signed integer.
+    */
+    pub fn i16(&mut self, v: i16) -> stream::Result {
+        self.inner().i16(v)
+    }
+
+    /**
+    Stream a 32-bit signed integer.
+    */
+    pub fn i32(&mut self, v: i32) -> stream::Result {
+        self.inner().i32(v)
+    }
+
     /**
     Stream a 128-bit signed integer.
     */
@@ -116,6 +179,13 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().f64(v)
     }
 
+    /**
+    Stream a 32-bit floating point value.
+    */
+    pub fn f32(&mut self, v: f32) -> stream::Result {
+        self.inner().f32(v)
+    }
+
     /**
     Stream a boolean.
     */
@@ -137,6 +207,61 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().str_borrowed(v)
     }
 
+    /**
+    Stream a UTF8 string that's tagged with the name of its type.
+    */
+    pub fn typed_str(&mut self, type_name: &str, v: &str) -> stream::Result {
+        self.inner().typed_str(type_name, v)
+    }
+
+    /**
+    Begin a value that's tagged with the name of its type.
+
+    The tagged value should be streamed next, followed by a call to [`typed_end`](#method.typed_end).
+    */
+    pub fn typed_begin(&mut self, tag: &str) -> stream::Result {
+        self.inner().typed_begin(tag)
+    }
+
+    /**
+    End a value that's tagged with the name of its type.
+    */
+    pub fn typed_end(&mut self) -> stream::Result {
+        self.inner().typed_end()
+    }
+
+    /**
+    Begin a value with a semantic tag, like a date-time.
+
+    The tagged value should be streamed next, followed by a call to [`tagged_end`](#method.tagged_end)
+    with the same tag.
+    */
+    pub fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.inner().tagged_begin(tag)
+    }
+
+    /**
+    End a value with a semantic tag.
+    */
+    pub fn tagged_end(&mut self, tag: &str) -> stream::Result {
+        self.inner().tagged_end(tag)
+    }
+
+    /**
+    Stream an owned UTF8 string.
+    */
+    #[cfg(feature = "alloc")]
+    pub fn str_owned(&mut self, v: String) -> stream::Result {
+        self.inner().str_owned(v)
+    }
+
+    /**
+    Stream a slice of bytes.
+    */
+    pub fn bytes(&mut self, v: &'v [u8]) -> stream::Result {
+        self.inner().bytes_borrowed(v)
+    }
+
     /**
     Stream an empty value.
     */
@@ -144,6 +269,19 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().none()
     }
 
+    /**
+    Stream an optional value.
+
+    If the value is `None` then an empty value is streamed,
+    the same as an `Option<T>`.
+    */
+    pub fn none_or(&mut self, v: Option<&'v impl Value>) -> stream::Result {
+        match v {
+            Some(v) => self.any(v),
+            None => self.none(),
+        }
+    }
+
     /**
     Begin a map.
     */
@@ -151,6 +289,13 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().map_begin(len)
     }
 
+    /**
+    Begin a map with an unknown length.
+    */
+    pub fn map_begin_unknown(&mut self) -> stream::Result {
+        self.inner().map_begin_unknown()
+    }
+
     /**
     Stream a map key.
     */
@@ -160,6 +305,19 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().map_key_collect_borrowed(stream::Value::new(k))
     }
 
+    /**
+    Stream a map key that's a UTF8 string.
+
+    This is the same as calling [`map_key`](#method.map_key) with a string,
+    but is streamed directly instead of through an implementation of [`Value`].
+
+    [`Value`]: ./trait.Value.html
+    */
+    pub fn map_key_str(&mut self, k: &'v str) -> stream::Result {
+        self.inner().map_key()?;
+        self.str(k)
+    }
+
     /**
     Stream a map value.
     */
@@ -170,6 +328,54 @@ impl<'s, 'v> Stream<'s, 'v> {
             .map_value_collect_borrowed(stream::Value::new(v))
     }
 
+    /**
+    Stream a map value that's a UTF8 string.
+    */
+    pub fn map_value_str(&mut self, v: &'v str) -> stream::Result {
+        self.inner().map_value()?;
+        self.str(v)
+    }
+
+    /**
+    Stream a map value that's a signed integer.
+    */
+    pub fn map_value_i64(&mut self, v: i64) -> stream::Result {
+        self.inner().map_value()?;
+        self.i64(v)
+    }
+
+    /**
+    Stream a map value that's an unsigned integer.
+    */
+    pub fn map_value_u64(&mut self, v: u64) -> stream::Result {
+        self.inner().map_value()?;
+        self.u64(v)
+    }
+
+    /**
+    Stream a map value that's a floating point value.
+    */
+    pub fn map_value_f64(&mut self, v: f64) -> stream::Result {
+        self.inner().map_value()?;
+        self.f64(v)
+    }
+
+    /**
+    Stream a map value that's a boolean.
+    */
+    pub fn map_value_bool(&mut self, v: bool) -> stream::Result {
+        self.inner().map_value()?;
+        self.bool(v)
+    }
+
+    /**
+    Stream a map key and its value.
+    */
+    pub fn map_flat_entry(&mut self, k: &'v impl Value, v: &'v impl Value) -> stream::Result {
+        self.inner()
+            .map_flat_entry_collect_borrowed(stream::Value::new(k), stream::Value::new(v))
+    }
+
     /**
     End a map.
     */
@@ -184,8 +390,18 @@ impl<'s, 'v> Stream<'s, 'v> {
         self.inner().seq_begin(len)
     }
 
+    /**
+    Begin a sequence with an unknown length.
+    */
+    pub fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.inner().seq_begin_unknown()
+    }
+
     /**
     Stream a sequence element.
+
+    This is the same as calling [`seq_elem_begin`](#method.seq_elem_begin),
+    streaming the element, then calling [`seq_elem_end`](#method.seq_elem_end).
     */
     pub fn seq_elem(&mut self, v: &'v impl Value) -> stream::Result {
         // NOTE: With specialization we could add a `?Sized` bound to `impl Value`
@@ -235,9 +451,25 @@ impl<'s, 'v> Stream<'s, 'v> {
 
         Ok(self)
     }
+
+    /**
+    End a sequence element.
+
+    Sequence elements are implicitly completed by the item that follows
+    [`seq_elem_begin`](#method.seq_elem_begin), so ending them is optional.
+    It can be used to bracket elements that are streamed using
+    any number of calls on this stream.
+    */
+    pub fn seq_elem_end(&mut self) -> stream::Result {
+        self.inner().seq_elem_end()
+    }
 }
 
 impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
+    fn hint_skip(&self) -> bool {
+        (self.0).0.hint_skip()
+    }
+
     fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
         self.inner().fmt(v)
     }
@@ -254,6 +486,10 @@ impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
         self.inner().error_borrowed(v)
     }
 
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.inner().error_str(v)
+    }
+
     fn i64(&mut self, v: i64) -> stream::Result {
         self.inner().i64(v)
     }
@@ -262,6 +498,30 @@ impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
         self.inner().u64(v)
     }
 
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.inner().u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.inner().u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.inner().u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.inner().i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.inner().i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.inner().i32(v)
+    }
+
     fn i128(&mut self, v: i128) -> stream::Result {
         self.inner().i128(v)
     }
@@ -274,6 +534,10 @@ impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
         self.inner().f64(v)
     }
 
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.inner().f32(v)
+    }
+
     fn bool(&mut self, v: bool) -> stream::Result {
         self.inner().bool(v)
     }
@@ -286,10 +550,43 @@ impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
         self.inner().str(v)
     }
 
+    fn typed_str(&mut self, type_name: &str, v: &str) -> stream::Result {
+        self.inner().typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> stream::Result {
+        self.inner().typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        self.inner().typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.inner().tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> stream::Result {
+        self.inner().tagged_end(tag)
+    }
+
     fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
         self.inner().str_borrowed(v)
     }
 
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.inner().str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.inner().bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.inner().bytes_borrowed(v)
+    }
+
     fn none(&mut self) -> stream::Result {
         self.inner().none()
     }
@@ -298,6 +595,10 @@ impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
         self.inner().map_begin(len)
     }
 
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.inner().map_begin_unknown()
+    }
+
     fn map_key(&mut self) -> stream::Result {
         self.inner().map_key()
     }
@@ -322,6 +623,18 @@ impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
         self.inner().map_value_collect_borrowed(v)
     }
 
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.inner().map_flat_entry_collect(k, v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.inner().map_flat_entry_collect_borrowed(k, v)
+    }
+
     fn map_end(&mut self) -> stream::Result {
         self.inner().map_end()
     }
@@ -330,10 +643,18 @@ impl<'s, 'v> stream::Stream<'v> for Stream<'s, 'v> {
         self.inner().seq_begin(len)
     }
 
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.inner().seq_begin_unknown()
+    }
+
     fn seq_elem(&mut self) -> stream::Result {
         self.inner().seq_elem()
     }
 
+    fn seq_elem_end(&mut self) -> stream::Result {
+        self.inner().seq_elem_end()
+    }
+
     fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
         self.inner().seq_elem_collect(v)
     }
@@ -351,6 +672,10 @@ impl<'a, 'v, S> stream::Stream<'v> for Owned<S>
 where
     S: stream::Stream<'a>,
 {
+    fn hint_skip(&self) -> bool {
+        self.0.hint_skip()
+    }
+
     fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
         self.0.fmt(v)
     }
@@ -367,6 +692,10 @@ where
         self.0.error(v)
     }
 
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.0.error_str(v)
+    }
+
     fn i64(&mut self, v: i64) -> stream::Result {
         self.0.i64(v)
     }
@@ -375,6 +704,30 @@ where
         self.0.u64(v)
     }
 
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.0.u8(v)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.0.u16(v)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.0.u32(v)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.0.i8(v)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.0.i16(v)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.0.i32(v)
+    }
+
     fn i128(&mut self, v: i128) -> stream::Result {
         self.0.i128(v)
     }
@@ -387,6 +740,10 @@ where
         self.0.f64(v)
     }
 
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.0.f32(v)
+    }
+
     fn bool(&mut self, v: bool) -> stream::Result {
         self.0.bool(v)
     }
@@ -399,10 +756,43 @@ where
         self.0.str(v)
     }
 
+    fn typed_str(&mut self, type_name: &str, v: &str) -> stream::Result {
+        self.0.typed_str(type_name, v)
+    }
+
+    fn typed_begin(&mut self, tag: &str) -> stream::Result {
+        self.0.typed_begin(tag)
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        self.0.typed_end()
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.0.tagged_begin(tag)
+    }
+
+    fn tagged_end(&mut self, tag: &str) -> stream::Result {
+        self.0.tagged_end(tag)
+    }
+
     fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
         self.0.str(v)
     }
 
+    #[cfg(feature = "alloc")]
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.0.str_owned(v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        self.0.bytes(v)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.0.bytes(v)
+    }
+
     fn none(&mut self) -> stream::Result {
         self.0.none()
     }
@@ -411,6 +801,10 @@ where
         self.0.map_begin(len)
     }
 
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.0.map_begin_unknown()
+    }
+
     fn map_key(&mut self) -> stream::Result {
         self.0.map_key()
     }
@@ -435,6 +829,18 @@ where
         self.0.map_value_collect(v)
     }
 
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.0.map_flat_entry_collect(k, v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.0.map_flat_entry_collect(k, v)
+    }
+
     fn map_end(&mut self) -> stream::Result {
         self.0.map_end()
     }
@@ -443,10 +849,18 @@ where
         self.0.seq_begin(len)
     }
 
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.0.seq_begin_unknown()
+    }
+
     fn seq_elem(&mut self) -> stream::Result {
         self.0.seq_elem()
     }
 
+    fn seq_elem_end(&mut self) -> stream::Result {
+        self.0.seq_elem_end()
+    }
+
     fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
         self.0.seq_elem_collect(v)
     }
@@ -459,3 +873,67 @@ where
         self.0.seq_end()
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::vec,
+        test::{
+            self,
+            Token,
+        },
+        value,
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn map_shortcuts() {
+        struct Map;
+
+        impl Value for Map {
+            fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+                stream.map_begin(Some(5))?;
+
+                stream.map_key_str("a")?;
+                stream.map_value_str("b")?;
+
+                stream.map_key_str("c")?;
+                stream.map_value_i64(-1)?;
+
+                stream.map_key_str("d")?;
+                stream.map_value_u64(1)?;
+
+                stream.map_key_str("e")?;
+                stream.map_value_f64(1.5)?;
+
+                stream.map_key_str("f")?;
+                stream.map_value_bool(true)?;
+
+                stream.map_end()
+            }
+        }
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(5)),
+                Token::Str("a".into()),
+                Token::Str("b".into()),
+                Token::Str("c".into()),
+                Token::Signed(-1),
+                Token::Str("d".into()),
+                Token::Unsigned(1),
+                Token::Str("e".into()),
+                Token::Float(1.5),
+                Token::Str("f".into()),
+                Token::Bool(true),
+                Token::MapEnd,
+            ],
+            test::tokens(&Map)
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    std::vec::Vec,
+    value::{
+        self,
+        Value,
+    },
+};
+
+/**
+A struct with named fields that's streamed as a map.
+
+Each field is streamed as a string key followed by its value,
+in the order they were added. The name of the struct isn't
+streamed, the same as a derived `Value`.
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+use sval::{
+    test::{self, Token},
+    value::{self, Struct, Value},
+};
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Value for Point {
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        Struct::new("Point")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .stream_owned(stream)
+    }
+}
+
+assert_eq!(
+    vec![
+        Token::MapBegin(Some(2)),
+        Token::Str("x".into()),
+        Token::I32(1),
+        Token::Str("y".into()),
+        Token::I32(2),
+        Token::MapEnd,
+    ],
+    test::tokens(&Point { x: 1, y: 2 }),
+);
+# }
+# }
+```
+*/
+#[derive(Clone)]
+pub struct Struct<'a> {
+    name: &'a str,
+    fields: Vec<(&'a str, &'a dyn Value)>,
+}
+
+impl<'a> Struct<'a> {
+    /**
+    Begin a struct with the given name.
+    */
+    pub fn new(name: &'a str) -> Self {
+        Struct {
+            name,
+            fields: Vec::new(),
+        }
+    }
+
+    /**
+    Add a field to the struct.
+    */
+    pub fn field(mut self, name: &'a str, value: &'a dyn Value) -> Self {
+        self.fields.push((name, value));
+        self
+    }
+
+    /**
+    Get the name of the struct.
+    */
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /**
+    Stream the struct, consuming it.
+
+    This is useful when a `Struct` is built inside an implementation
+    of [`Value`](trait.Value.html), so the fields can't outlive it.
+    */
+    pub fn stream_owned(self, mut stream: value::Stream) -> value::Result {
+        stream.owned().any(&self)
+    }
+}
+
+impl<'a> Value for Struct<'a> {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.map_begin(Some(self.fields.len()))?;
+
+        for (name, value) in &self.fields {
+            stream.map_key(name)?;
+            stream.map_value(value)?;
+        }
+
+        stream.map_end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::vec,
+        test::{
+            self,
+            Token,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn struct_streams_map() {
+        let nested = Struct::new("Inner").field("a", &true);
+        let tags = vec!["a", "b"];
+        let value = Struct::new("Outer")
+            .field("id", &1)
+            .field("inner", &nested)
+            .field("tags", &tags);
+
+        assert_eq!("Outer", value.name());
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(3)),
+                Token::Str("id".into()),
+                Token::I32(1),
+                Token::Str("inner".into()),
+                Token::MapBegin(Some(1)),
+                Token::Str("a".into()),
+                Token::Bool(true),
+                Token::MapEnd,
+                Token::Str("tags".into()),
+                Token::SeqBegin(Some(2)),
+                Token::Str("a".into()),
+                Token::Str("b".into()),
+                Token::SeqEnd,
+                Token::MapEnd,
+            ],
+            test::tokens(&value)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn struct_empty() {
+        assert_eq!(
+            vec![Token::MapBegin(Some(0)), Token::MapEnd],
+            test::tokens(&Struct::new("Empty"))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn struct_stream_owned() {
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        impl Value for Point {
+            fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+                Struct::new("Point")
+                    .field("x", &self.x)
+                    .field("y", &self.y)
+                    .stream_owned(stream)
+            }
+        }
+
+        assert_eq!(
+            vec![
+                Token::MapBegin(Some(2)),
+                Token::Str("x".into()),
+                Token::I32(1),
+                Token::Str("y".into()),
+                Token::I32(2),
+                Token::MapEnd,
+            ],
+            test::tokens(&Point { x: 1, y: 2 })
+        );
+    }
+}
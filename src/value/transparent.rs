@@ -0,0 +1,109 @@
+use crate::{
+    std::fmt,
+    value::{
+        self,
+        Value,
+    },
+};
+
+/**
+A value that's tagged with the name of its type.
+
+The inner value is streamed between calls to [`Stream::typed_begin`]
+and [`Stream::typed_end`]. Streams that support tags, like those for
+formats with semantic tags, can use it to give the value a specific
+representation. Other streams ignore the tag and see the inner value.
+
+```
+# use sval::value::{self, Value};
+fn with_value(value: impl Value) {
+    // Do something with the value
+}
+
+with_value(value::Transparent::new("base64", "c3ZhbA=="));
+```
+
+[`Stream::typed_begin`]: struct.Stream.html#method.typed_begin
+[`Stream::typed_end`]: struct.Stream.html#method.typed_end
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Transparent<T> {
+    tag: &'static str,
+    value: T,
+}
+
+impl<T> Transparent<T>
+where
+    T: Value,
+{
+    /**
+    Tag a value with the name of its type.
+    */
+    pub fn new(tag: &'static str, value: T) -> Self {
+        Transparent { tag, value }
+    }
+
+    /**
+    Get the tag.
+    */
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /**
+    Get the inner value.
+    */
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Value for Transparent<T>
+where
+    T: Value,
+{
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.typed_begin(self.tag)?;
+        stream.any(&self.value)?;
+        stream.typed_end()
+    }
+}
+
+impl<T> fmt::Display for Transparent<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    mod alloc_support {
+        use crate::{
+            std::string::ToString,
+            test,
+            value::Transparent,
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_transparent() {
+            assert_eq!(
+                vec![test::Token::Str("c3ZhbA==".into())],
+                test::tokens(&Transparent::new("base64", "c3ZhbA=="))
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn transparent_display() {
+            assert_eq!("42", Transparent::new("int", 42).to_string());
+        }
+    }
+}
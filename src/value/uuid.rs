@@ -0,0 +1,135 @@
+use crate::value::{
+    self,
+    Value,
+};
+
+use uuid_lib::Uuid;
+
+/**
+The format to stream a [`Uuid`] in.
+
+Implementations of [`Value`] for `Uuid` use the default hyphenated format.
+Types that contain a `Uuid` can choose a different format when they're streamed:
+
+```
+# fn main() {
+# #[cfg(feature = "test")]
+# {
+# use uuid_lib as uuid;
+use sval::{
+    test::{self, Token},
+    value::{self, UuidFormat, Value},
+};
+use uuid::Uuid;
+
+struct Id(Uuid);
+
+impl Value for Id {
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        UuidFormat::Bytes.stream(&self.0, stream)
+    }
+}
+
+assert_eq!(
+    vec![Token::Bytes(vec![0; 16])],
+    test::tokens(&Id(Uuid::nil())),
+);
+# }
+# }
+```
+
+[`Uuid`]: https://docs.rs/uuid/1/uuid/struct.Uuid.html
+[`Value`]: trait.Value.html
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum UuidFormat {
+    /**
+    A hyphenated lowercase string, like `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    */
+    #[default]
+    Hyphenated,
+    /**
+    The 16 bytes of the uuid, in big-endian order.
+    */
+    Bytes,
+}
+
+impl UuidFormat {
+    /**
+    Stream a uuid in this format.
+    */
+    pub fn stream<'s, 'v>(
+        self,
+        uuid: &'v Uuid,
+        mut stream: value::Stream<'s, 'v>,
+    ) -> value::Result {
+        match self {
+            UuidFormat::Hyphenated => {
+                // Uuids can be formatted into a buffer without allocating
+                let mut buf = Uuid::encode_buffer();
+
+                stream.owned().str(uuid.hyphenated().encode_lower(&mut buf))
+            }
+            UuidFormat::Bytes => stream.bytes(uuid.as_bytes()),
+        }
+    }
+}
+
+impl Value for Uuid {
+    fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+        UuidFormat::default().stream(self, stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    mod alloc_support {
+        use crate::{
+            std::vec,
+            test::{
+                self,
+                Token,
+            },
+            value::{
+                self,
+                UuidFormat,
+                Value,
+            },
+        };
+
+        use uuid_lib::Uuid;
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        const UUID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+        struct AsBytes(Uuid);
+
+        impl Value for AsBytes {
+            fn stream<'s, 'v>(&'v self, stream: value::Stream<'s, 'v>) -> value::Result {
+                UuidFormat::Bytes.stream(&self.0, stream)
+            }
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_uuid() {
+            let uuid = Uuid::parse_str(UUID).unwrap();
+
+            assert_eq!(vec![Token::Str(UUID.into())], test::tokens(&uuid));
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn stream_uuid_bytes() {
+            let uuid = Uuid::parse_str(UUID).unwrap();
+
+            assert_eq!(
+                vec![Token::Bytes(uuid.as_bytes().to_vec())],
+                test::tokens(&AsBytes(uuid))
+            );
+        }
+    }
+}
@@ -0,0 +1,791 @@
+/*!
+XML support for `sval`.
+
+Add the `xml` feature to your `Cargo.toml` to enable this module:
+
+```toml,no_run
+[dependencies.sval]
+features = ["xml"]
+```
+
+# Writing XML to `io::Write`
+
+```
+# fn main() -> Result<(), sval::Error> {
+use std::collections::BTreeMap;
+
+let mut map = BTreeMap::new();
+map.insert("id", vec![1, 2]);
+map.insert("title", vec![]);
+
+let mut encoder = sval::xml::Encoder::new(Vec::new());
+sval::stream(&mut encoder, &map)?;
+
+assert_eq!(
+    "<value><id>1</id><id>2</id></value>",
+    String::from_utf8(encoder.into_inner()).unwrap()
+);
+# Ok(())
+# }
+```
+
+# Mapping values to XML
+
+XML has a different structure to `sval`'s data model, so encoding
+values as XML is lossy:
+
+- The value being streamed is written as an element called `value`.
+- Primitives are written as the text content of their element.
+  Empty values are written as empty elements.
+- Maps are written as elements with a child element for each entry,
+  named after the entry's key. Keys must be primitives that are valid XML names.
+- Sequences in maps are written as a repeated child element for each item,
+  named after the entry's key. Other sequences are written as an element
+  with a child element called `item` for each item.
+
+The names of elements, namespaces, and whether primitive map values
+are written as attributes can be changed using an [`XmlConfig`].
+*/
+
+use crate::std::{
+    fmt::Write as _,
+    io::Write,
+    mem,
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+
+use crate::stream::{
+    self,
+    Stream,
+};
+
+/**
+Configuration for how values are written as XML.
+*/
+#[derive(Debug, Clone)]
+pub struct XmlConfig {
+    root: String,
+    item: String,
+    namespace: Option<(String, String)>,
+    attributes: bool,
+}
+
+impl Default for XmlConfig {
+    fn default() -> Self {
+        XmlConfig {
+            root: "value".into(),
+            item: "item".into(),
+            namespace: None,
+            attributes: false,
+        }
+    }
+}
+
+impl XmlConfig {
+    /**
+    Create a default configuration.
+    */
+    pub fn new() -> Self {
+        XmlConfig::default()
+    }
+
+    /**
+    Set the name of the element for the value being streamed.
+
+    The default name is `value`.
+    */
+    pub fn root(mut self, name: impl Into<String>) -> Self {
+        self.root = name.into();
+        self
+    }
+
+    /**
+    Set the name of the elements for items in sequences that aren't map values.
+
+    The default name is `item`.
+    */
+    pub fn item(mut self, name: impl Into<String>) -> Self {
+        self.item = name.into();
+        self
+    }
+
+    /**
+    Prefix the names of all elements with a namespace.
+
+    The namespace is declared on the root element.
+    */
+    pub fn namespace(mut self, prefix: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.namespace = Some((prefix.into(), uri.into()));
+        self
+    }
+
+    /**
+    Whether to write primitive map values as attributes.
+
+    Only entries that come before any child elements can be written
+    as attributes. Others are written as child elements.
+    Attributes aren't written by default.
+    */
+    pub fn attributes(mut self, attributes: bool) -> Self {
+        self.attributes = attributes;
+        self
+    }
+}
+
+/**
+A stream for writing structured data as XML.
+
+The stream internally wraps a [`std::io::Write`].
+*/
+pub struct Encoder<W> {
+    config: XmlConfig,
+    stack: Vec<Frame>,
+    next: Next,
+    out: W,
+}
+
+enum Frame {
+    // A map element, and whether its start tag is still open for attributes
+    Map {
+        name: String,
+        open: bool,
+        key: Option<String>,
+    },
+    // A sequence, with the name of its items and its wrapping element, if any
+    Seq {
+        item: String,
+        wrapper: Option<String>,
+    },
+}
+
+enum Next {
+    // The next value is written as an element with the given name
+    Value {
+        name: String,
+        attribute: bool,
+        wrap_seq: bool,
+    },
+    // The next value is a map key
+    Key,
+    // No value is expected
+    None,
+}
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    /**
+    Create a new XML stream with the default configuration.
+    */
+    pub fn new(out: W) -> Self {
+        Encoder::with_config(out, XmlConfig::default())
+    }
+
+    /**
+    Create a new XML stream with the given configuration.
+    */
+    pub fn with_config(out: W, config: XmlConfig) -> Self {
+        Encoder {
+            next: Next::Value {
+                name: config.root.clone(),
+                attribute: false,
+                wrap_seq: true,
+            },
+            config,
+            stack: Vec::new(),
+            out,
+        }
+    }
+
+    /**
+    Get the inner writer back out of the stream without ensuring it's valid.
+    */
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn write(&mut self, v: &str) -> stream::Result {
+        self.out.write_all(v.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn write_escaped(&mut self, v: &str, attribute: bool) -> stream::Result {
+        let mut start = 0;
+
+        for (i, c) in v.char_indices() {
+            let escaped = match c {
+                '&' => "&amp;",
+                '<' => "&lt;",
+                '>' => "&gt;",
+                '"' if attribute => "&quot;",
+                _ => continue,
+            };
+
+            self.write(&v[start..i])?;
+            self.write(escaped)?;
+            start = i + 1;
+        }
+
+        self.write(&v[start..])
+    }
+
+    fn qualify(&self, name: &str) -> String {
+        match self.config.namespace {
+            Some((ref prefix, _)) => {
+                let mut qualified = String::with_capacity(prefix.len() + name.len() + 1);
+                qualified.push_str(prefix);
+                qualified.push(':');
+                qualified.push_str(name);
+
+                qualified
+            }
+            None => name.into(),
+        }
+    }
+
+    fn take_value(&mut self) -> Result<(String, bool, bool), crate::Error> {
+        match mem::replace(&mut self.next, Next::None) {
+            Next::Value {
+                name,
+                attribute,
+                wrap_seq,
+            } => Ok((self.qualify(&name), attribute, wrap_seq)),
+            Next::Key => Err(crate::Error::msg("map keys must be primitives")),
+            Next::None => Err(crate::Error::msg("a value isn't expected here")),
+        }
+    }
+
+    /**
+    Close the start tag of the parent map so child elements can be written.
+    */
+    fn close_parent(&mut self) -> stream::Result {
+        if let Some(Frame::Map { ref mut open, .. }) = self.stack.last_mut() {
+            if *open {
+                *open = false;
+                return self.write(">");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_tag(&mut self, name: &str) -> stream::Result {
+        self.close_parent()?;
+
+        self.write("<")?;
+        self.write(name)?;
+
+        if self.stack.is_empty() {
+            if let Some((prefix, uri)) = self.config.namespace.clone() {
+                self.write(" xmlns:")?;
+                self.write(&prefix)?;
+                self.write("=\"")?;
+                self.write_escaped(&uri, true)?;
+                self.write("\"")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn primitive(&mut self, v: Option<&str>) -> stream::Result {
+        if let Next::Key = self.next {
+            self.next = Next::None;
+
+            return match (self.stack.last_mut(), v) {
+                (Some(Frame::Map { key, .. }), Some(v)) if is_name(v) => {
+                    *key = Some(v.into());
+
+                    Ok(())
+                }
+                _ => Err(crate::Error::msg(
+                    "map keys must be primitives that are valid XML names",
+                )),
+            };
+        }
+
+        let (name, attribute, _) = self.take_value()?;
+
+        let parent_open = match self.stack.last() {
+            Some(Frame::Map { open, .. }) => *open,
+            _ => false,
+        };
+
+        if attribute && parent_open && self.config.attributes {
+            if let Some(v) = v {
+                self.write(" ")?;
+                self.write(&name)?;
+                self.write("=\"")?;
+                self.write_escaped(v, true)?;
+                self.write("\"")?;
+            }
+
+            return Ok(());
+        }
+
+        self.start_tag(&name)?;
+
+        match v {
+            Some(v) if !v.is_empty() => {
+                self.write(">")?;
+                self.write_escaped(v, false)?;
+                self.write("</")?;
+                self.write(&name)?;
+                self.write(">")
+            }
+            _ => self.write("/>"),
+        }
+    }
+
+    fn display(&mut self, v: impl ToString) -> stream::Result {
+        self.primitive(Some(&v.to_string()))
+    }
+}
+
+fn is_name(v: &str) -> bool {
+    let mut chars = v.chars();
+
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => (),
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+impl<'v, W> Stream<'v> for Encoder<W>
+where
+    W: Write,
+{
+    fn hint_skip(&self) -> bool {
+        false
+    }
+
+    fn fmt(&mut self, v: stream::Arguments) -> stream::Result {
+        self.display(v)
+    }
+
+    fn fmt_borrowed(&mut self, v: stream::Arguments<'v>) -> stream::Result {
+        self.fmt(v)
+    }
+
+    fn error(&mut self, v: stream::Source) -> stream::Result {
+        self.fmt(stream::Arguments::display(&v))
+    }
+
+    fn error_borrowed(&mut self, v: stream::Source<'v>) -> stream::Result {
+        self.error(v)
+    }
+
+    fn error_str(&mut self, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn i64(&mut self, v: i64) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u64(&mut self, v: u64) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u8(&mut self, v: u8) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u16(&mut self, v: u16) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u32(&mut self, v: u32) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i8(&mut self, v: i8) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i16(&mut self, v: i16) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i32(&mut self, v: i32) -> stream::Result {
+        self.display(v)
+    }
+
+    fn i128(&mut self, v: i128) -> stream::Result {
+        self.display(v)
+    }
+
+    fn u128(&mut self, v: u128) -> stream::Result {
+        self.display(v)
+    }
+
+    fn f64(&mut self, v: f64) -> stream::Result {
+        self.display(v)
+    }
+
+    fn f32(&mut self, v: f32) -> stream::Result {
+        self.display(v)
+    }
+
+    fn bool(&mut self, v: bool) -> stream::Result {
+        self.display(v)
+    }
+
+    fn char(&mut self, v: char) -> stream::Result {
+        self.str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn str(&mut self, v: &str) -> stream::Result {
+        self.primitive(Some(v))
+    }
+
+    fn typed_str(&mut self, _: &str, v: &str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn typed_begin(&mut self, _: &str) -> stream::Result {
+        Ok(())
+    }
+
+    fn typed_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn tagged_begin(&mut self, tag: &str) -> stream::Result {
+        self.typed_begin(tag)
+    }
+
+    fn tagged_end(&mut self, _: &str) -> stream::Result {
+        self.typed_end()
+    }
+
+    fn str_borrowed(&mut self, v: &'v str) -> stream::Result {
+        self.str(v)
+    }
+
+    fn str_owned(&mut self, v: String) -> stream::Result {
+        self.str(&v)
+    }
+
+    fn bytes(&mut self, v: &[u8]) -> stream::Result {
+        // Bytes are written as hex
+        let mut hex = String::with_capacity(v.len() * 2);
+        for b in v {
+            let _ = write!(hex, "{:02x}", b);
+        }
+
+        self.str(&hex)
+    }
+
+    fn bytes_borrowed(&mut self, v: &'v [u8]) -> stream::Result {
+        self.bytes(v)
+    }
+
+    fn none(&mut self) -> stream::Result {
+        self.primitive(None)
+    }
+
+    fn map_begin(&mut self, _: Option<usize>) -> stream::Result {
+        let (name, _, _) = self.take_value()?;
+
+        self.start_tag(&name)?;
+        self.stack.push(Frame::Map {
+            name,
+            open: true,
+            key: None,
+        });
+
+        Ok(())
+    }
+
+    fn map_begin_unknown(&mut self) -> stream::Result {
+        self.map_begin(None)
+    }
+
+    fn map_key(&mut self) -> stream::Result {
+        match self.stack.last() {
+            Some(Frame::Map { .. }) => {
+                self.next = Next::Key;
+
+                Ok(())
+            }
+            _ => Err(crate::Error::msg("a map key isn't expected here")),
+        }
+    }
+
+    fn map_key_collect(&mut self, k: stream::Value) -> stream::Result {
+        self.map_key()?;
+        k.stream(self)
+    }
+
+    fn map_key_collect_borrowed(&mut self, k: stream::Value<'v>) -> stream::Result {
+        self.map_key_collect(k)
+    }
+
+    fn map_value(&mut self) -> stream::Result {
+        match self.stack.last_mut() {
+            Some(Frame::Map { key, .. }) => match key.take() {
+                Some(name) => {
+                    self.next = Next::Value {
+                        name,
+                        attribute: true,
+                        wrap_seq: false,
+                    };
+
+                    Ok(())
+                }
+                None => Err(crate::Error::msg("a map value must follow a map key")),
+            },
+            _ => Err(crate::Error::msg("a map value isn't expected here")),
+        }
+    }
+
+    fn map_value_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.map_value()?;
+        v.stream(self)
+    }
+
+    fn map_value_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect(&mut self, k: stream::Value, v: stream::Value) -> stream::Result {
+        self.map_key_collect(k)?;
+        self.map_value_collect(v)
+    }
+
+    fn map_flat_entry_collect_borrowed(
+        &mut self,
+        k: stream::Value<'v>,
+        v: stream::Value<'v>,
+    ) -> stream::Result {
+        self.map_flat_entry_collect(k, v)
+    }
+
+    fn map_end(&mut self) -> stream::Result {
+        match self.stack.pop() {
+            Some(Frame::Map { open: true, .. }) => self.write("/>"),
+            Some(Frame::Map { name, .. }) => {
+                self.write("</")?;
+                self.write(&name)?;
+                self.write(">")
+            }
+            _ => Err(crate::Error::msg("unexpected end of a map")),
+        }
+    }
+
+    fn seq_begin(&mut self, _: Option<usize>) -> stream::Result {
+        let (name, _, wrap_seq) = self.take_value()?;
+
+        if wrap_seq {
+            self.start_tag(&name)?;
+            self.write(">")?;
+
+            let item = self.config.item.clone();
+            self.stack.push(Frame::Seq {
+                item,
+                wrapper: Some(name),
+            });
+        } else {
+            // The items will be children of the map
+            self.close_parent()?;
+
+            let item = match self.config.namespace {
+                Some((ref prefix, _)) => name[prefix.len() + 1..].into(),
+                None => name,
+            };
+            self.stack.push(Frame::Seq {
+                item,
+                wrapper: None,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn seq_begin_unknown(&mut self) -> stream::Result {
+        self.seq_begin(None)
+    }
+
+    fn seq_elem(&mut self) -> stream::Result {
+        match self.stack.last() {
+            Some(Frame::Seq { item, .. }) => {
+                self.next = Next::Value {
+                    name: item.clone(),
+                    attribute: false,
+                    wrap_seq: true,
+                };
+
+                Ok(())
+            }
+            _ => Err(crate::Error::msg("a sequence element isn't expected here")),
+        }
+    }
+
+    fn seq_elem_end(&mut self) -> stream::Result {
+        Ok(())
+    }
+
+    fn seq_elem_collect(&mut self, v: stream::Value) -> stream::Result {
+        self.seq_elem()?;
+        v.stream(self)
+    }
+
+    fn seq_elem_collect_borrowed(&mut self, v: stream::Value<'v>) -> stream::Result {
+        self.seq_elem_collect(v)
+    }
+
+    fn seq_end(&mut self) -> stream::Result {
+        match self.stack.pop() {
+            Some(Frame::Seq {
+                wrapper: Some(name),
+                ..
+            }) => {
+                self.write("</")?;
+                self.write(&name)?;
+                self.write(">")
+            }
+            Some(Frame::Seq { wrapper: None, .. }) => Ok(()),
+            _ => Err(crate::Error::msg("unexpected end of a sequence")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        std::{
+            collections::BTreeMap,
+            vec,
+        },
+        value::{
+            self,
+            Value,
+        },
+    };
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn encode_with(v: impl Value, config: XmlConfig) -> String {
+        let mut encoder = Encoder::with_config(Vec::new(), config);
+        crate::stream(&mut encoder, &v).unwrap();
+
+        String::from_utf8(encoder.into_inner()).unwrap()
+    }
+
+    fn encode(v: impl Value) -> String {
+        encode_with(v, XmlConfig::default())
+    }
+
+    struct Data;
+
+    const TAGS: &[&str] = &["a", "b"];
+
+    impl Value for Data {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.map_begin(None)?;
+            stream.map_key(&"id")?;
+            stream.map_value(&1)?;
+            stream.map_key(&"tags")?;
+            stream.map_value(&TAGS)?;
+            stream.map_key(&"title")?;
+            stream.map_value(&"<sval>")?;
+            stream.map_end()
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_primitives() {
+        assert_eq!("<value>1</value>", encode(1));
+        assert_eq!("<value>true</value>", encode(true));
+        assert_eq!("<value>1.5</value>", encode(1.5));
+        assert_eq!("<value>a &amp; &lt;b&gt;</value>", encode("a & <b>"));
+        assert_eq!("<value/>", encode(""));
+        assert_eq!("<value/>", encode(Option::None::<i32>));
+        assert_eq!("<value>0aff</value>", encode(value::Bytes::new(&[10, 255])));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_map() {
+        assert_eq!(
+            "<value><id>1</id><tags>a</tags><tags>b</tags><title>&lt;sval&gt;</title></value>",
+            encode(Data)
+        );
+
+        assert_eq!("<value/>", encode(BTreeMap::<&str, i32>::new()));
+
+        let mut inner = BTreeMap::new();
+        inner.insert("b", 1);
+
+        let mut outer = BTreeMap::new();
+        outer.insert("a", inner);
+
+        assert_eq!("<value><a><b>1</b></a></value>", encode(outer));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_seq() {
+        assert_eq!(
+            "<value><item>1</item><item>2</item></value>",
+            encode(vec![1, 2])
+        );
+        assert_eq!(
+            "<value><item><item>1</item></item><item></item></value>",
+            encode(vec![vec![1], vec![]])
+        );
+        assert_eq!("<value></value>", encode(Vec::<i32>::new()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_config() {
+        assert_eq!(
+            "<data id=\"1\"><tags>a</tags><tags>b</tags><title>&lt;sval&gt;</title></data>",
+            encode_with(Data, XmlConfig::new().root("data").attributes(true))
+        );
+
+        assert_eq!(
+            "<s:values xmlns:s=\"urn:sval\"><s:v>1</s:v></s:values>",
+            encode_with(
+                vec![1],
+                XmlConfig::new()
+                    .root("values")
+                    .item("v")
+                    .namespace("s", "urn:sval")
+            )
+        );
+
+        assert_eq!(
+            "<s:value xmlns:s=\"urn:sval\"><s:id>1</s:id><s:tags>a</s:tags><s:tags>b</s:tags><s:title>&lt;sval&gt;</s:title></s:value>",
+            encode_with(Data, XmlConfig::new().namespace("s", "urn:sval"))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn encode_invalid_key() {
+        let mut map = BTreeMap::new();
+        map.insert("not a name", 1);
+
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &map).is_err());
+
+        let mut map = BTreeMap::new();
+        map.insert(1, 1);
+
+        assert!(crate::stream(&mut Encoder::new(Vec::new()), &map).is_err());
+    }
+}
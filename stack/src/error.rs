@@ -2,24 +2,78 @@ use crate::std::fmt;
 
 /**
 An error encountered while visiting a value.
+
+The error includes the operation that failed and,
+when it's known, the depth of the stack at the point of failure.
 */
-pub struct Error(&'static dyn fmt::Display);
+pub struct Error {
+    msg: &'static dyn fmt::Display,
+    depth: Option<usize>,
+}
 
 impl Error {
     /** Capture a static message as an error. */
     pub(crate) fn custom(msg: &'static dyn fmt::Display) -> Self {
-        Error(msg)
+        Error { msg, depth: None }
+    }
+
+    /** Attach the depth of the stack the error happened at. */
+    pub(crate) fn at_depth(self, depth: usize) -> Self {
+        Error {
+            depth: Some(depth),
+            ..self
+        }
+    }
+
+    /**
+    The depth of the stack when the error happened, if it's known.
+
+    The root of the stream has a depth of `0`.
+    */
+    pub fn depth(&self) -> Option<usize> {
+        self.depth
     }
 }
 
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
+        fmt::Display::fmt(self, f)
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(f)
+        self.msg.fmt(f)?;
+
+        if let Some(depth) = self.depth {
+            write!(f, " at depth {}", depth)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_display() {
+        #[cfg(feature = "alloc")]
+        {
+            use crate::alloc_lib::string::ToString;
+
+            assert_eq!(
+                "unexpected map key",
+                Error::custom(&"unexpected map key").to_string()
+            );
+            assert_eq!(
+                "unexpected map key at depth 2",
+                Error::custom(&"unexpected map key").at_depth(2).to_string()
+            );
+        }
+
+        assert_eq!(Some(2), Error::custom(&"").at_depth(2).depth());
+        assert_eq!(None, Error::custom(&"").depth());
     }
 }
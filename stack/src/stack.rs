@@ -252,7 +252,7 @@ impl Stack {
     - `Option<T>`.
     */
     pub fn primitive(&mut self) -> Result<Pos, crate::Error> {
-        let mut curr = self.inner.current_mut();
+        let curr = self.inner.current_mut();
 
         // The current slot must:
         // - not be done
@@ -263,7 +263,10 @@ impl Stack {
 
                 Ok(curr.pos(self.inner.depth()))
             }
-            _ => Err(crate::Error::custom(expecting!(*curr, "primitive"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(*curr, "primitive"))
+                    .at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -289,7 +292,10 @@ impl Stack {
 
                 Ok(curr.pos(self.inner.depth()))
             }
-            _ => Err(crate::Error::custom(expecting!(curr, "map begin"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(curr, "map begin"))
+                    .at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -300,7 +306,7 @@ impl Stack {
     that follows it.
     */
     pub fn map_key(&mut self) -> Result<Pos, crate::Error> {
-        let mut curr = self.inner.current_mut();
+        let curr = self.inner.current_mut();
 
         // The current slot must:
         // - be a fresh map (with no key or value) or
@@ -312,7 +318,9 @@ impl Stack {
 
                 Ok(curr.pos(self.inner.depth()))
             }
-            _ => Err(crate::Error::custom(expecting!(*curr, "map key"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(*curr, "map key")).at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -323,7 +331,7 @@ impl Stack {
     that follows it.
     */
     pub fn map_value(&mut self) -> Result<Pos, crate::Error> {
-        let mut curr = self.inner.current_mut();
+        let curr = self.inner.current_mut();
 
         // The current slot must:
         // - be a map with a done key
@@ -334,7 +342,10 @@ impl Stack {
 
                 Ok(curr.pos(self.inner.depth()))
             }
-            _ => Err(crate::Error::custom(expecting!(*curr, "map value"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(*curr, "map value"))
+                    .at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -357,12 +368,14 @@ impl Stack {
                     self.inner.pop_depth();
                 }
 
-                let mut curr = self.inner.current_mut();
+                let curr = self.inner.current_mut();
                 curr.0 |= Slot::DONE;
 
                 Ok(curr.pos(self.inner.depth() + 1))
             }
-            _ => Err(crate::Error::custom(expecting!(curr, "map end"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(curr, "map end")).at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -388,7 +401,10 @@ impl Stack {
 
                 Ok(curr.pos(self.inner.depth()))
             }
-            _ => Err(crate::Error::custom(expecting!(curr, "seq begin"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(curr, "seq begin"))
+                    .at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -399,7 +415,7 @@ impl Stack {
     that follows it.
     */
     pub fn seq_elem(&mut self) -> Result<Pos, crate::Error> {
-        let mut curr = self.inner.current_mut();
+        let curr = self.inner.current_mut();
 
         // The current slot must:
         // - be a fresh sequence (with no element) or
@@ -411,7 +427,10 @@ impl Stack {
 
                 Ok(curr.pos(self.inner.depth()))
             }
-            _ => Err(crate::Error::custom(expecting!(*curr, "seq elem"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(*curr, "seq elem"))
+                    .at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -434,12 +453,14 @@ impl Stack {
                     self.inner.pop_depth();
                 }
 
-                let mut curr = self.inner.current_mut();
+                let curr = self.inner.current_mut();
                 curr.0 |= Slot::DONE;
 
                 Ok(curr.pos(self.inner.depth() + 1))
             }
-            _ => Err(crate::Error::custom(expecting!(curr, "seq end"))),
+            _ => {
+                Err(crate::Error::custom(expecting!(curr, "seq end")).at_depth(self.inner.depth()))
+            }
         }
     }
 
@@ -468,7 +489,7 @@ impl Stack {
 
             Ok(())
         } else {
-            Err(crate::Error::custom(&"stack is not empty"))
+            Err(crate::Error::custom(&"stack is not empty").at_depth(self.inner.depth()))
         }
     }
 }
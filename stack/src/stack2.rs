@@ -1,5 +1,27 @@
 /*!
 A fixed-size, stateful stack for streams.
+
+# Maximum depth
+
+Each level of nesting is tracked in a 5 bit slot that records whether
+it's a map or sequence and which item it expects next. Slots are packed
+into a single `u64`, so 12 levels of nesting fit inline without
+allocating.
+
+The maximum depth of a [`DynStack`] is given by [`DynStack::MAX_DEPTH`].
+With the `alloc` feature, stacks deeper than 12 levels spill their
+slots onto the heap, so `MAX_DEPTH` is the `DEPTH` parameter.
+Without `alloc`, `MAX_DEPTH` is capped at 12.
+The default [`Stack`] supports 12 levels of nesting:
+
+```
+use sval_stack::stack2::Stack;
+
+assert_eq!(12, Stack::MAX_DEPTH);
+```
+
+Beginning a map or sequence that would exceed the maximum depth
+returns an error.
 */
 
 /*
@@ -14,23 +36,14 @@ is maintained, but any changes here should be
 reviewed carefully.
 */
 
-use crate::std::fmt;
-
-struct Expecting {
-    expecting: &'static str,
-}
-
-impl fmt::Display for Expecting {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "expecting {}", self.expecting)
-    }
-}
+#[cfg(feature = "alloc")]
+use crate::std::vec::Vec;
 
 /**
 The expected position in the stream.
 */
 #[derive(Clone)]
-pub struct Pos(RawStack, u8);
+pub struct Pos(RawStack, usize);
 
 /**
 The depth of a position.
@@ -41,7 +54,7 @@ to have the same depth or greater.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Depth(usize);
 
-#[cfg(all(feature = "alloc", any(test, feature = "test")))]
+#[cfg(all(feature = "alloc", test))]
 impl Depth {
     pub fn root() -> Self {
         Depth(0)
@@ -67,7 +80,7 @@ impl Pos {
     Whether the current position is a sequence element.
     */
     pub fn is_elem(&self) -> bool {
-        (self.0 as u8) & Slot::MASK_SLOT == Slot::IS_SEQ_ELEM
+        (self.0 as u8) & Slot::MASK_SLOT & !Slot::IN_SEQ_ELEM == Slot::IS_SEQ_ELEM
     }
 
     /**
@@ -77,6 +90,27 @@ impl Pos {
         (self.0 as u8) & Slot::MASK_VALUE_ELEM != 0
     }
 
+    /**
+    Whether the current position is at the root of the stream.
+    */
+    pub fn is_root(&self) -> bool {
+        self.1 == 0
+    }
+
+    /**
+    Whether the current position is within a map.
+    */
+    pub fn is_map(&self) -> bool {
+        (self.0 as u8) & Slot::MASK_MAP != 0
+    }
+
+    /**
+    Whether the current position is within a sequence.
+    */
+    pub fn is_seq(&self) -> bool {
+        (self.0 as u8) & Slot::MASK_SEQ != 0
+    }
+
     /**
     Whether the current position is an empty map.
     */
@@ -95,7 +129,7 @@ impl Pos {
     The depth of this position.
     */
     pub fn depth(&self) -> Depth {
-        Depth(self.1 as usize)
+        Depth(self.1)
     }
 }
 
@@ -107,10 +141,12 @@ impl Slot {
     const IS_MAP_VALUE: u8 = 0b0000_0110;
     const IS_SEQ_ELEM: u8 = 0b0000_1000;
 
-    #[allow(dead_code)]
-    const RESERVED: u8 = 0b0001_0000;
+    // Set between `seq_elem` and `seq_elem_end`
+    const IN_SEQ_ELEM: u8 = 0b0001_0000;
 
     const MASK_VALUE_ELEM: u8 = 0b0000_1100;
+    const MASK_MAP: u8 = Slot::NEEDS_MAP_KEY | Slot::NEEDS_MAP_VALUE;
+    const MASK_SEQ: u8 = Slot::NEEDS_SEQ_ELEM;
 
     const NEEDS_ITEM: u8 = 0b0000_0001;
     const NEEDS_MAP_KEY: u8 = 0b0000_0100;
@@ -124,7 +160,7 @@ impl Slot {
     const BITS: u8 = 5;
 }
 
-impl Default for Stack {
+impl<const DEPTH: usize> Default for DynStack<DEPTH> {
     fn default() -> Self {
         Self::new()
     }
@@ -132,14 +168,40 @@ impl Default for Stack {
 
 type RawStack = u64;
 
+/**
+A stack with the default maximum depth.
+*/
+pub type Stack = DynStack<12>;
+
+/**
+A stack that supports up to `DEPTH` levels of nesting.
+
+Up to 12 levels of nesting are stored inline. Deeper stacks spill
+onto the heap when the `alloc` feature is enabled. Without `alloc`,
+the maximum depth is capped at 12 regardless of `DEPTH`.
+*/
 #[derive(Clone)]
-pub struct Stack {
+pub struct DynStack<const DEPTH: usize> {
     inner: RawStack,
-    depth: u8,
+    depth: usize,
+    #[cfg(feature = "alloc")]
+    spilled: Vec<RawStack>,
 }
 
-impl Stack {
-    const MAX_DEPTH: u8 = Self::BITS / Slot::BITS;
+impl<const DEPTH: usize> DynStack<DEPTH> {
+    const INLINE_DEPTH: usize = (Self::BITS / Slot::BITS) as usize;
+
+    /**
+    The maximum depth of nesting supported by this stack.
+
+    This is `DEPTH` when the `alloc` feature is enabled, and
+    at most 12 otherwise.
+    */
+    pub const MAX_DEPTH: usize = if cfg!(feature = "alloc") || DEPTH < Self::INLINE_DEPTH {
+        DEPTH
+    } else {
+        Self::INLINE_DEPTH
+    };
 
     const BITS: u8 = RawStack::BITS as u8;
 
@@ -150,9 +212,11 @@ impl Stack {
     Create a new stack.
     */
     pub fn new() -> Self {
-        Stack {
+        DynStack {
             inner: Slot::NEEDS_ITEM as RawStack,
             depth: 0,
+            #[cfg(feature = "alloc")]
+            spilled: Vec::new(),
         }
     }
 
@@ -162,7 +226,46 @@ impl Stack {
     Any state it currently contains will be lost.
     */
     pub fn clear(&mut self) {
-        *self = Stack::new();
+        *self = DynStack::new();
+    }
+
+    fn push_depth(&mut self, expect: RawStack) {
+        #[cfg(feature = "alloc")]
+        {
+            // If the inline stack is full then move it to the heap
+            // The current slot no longer needs an item once its
+            // map or sequence has begun
+            if self.depth > 0 && self.depth % Self::INLINE_DEPTH == 0 {
+                self.spilled
+                    .push(self.inner & !(Slot::NEEDS_ITEM as RawStack));
+                self.inner = expect;
+                self.depth += 1;
+
+                return;
+            }
+        }
+
+        self.inner = (self.inner << Slot::BITS) & Self::MASK_SLOT_BEGIN | expect;
+        self.depth += 1;
+    }
+
+    fn pop_depth(&mut self) {
+        #[cfg(feature = "alloc")]
+        {
+            // If the inline stack is about to be emptied then
+            // restore the last one from the heap
+            if self.depth % Self::INLINE_DEPTH == 1 {
+                if let Some(spilled) = self.spilled.pop() {
+                    self.inner = spilled;
+                    self.depth -= 1;
+
+                    return;
+                }
+            }
+        }
+
+        self.inner >>= Slot::BITS;
+        self.depth -= 1;
     }
 
     /**
@@ -188,7 +291,7 @@ impl Stack {
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"a primitive"))
+            Err(crate::Error::custom(&"unexpected primitive").at_depth(self.depth))
         }
     }
 
@@ -203,16 +306,17 @@ impl Stack {
         const EXPECT: RawStack = (Slot::NEEDS_MAP_KEY | Slot::NEEDS_MAP_VALUE) as RawStack;
 
         if self.depth == Self::MAX_DEPTH {
-            return Err(crate::Error::custom(&"more depth at the start of a map"));
+            return Err(
+                crate::Error::custom(&"too deeply nested start of a map").at_depth(self.depth)
+            );
         }
 
         if self.inner as u8 & MASK == VALID {
-            self.inner = (self.inner << Slot::BITS) & Self::MASK_SLOT_BEGIN | EXPECT;
-            self.depth += 1;
+            self.push_depth(EXPECT);
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"the start of a map"))
+            Err(crate::Error::custom(&"unexpected start of a map").at_depth(self.depth))
         }
     }
 
@@ -232,7 +336,7 @@ impl Stack {
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"a map key"))
+            Err(crate::Error::custom(&"unexpected map key").at_depth(self.depth))
         }
     }
 
@@ -252,7 +356,7 @@ impl Stack {
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"a map value"))
+            Err(crate::Error::custom(&"unexpected map value").at_depth(self.depth))
         }
     }
 
@@ -264,12 +368,11 @@ impl Stack {
         const VALID: u8 = Slot::NEEDS_MAP_KEY | Slot::NEEDS_MAP_VALUE;
 
         if self.inner as u8 & MASK == VALID {
-            self.inner >>= Slot::BITS;
-            self.depth -= 1;
+            self.pop_depth();
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"the end of a map"))
+            Err(crate::Error::custom(&"unexpected end of a map").at_depth(self.depth))
         }
     }
 
@@ -284,18 +387,17 @@ impl Stack {
         const EXPECT: RawStack = (Slot::NEEDS_SEQ_ELEM) as RawStack;
 
         if self.depth == Self::MAX_DEPTH {
-            return Err(crate::Error::custom(
-                &"more depth at the start of a sequence",
-            ));
+            return Err(
+                crate::Error::custom(&"too deeply nested start of a sequence").at_depth(self.depth),
+            );
         }
 
         if self.inner as u8 & MASK == VALID {
-            self.inner = (self.inner << Slot::BITS) & Self::MASK_SLOT_BEGIN | EXPECT;
-            self.depth += 1;
+            self.push_depth(EXPECT);
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"the start of a sequence"))
+            Err(crate::Error::custom(&"unexpected start of a sequence").at_depth(self.depth))
         }
     }
 
@@ -306,16 +408,37 @@ impl Stack {
     that follows it.
     */
     pub fn seq_elem(&mut self) -> Result<Pos, crate::Error> {
-        const MASK: u8 = Slot::MASK_SLOT;
+        // The previous element doesn't need to be explicitly ended
+        const MASK: u8 = Slot::MASK_SLOT & !Slot::IN_SEQ_ELEM;
         const VALID: u8 = Slot::NEEDS_SEQ_ELEM;
-        const EXPECT: RawStack = Slot::NEEDS_ITEM as RawStack;
+        const EXPECT: RawStack = (Slot::NEEDS_ITEM | Slot::IN_SEQ_ELEM) as RawStack;
 
         if self.inner as u8 & MASK == VALID {
             self.inner |= EXPECT;
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"a sequence element"))
+            Err(crate::Error::custom(&"unexpected sequence element").at_depth(self.depth))
+        }
+    }
+
+    /**
+    Complete the current sequence element.
+
+    Ending an element is optional, but if it's ended then
+    the value that follows it must have been completed.
+    */
+    pub fn seq_elem_end(&mut self) -> Result<Pos, crate::Error> {
+        const MASK: u8 = Slot::MASK_SLOT;
+        const VALID: u8 = Slot::NEEDS_SEQ_ELEM | Slot::IN_SEQ_ELEM;
+        const EXPECT: RawStack = Slot::IN_SEQ_ELEM as RawStack;
+
+        if self.inner as u8 & MASK == VALID {
+            self.inner ^= EXPECT;
+
+            Ok(Pos(self.inner, self.depth))
+        } else {
+            Err(crate::Error::custom(&"unexpected end of a sequence element").at_depth(self.depth))
         }
     }
 
@@ -323,16 +446,15 @@ impl Stack {
     Complete the current sequence.
     */
     pub fn seq_end(&mut self) -> Result<Pos, crate::Error> {
-        const MASK: u8 = Slot::MASK_SLOT;
+        const MASK: u8 = Slot::MASK_SLOT & !Slot::IN_SEQ_ELEM;
         const VALID: u8 = Slot::NEEDS_SEQ_ELEM;
 
         if self.inner as u8 & MASK == VALID {
-            self.inner >>= Slot::BITS;
-            self.depth -= 1;
+            self.pop_depth();
 
             Ok(Pos(self.inner, self.depth))
         } else {
-            Err(crate::Error::custom(&"the end of a sequence"))
+            Err(crate::Error::custom(&"unexpected end of a sequence").at_depth(self.depth))
         }
     }
 
@@ -357,7 +479,480 @@ impl Stack {
         if self.can_end() {
             Ok(())
         } else {
-            Err(crate::Error::custom(&"the end of the stream"))
+            Err(crate::Error::custom(&"unexpected end of the stream").at_depth(self.depth))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    mod prop_test {
+        use super::*;
+
+        use crate::std::vec::Vec;
+
+        use quickcheck::{
+            quickcheck,
+            Arbitrary,
+            Gen,
+        };
+
+        type DeepStack = DynStack<64>;
+
+        #[derive(Clone, Copy, Debug)]
+        enum Command {
+            Primitive,
+            MapBegin,
+            MapKey,
+            MapValue,
+            MapEnd,
+            SeqBegin,
+            SeqElem,
+            SeqElemEnd,
+            SeqEnd,
+            End,
+        }
+
+        impl Arbitrary for Command {
+            fn arbitrary<G: Gen>(g: &mut G) -> Command {
+                match g.next_u32() % 10 {
+                    0 => Command::Primitive,
+                    1 => Command::MapBegin,
+                    2 => Command::MapKey,
+                    3 => Command::MapValue,
+                    4 => Command::MapEnd,
+                    5 => Command::SeqBegin,
+                    6 => Command::SeqElem,
+                    7 => Command::SeqElemEnd,
+                    8 => Command::SeqEnd,
+                    9 => Command::End,
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        impl Command {
+            fn apply(self, stack: &mut DeepStack) -> Result<(), crate::Error> {
+                match self {
+                    Command::Primitive => stack.primitive().map(|_| ()),
+                    Command::MapBegin => stack.map_begin().map(|_| ()),
+                    Command::MapKey => stack.map_key().map(|_| ()),
+                    Command::MapValue => stack.map_value().map(|_| ()),
+                    Command::MapEnd => stack.map_end().map(|_| ()),
+                    Command::SeqBegin => stack.seq_begin().map(|_| ()),
+                    Command::SeqElem => stack.seq_elem().map(|_| ()),
+                    Command::SeqElemEnd => stack.seq_elem_end().map(|_| ()),
+                    Command::SeqEnd => stack.seq_end().map(|_| ()),
+                    Command::End => stack.end(),
+                }
+            }
+        }
+
+        /**
+        A valid structure to stream through the stack.
+
+        Nodes are nested deeply enough to spill the stack onto the heap.
+        */
+        #[derive(Clone, Debug)]
+        enum Node {
+            Primitive,
+            Map(Vec<Node>),
+            Seq(Vec<Node>),
+        }
+
+        const MAX_NODE_DEPTH: usize = 30;
+
+        impl Node {
+            fn arbitrary_at<G: Gen>(g: &mut G, depth: usize) -> Node {
+                if depth >= MAX_NODE_DEPTH {
+                    return Node::Primitive;
+                }
+
+                // Favour nesting so deep stacks are generated
+                let len = (g.next_u32() % 3) as usize;
+                let children = (0..len).map(|_| Node::arbitrary_at(g, depth + 1)).collect();
+
+                match g.next_u32() % 5 {
+                    0 => Node::Primitive,
+                    1 | 2 => Node::Map(children),
+                    _ => Node::Seq(children),
+                }
+            }
+
+            fn commands(&self, commands: &mut Vec<Command>) {
+                match self {
+                    Node::Primitive => commands.push(Command::Primitive),
+                    Node::Map(entries) => {
+                        commands.push(Command::MapBegin);
+
+                        for value in entries {
+                            commands.push(Command::MapKey);
+                            commands.push(Command::Primitive);
+                            commands.push(Command::MapValue);
+                            value.commands(commands);
+                        }
+
+                        commands.push(Command::MapEnd);
+                    }
+                    Node::Seq(elems) => {
+                        commands.push(Command::SeqBegin);
+
+                        for (i, elem) in elems.iter().enumerate() {
+                            commands.push(Command::SeqElem);
+                            elem.commands(commands);
+
+                            // Elements may or may not be explicitly ended
+                            if i % 2 == 0 {
+                                commands.push(Command::SeqElemEnd);
+                            }
+                        }
+
+                        commands.push(Command::SeqEnd);
+                    }
+                }
+            }
+        }
+
+        impl Arbitrary for Node {
+            fn arbitrary<G: Gen>(g: &mut G) -> Node {
+                Node::arbitrary_at(g, 0)
+            }
+        }
+
+        quickcheck! {
+            fn stack_does_not_panic(cmd: Vec<Command>) -> bool {
+                let mut stack = DeepStack::new();
+
+                for cmd in cmd {
+                    let _ = cmd.apply(&mut stack);
+                }
+
+                // So long as the stack doesn't panic we're happy
+                true
+            }
+
+            fn stack_accepts_valid_structures(node: Node) -> bool {
+                let mut commands = Vec::new();
+                node.commands(&mut commands);
+
+                let mut stack = DeepStack::new();
+
+                for (i, cmd) in commands.iter().enumerate() {
+                    if cmd.apply(&mut stack).is_err() {
+                        return false;
+                    }
+
+                    // The stack can only end once every command has been applied
+                    if stack.can_end() != (i == commands.len() - 1) {
+                        return false;
+                    }
+                }
+
+                stack.end().is_ok()
+            }
+
+            fn stack_rejects_invalid_structures(node: Node, cmd: Command) -> bool {
+                let mut commands = Vec::new();
+                node.commands(&mut commands);
+
+                let mut stack = DeepStack::new();
+
+                for cmd in &commands {
+                    cmd.apply(&mut stack).unwrap();
+                }
+
+                // A complete stream can't accept anything else
+                cmd.apply(&mut stack).is_err() || matches!(cmd, Command::End)
+            }
+
+            fn stack_clear_resets_state(cmd: Vec<Command>, node: Node) -> bool {
+                let mut commands = Vec::new();
+                node.commands(&mut commands);
+
+                let mut stack = DeepStack::new();
+
+                for cmd in cmd {
+                    let _ = cmd.apply(&mut stack);
+                }
+
+                stack.clear();
+
+                // A cleared stack should behave exactly like a new one
+                let mut fresh = DeepStack::new();
+
+                for cmd in &commands {
+                    if cmd.apply(&mut stack).is_err() || cmd.apply(&mut fresh).is_err() {
+                        return false;
+                    }
+
+                    if stack.inner != fresh.inner || stack.depth != fresh.depth {
+                        return false;
+                    }
+                }
+
+                stack.end().is_ok()
+            }
+        }
+    }
+
+    #[test]
+    fn error_overflow_stack() {
+        let mut stack = DynStack::<2>::new();
+
+        stack.seq_begin().unwrap();
+        stack.seq_elem().unwrap();
+        stack.map_begin().unwrap();
+        stack.map_key().unwrap();
+
+        // The 3rd attempt to begin a map should fail
+        assert!(stack.map_begin().is_err());
+        assert!(stack.seq_begin().is_err());
+    }
+
+    #[test]
+    fn max_depth() {
+        fn check<const DEPTH: usize>() {
+            let mut stack = DynStack::<DEPTH>::new();
+
+            for _ in 0..DynStack::<DEPTH>::MAX_DEPTH {
+                stack.seq_begin().unwrap();
+                stack.seq_elem().unwrap();
+            }
+
+            assert!(stack.seq_begin().is_err());
+        }
+
+        assert_eq!(12, Stack::MAX_DEPTH);
+        check::<12>();
+
+        if cfg!(feature = "alloc") {
+            assert_eq!(64, DynStack::<64>::MAX_DEPTH);
+        } else {
+            assert_eq!(12, DynStack::<64>::MAX_DEPTH);
+        }
+        check::<64>();
+    }
+
+    #[test]
+    fn clear() {
+        type DeepStack = DynStack<40>;
+
+        // Nest as deeply as possible so the stack spills onto the heap
+        const ITERATIONS: usize = DeepStack::MAX_DEPTH / 2;
+
+        fn begin(stack: &mut DeepStack) {
+            for _ in 0..ITERATIONS {
+                stack.map_begin().unwrap();
+                stack.map_key().unwrap();
+                stack.primitive().unwrap();
+                stack.map_value().unwrap();
+
+                stack.seq_begin().unwrap();
+                stack.seq_elem().unwrap();
+            }
+        }
+
+        fn end(stack: &mut DeepStack) {
+            stack.primitive().unwrap();
+
+            for _ in 0..ITERATIONS {
+                stack.seq_end().unwrap();
+                stack.map_end().unwrap();
+            }
+
+            stack.end().unwrap();
+        }
+
+        fn assert_fresh(stack: &DeepStack) {
+            let fresh = DeepStack::new();
+
+            assert_eq!(fresh.inner, stack.inner);
+            assert_eq!(fresh.depth, stack.depth);
+
+            #[cfg(feature = "alloc")]
+            assert!(stack.spilled.is_empty());
+        }
+
+        let mut stack = DeepStack::new();
+
+        // Clear a stack after a complete value
+        begin(&mut stack);
+        end(&mut stack);
+
+        stack.clear();
+        assert_fresh(&stack);
+
+        // Clear a stack in the middle of a value
+        begin(&mut stack);
+
+        stack.clear();
+        assert_fresh(&stack);
+
+        // The cleared stack can stream a new value
+        begin(&mut stack);
+        end(&mut stack);
+    }
+
+    #[test]
+    fn pos_root() {
+        let mut stack = Stack::new();
+
+        assert!(stack.primitive().unwrap().is_root());
+
+        let mut stack = Stack::new();
+
+        assert!(!stack.seq_begin().unwrap().is_root());
+        assert!(stack.seq_end().unwrap().is_root());
+    }
+
+    #[test]
+    fn pos_map() {
+        let mut stack = Stack::new();
+
+        assert!(stack.map_begin().unwrap().is_map());
+        assert!(stack.map_key().unwrap().is_map());
+
+        let pos = stack.primitive().unwrap();
+        assert!(pos.is_map());
+        assert!(pos.is_key());
+        assert!(!pos.is_seq());
+
+        assert!(stack.map_value().unwrap().is_map());
+
+        let pos = stack.primitive().unwrap();
+        assert!(pos.is_map());
+        assert!(pos.is_value());
+        assert!(!pos.is_seq());
+
+        let pos = stack.map_end().unwrap();
+        assert!(!pos.is_map());
+        assert!(pos.is_root());
+    }
+
+    #[test]
+    fn pos_seq() {
+        let mut stack = Stack::new();
+
+        assert!(stack.seq_begin().unwrap().is_seq());
+        assert!(stack.seq_elem().unwrap().is_seq());
+
+        let pos = stack.primitive().unwrap();
+        assert!(pos.is_seq());
+        assert!(pos.is_elem());
+        assert!(!pos.is_map());
+
+        let pos = stack.seq_end().unwrap();
+        assert!(!pos.is_seq());
+        assert!(pos.is_root());
+    }
+
+    #[test]
+    fn seq_elem_end() {
+        let mut stack = Stack::new();
+
+        stack.seq_begin().unwrap();
+
+        // An element must begin before it can end
+        assert!(stack.seq_elem_end().is_err());
+
+        stack.seq_elem().unwrap();
+
+        // An element must be completed before it can end
+        assert!(stack.seq_elem_end().is_err());
+
+        stack.primitive().unwrap();
+
+        assert!(stack.seq_elem_end().unwrap().is_seq());
+
+        // An element can only end once
+        assert!(stack.seq_elem_end().is_err());
+
+        // Elements don't need to be explicitly ended
+        stack.seq_elem().unwrap();
+        assert!(stack.primitive().unwrap().is_elem());
+
+        stack.seq_end().unwrap();
+        stack.end().unwrap();
+    }
+
+    #[test]
+    fn seq_elem_end_nested() {
+        let mut stack = Stack::new();
+
+        stack.seq_begin().unwrap();
+        stack.seq_elem().unwrap();
+        stack.map_begin().unwrap();
+
+        // The map must be completed before the element can end
+        assert!(stack.seq_elem_end().is_err());
+
+        stack.map_end().unwrap();
+        stack.seq_elem_end().unwrap();
+
+        stack.seq_end().unwrap();
+        stack.end().unwrap();
+    }
+
+    #[test]
+    fn pos_nested() {
+        let mut stack = Stack::new();
+
+        stack.seq_begin().unwrap();
+        stack.seq_elem().unwrap();
+        stack.map_begin().unwrap();
+
+        let pos = stack.map_end().unwrap();
+        assert!(pos.is_seq());
+        assert!(!pos.is_map());
+        assert!(!pos.is_root());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn spill_deep_stack() {
+        let mut stack = DynStack::<40>::new();
+
+        for _ in 0..20 {
+            stack.map_begin().unwrap();
+            stack.map_key().unwrap();
+            stack.primitive().unwrap();
+            stack.map_value().unwrap();
+
+            stack.seq_begin().unwrap();
+            stack.seq_elem().unwrap();
+        }
+
+        assert!(stack.map_begin().is_err());
+        stack.primitive().unwrap();
+
+        for _ in 0..20 {
+            assert!(stack.seq_end().unwrap().depth() > Depth(0));
+            stack.map_end().unwrap();
         }
+
+        stack.end().unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn spill_deep_stack_mismatched_end() {
+        let mut stack = DynStack::<40>::new();
+
+        for _ in 0..13 {
+            stack.seq_begin().unwrap();
+            stack.seq_elem().unwrap();
+        }
+
+        stack.primitive().unwrap();
+
+        for _ in 0..13 {
+            assert!(stack.map_end().is_err());
+            stack.seq_end().unwrap();
+        }
+
+        stack.end().unwrap();
     }
 }
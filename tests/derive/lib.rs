@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+#[macro_use]
+extern crate sval;
+
+use sval::test::{
+    tokens,
+    Token,
+};
+
+#[test]
+fn derive_struct_named() {
+    #[derive(Value)]
+    struct Named {
+        a: i32,
+        #[sval(rename = "renamed")]
+        b: &'static str,
+    }
+
+    assert_eq!(
+        vec![
+            Token::MapBegin(Some(2)),
+            Token::Str("a".into()),
+            Token::I32(1),
+            Token::Str("renamed".into()),
+            Token::Str("b".into()),
+            Token::MapEnd,
+        ],
+        tokens(&Named { a: 1, b: "b" })
+    );
+}
+
+#[test]
+fn derive_struct_tuple() {
+    #[derive(Value)]
+    struct Tuple(i32, bool);
+
+    assert_eq!(
+        vec![
+            Token::SeqBegin(Some(2)),
+            Token::I32(1),
+            Token::Bool(true),
+            Token::SeqEnd,
+        ],
+        tokens(&Tuple(1, true))
+    );
+}
+
+#[test]
+fn derive_struct_unit() {
+    #[derive(Value)]
+    struct Unit;
+
+    assert_eq!(vec![Token::None], tokens(&Unit));
+}
+
+#[test]
+fn derive_struct_generic() {
+    #[derive(Value)]
+    struct Generic<T> {
+        a: T,
+    }
+
+    assert_eq!(
+        vec![
+            Token::MapBegin(Some(1)),
+            Token::Str("a".into()),
+            Token::Bool(false),
+            Token::MapEnd,
+        ],
+        tokens(&Generic { a: false })
+    );
+}
+
+#[test]
+fn derive_enum() {
+    #[derive(Value)]
+    enum Enum {
+        Unit,
+        Newtype(i32),
+        Tuple(i32, bool),
+        Named { a: i32 },
+    }
+
+    assert_eq!(vec![Token::Str("Unit".into())], tokens(&Enum::Unit));
+
+    assert_eq!(vec![Token::I32(1)], tokens(&Enum::Newtype(1)));
+
+    assert_eq!(
+        vec![
+            Token::SeqBegin(Some(2)),
+            Token::I32(1),
+            Token::Bool(true),
+            Token::SeqEnd,
+        ],
+        tokens(&Enum::Tuple(1, true))
+    );
+
+    assert_eq!(
+        vec![
+            Token::MapBegin(Some(1)),
+            Token::Str("a".into()),
+            Token::I32(1),
+            Token::MapEnd,
+        ],
+        tokens(&Enum::Named { a: 1 })
+    );
+}
+
+#[test]
+fn derive_enum_field_named_stream() {
+    #[derive(Value)]
+    enum Enum {
+        Named { stream: i32, other: i32 },
+    }
+
+    assert_eq!(
+        vec![
+            Token::MapBegin(Some(2)),
+            Token::Str("stream".into()),
+            Token::I32(1),
+            Token::Str("other".into()),
+            Token::I32(2),
+            Token::MapEnd,
+        ],
+        tokens(&Enum::Named {
+            stream: 1,
+            other: 2
+        })
+    );
+}
@@ -2,13 +2,17 @@
 
 extern crate sval;
 
-use std::fmt::{
-    self,
-    Debug,
+use std::{
+    cell::Cell,
+    fmt::{
+        self,
+        Debug,
+    },
 };
 
 use sval::value::{
     self,
+    Lazy,
     Value,
 };
 
@@ -237,3 +241,118 @@ fn sval_fmt_retains_flags() {
     check(OuterSeq);
     check(WeirdMapKeys);
 }
+
+#[test]
+fn sval_display_is_consistent_with_debug() {
+    fn check(value: (impl Value + Debug)) {
+        let display = format!("{}", sval::fmt::to_display(&value));
+        let debug = format!("{:?}", sval::fmt::to_display(&value));
+        let std = format!("{:?}", value);
+
+        assert_eq!(std, display);
+        assert_eq!(std, debug);
+    }
+
+    check(42);
+    check("a string");
+    check(OuterMap);
+    check(OuterSeq);
+    check(WeirdMapKeys);
+}
+
+#[test]
+fn sval_fmt_lazy() {
+    struct Skip;
+    impl<'v> sval::stream::Stream<'v> for Skip {
+        fn hint_skip(&self) -> bool {
+            true
+        }
+    }
+
+    let calls = Cell::new(0);
+    let value = Lazy::new(|| {
+        calls.set(calls.get() + 1);
+        OuterMap
+    });
+
+    sval::stream(&mut Skip, &value).unwrap();
+    assert_eq!(0, calls.get());
+
+    let sval = format!("{:?}", sval::fmt::to_debug(&value));
+    let std = format!("{:?}", OuterMap);
+
+    assert_eq!(std, sval);
+    assert_eq!(1, calls.get());
+}
+
+#[test]
+fn sval_fmt_typed_str() {
+    struct DateTime;
+
+    impl Value for DateTime {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.typed_str("date-time", "2020-01-01T00:00:00Z")
+        }
+    }
+
+    struct Typed(Option<String>);
+
+    impl<'v> sval::stream::Stream<'v> for Typed {
+        fn typed_str(&mut self, type_name: &str, _: &str) -> sval::stream::Result {
+            self.0 = Some(type_name.to_owned());
+
+            Ok(())
+        }
+    }
+
+    let mut typed = Typed(None);
+    sval::stream(&mut typed, &DateTime).unwrap();
+    assert_eq!(Some("date-time"), typed.0.as_deref());
+
+    // Streams that don't handle typed strings treat them as plain strings
+    assert_eq!(
+        format!("{:?}", "2020-01-01T00:00:00Z"),
+        format!("{:?}", sval::fmt::to_debug(&DateTime))
+    );
+}
+
+#[test]
+fn sval_fmt_transparent() {
+    #[derive(Default)]
+    struct Typed {
+        tags: Vec<String>,
+        ended: usize,
+    }
+
+    impl<'v> sval::stream::Stream<'v> for Typed {
+        fn typed_begin(&mut self, tag: &str) -> sval::stream::Result {
+            self.tags.push(tag.to_owned());
+
+            Ok(())
+        }
+
+        fn typed_end(&mut self) -> sval::stream::Result {
+            self.ended += 1;
+
+            Ok(())
+        }
+
+        fn str(&mut self, _: &str) -> sval::stream::Result {
+            Ok(())
+        }
+    }
+
+    let value = value::Transparent::new("base64", "c3ZhbA==");
+
+    let mut typed = Typed::default();
+    sval::stream(&mut typed, &value).unwrap();
+    assert_eq!(vec!["base64"], typed.tags);
+    assert_eq!(1, typed.ended);
+
+    // Streams that don't handle tags only see the inner value
+    assert_eq!(
+        format!("{:?}", "c3ZhbA=="),
+        format!("{:?}", sval::fmt::to_debug(&value))
+    );
+    assert_eq!("c3ZhbA==", value.to_string());
+}
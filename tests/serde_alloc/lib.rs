@@ -83,13 +83,13 @@ fn sval_derive() {
         vec![
             Token::MapBegin(Some(3)),
             Token::Str(String::from("a")),
-            Token::Signed(1),
+            Token::I32(1),
             Token::Str(String::from("b")),
-            Token::Signed(2),
+            Token::I32(2),
             Token::Str(String::from("renamed")),
             Token::MapBegin(Some(2)),
             Token::Str(String::from("a")),
-            Token::Signed(3),
+            Token::I32(3),
             Token::Str(String::from("b")),
             Token::Str(String::from("Hello!")),
             Token::MapEnd,
@@ -109,7 +109,7 @@ fn sval_derive_from_serde() {
             Token::MapBegin(Some(1)),
             Token::Str(String::from("NewType")),
             Token::SeqBegin(Some(1)),
-            Token::Signed(1),
+            Token::I32(1),
             Token::SeqEnd,
             Token::MapEnd,
         ],
@@ -130,7 +130,7 @@ fn serde_to_sval_tagged() {
             Token::MapBegin(Some(1)),
             Token::Str(String::from("NewType")),
             Token::SeqBegin(Some(1)),
-            Token::Signed(1),
+            Token::I32(1),
             Token::SeqEnd,
             Token::MapEnd,
         ],
@@ -143,8 +143,8 @@ fn serde_to_sval_tagged() {
             Token::MapBegin(Some(1)),
             Token::Str(String::from("Tuple")),
             Token::SeqBegin(Some(2)),
-            Token::Signed(1),
-            Token::Signed(2),
+            Token::I32(1),
+            Token::I32(2),
             Token::SeqEnd,
             Token::MapEnd,
         ],
@@ -158,9 +158,9 @@ fn serde_to_sval_tagged() {
             Token::Str(String::from("Struct")),
             Token::MapBegin(Some(2)),
             Token::Str(String::from("a")),
-            Token::Signed(1),
+            Token::I32(1),
             Token::Str(String::from("b")),
-            Token::Signed(2),
+            Token::I32(2),
             Token::MapEnd,
             Token::MapEnd,
         ],
@@ -178,14 +178,300 @@ fn sval_to_serde_anonymous() {
             Token::Map { len: None },
             Token::I64(1),
             Token::Map { len: None },
-            Token::I64(2),
+            Token::I32(2),
             Token::Seq { len: None },
             Token::I64(3),
             Token::SeqEnd,
             Token::MapEnd,
-            Token::I64(11),
-            Token::I64(111),
+            Token::I32(11),
+            Token::I32(111),
             Token::MapEnd,
         ],
     );
 }
+
+struct CompoundKey;
+
+impl Value for CompoundKey {
+    fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+        stream.map_begin(Some(1))?;
+
+        stream.map_key_begin()?.seq_begin(Some(2))?;
+
+        stream.seq_elem(&1)?;
+
+        stream.seq_elem_begin()?.map_begin(Some(1))?;
+
+        stream.map_key(&"a")?;
+
+        stream.map_value(&2)?;
+
+        stream.map_end()?;
+
+        stream.seq_end()?;
+
+        stream.map_value(&3)?;
+
+        stream.map_end()
+    }
+}
+
+#[test]
+fn sval_to_serde_compound_key() {
+    use self::SerdeToken as Token;
+
+    assert_ser_tokens(
+        &sval::serde::v1::to_serialize(CompoundKey),
+        &[
+            Token::Map { len: Some(1) },
+            Token::Seq { len: Some(2) },
+            Token::I32(1),
+            Token::Map { len: Some(1) },
+            Token::Str("a"),
+            Token::I32(2),
+            Token::MapEnd,
+            Token::SeqEnd,
+            Token::I32(3),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn compound_key_tokens() {
+    use self::SvalToken as Token;
+
+    assert_eq!(
+        vec![
+            Token::MapBegin(Some(1)),
+            Token::SeqBegin(Some(2)),
+            Token::I32(1),
+            Token::MapBegin(Some(1)),
+            Token::Str("a".into()),
+            Token::I32(2),
+            Token::MapEnd,
+            Token::SeqEnd,
+            Token::I32(3),
+            Token::MapEnd,
+        ],
+        sval::test::tokens(&CompoundKey)
+    );
+
+    sval::test::roundtrip(&CompoundKey);
+}
+
+#[test]
+fn sval_to_serde_bytes() {
+    use self::SerdeToken as Token;
+
+    assert_ser_tokens(
+        &sval::serde::v1::to_serialize(value::Bytes::new(&[1u8, 2, 3][..])),
+        &[Token::Bytes(&[1, 2, 3])],
+    );
+}
+
+#[test]
+fn serde_to_sval_bytes() {
+    use self::SvalToken as Token;
+
+    struct Bytes;
+
+    impl serde::Serialize for Bytes {
+        fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_bytes(&[1, 2, 3])
+        }
+    }
+
+    let v = sval::test::tokens(&sval::serde::v1::to_value(Bytes));
+    assert_eq!(vec![Token::Bytes(vec![1, 2, 3])], v);
+}
+
+#[test]
+fn sval_to_serde_f32() {
+    use self::SerdeToken as Token;
+
+    assert_ser_tokens(&sval::serde::v1::to_serialize(1.5f32), &[Token::F32(1.5)]);
+
+    assert_ser_tokens(
+        &sval::serde::v1::to_serialize(vec![1.5f32]),
+        &[Token::Seq { len: Some(1) }, Token::F32(1.5), Token::SeqEnd],
+    );
+}
+
+#[test]
+fn serde_to_sval_f32() {
+    use self::SvalToken as Token;
+
+    let v = sval::test::tokens(&sval::serde::v1::to_value(1.5f32));
+    assert_eq!(vec![Token::Float32(1.5)], v);
+}
+
+#[test]
+fn sval_to_serde_char() {
+    use self::SerdeToken as Token;
+
+    assert_ser_tokens(&sval::serde::v1::to_serialize('€'), &[Token::Char('€')]);
+
+    assert_ser_tokens(
+        &sval::serde::v1::to_serialize(vec!['€']),
+        &[Token::Seq { len: Some(1) }, Token::Char('€'), Token::SeqEnd],
+    );
+}
+
+#[test]
+fn serde_to_sval_char() {
+    use self::SvalToken as Token;
+
+    let v = sval::test::tokens(&sval::serde::v1::to_value('€'));
+    assert_eq!(vec![Token::Char('€')], v);
+}
+
+#[test]
+fn sval_to_json_to_sval_char() {
+    use self::SvalToken as Token;
+
+    let json = serde_json::to_string(&sval::serde::v1::to_serialize('€')).unwrap();
+    assert_eq!("\"€\"", json);
+
+    let v: char = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        vec![Token::Char('€')],
+        sval::test::tokens(&sval::serde::v1::to_value(v))
+    );
+}
+
+#[test]
+fn sval_to_serde_big_integer() {
+    // Human-readable serializers get 128bit integers that fit in 64 bits as 64bit integers
+    assert_eq!(
+        "1",
+        serde_json::to_string(&sval::serde::v1::to_serialize(1u128)).unwrap()
+    );
+    assert_eq!(
+        "-1",
+        serde_json::to_string(&sval::serde::v1::to_serialize(-1i128)).unwrap()
+    );
+
+    // Larger integers are given to them as strings
+    assert_eq!(
+        "\"340282366920938463463374607431768211455\"",
+        serde_json::to_string(&sval::serde::v1::to_serialize(u128::MAX)).unwrap()
+    );
+    assert_eq!(
+        "\"-170141183460469231731687303715884105728\"",
+        serde_json::to_string(&sval::serde::v1::to_serialize(i128::MIN)).unwrap()
+    );
+}
+
+#[test]
+fn serde_to_sval_stream_serializer() {
+    use serde::Serialize;
+    use sval::{
+        buffer::Buffer,
+        serde::v1::StreamSerializer,
+    };
+
+    let v = Struct {
+        a: 1,
+        b: 2,
+        c: Nested { a: 3, b: "Hello!" },
+    };
+
+    let mut buffer = Buffer::new();
+    v.serialize(StreamSerializer::new(&mut buffer)).unwrap();
+
+    assert_eq!(
+        sval::test::tokens(&sval::serde::v1::to_value(&v)),
+        sval::test::tokens(&buffer)
+    );
+}
+
+#[test]
+fn sval_to_serde_error_str() {
+    use self::SerdeToken as Token;
+
+    struct ErrorStr;
+
+    impl Value for ErrorStr {
+        fn stream<'s, 'v>(&'v self, mut stream: value::Stream<'s, 'v>) -> value::Result {
+            stream.error_str("something went wrong")
+        }
+    }
+
+    assert_ser_tokens(
+        &sval::serde::v1::to_serialize(ErrorStr),
+        &[Token::Str("something went wrong")],
+    );
+}
+
+#[test]
+fn roundtrip_primitive() {
+    sval::test::roundtrip(&1u8);
+    sval::test::roundtrip(&1u16);
+    sval::test::roundtrip(&1u32);
+    sval::test::roundtrip(&1u64);
+    sval::test::roundtrip(&-1i8);
+    sval::test::roundtrip(&-1i16);
+    sval::test::roundtrip(&-1i32);
+    sval::test::roundtrip(&-1i64);
+    sval::test::roundtrip(&1.5f32);
+    sval::test::roundtrip(&1.5f64);
+    sval::test::roundtrip(&true);
+    sval::test::roundtrip(&'€');
+    sval::test::roundtrip("a string");
+    sval::test::roundtrip(&value::Bytes::new(&[1u8, 2, 3][..]));
+    sval::test::roundtrip(&Option::None::<i32>);
+}
+
+#[test]
+fn roundtrip_structure() {
+    use std::collections::BTreeMap;
+
+    sval::test::roundtrip(&vec![1, 2, 3]);
+    sval::test::roundtrip(&(1, "a", true));
+
+    let mut map = BTreeMap::new();
+    map.insert("a", vec![Some(1), None]);
+    map.insert("b", vec![]);
+
+    let mut nested = BTreeMap::new();
+    nested.insert("map", map);
+
+    sval::test::roundtrip(&nested);
+    sval::test::roundtrip(&Struct {
+        a: 1,
+        b: 2,
+        c: Nested { a: 3, b: "b" },
+    });
+}
+
+#[test]
+fn serde_wrappers_are_send_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: T) {}
+
+    assert_send_sync(sval::serde::v1::to_serialize(42));
+    assert_send_sync(sval::serde::v1::to_value(42));
+}
+
+#[test]
+fn sval_to_serde_unsized() {
+    assert_ser_tokens(
+        sval::serde::v1::to_serialize_ref("a str"),
+        &[SerdeToken::Str("a str")],
+    );
+
+    let seq: &sval::serde::v1::ToSerialize<[i32]> = sval::serde::v1::to_serialize_ref(&[1, 2][..]);
+    assert_ser_tokens(
+        seq,
+        &[
+            SerdeToken::Seq { len: Some(2) },
+            SerdeToken::I32(1),
+            SerdeToken::I32(2),
+            SerdeToken::SeqEnd,
+        ],
+    );
+
+    let value: &sval::serde::v1::ToSerialize<dyn Value> = &sval::serde::v1::to_serialize(1.5f64);
+    assert_ser_tokens(value, &[SerdeToken::F64(1.5)]);
+}